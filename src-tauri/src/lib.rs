@@ -73,14 +73,25 @@ pub fn run() {
             trace::readtrace_highperf,  // 고성능 파서 명령 추가
             trace::readtrace_to_files,  // 파일 기반 데이터 전송
             trace::trace_lengths,
+            trace::trace_info,
             trace::ufs_latencystats,
             trace::block_latencystats,
             trace::ufs_sizestats,
             trace::block_sizestats,
             trace::ufs_allstats,
+            trace::ufs_hwq_stats,
             trace::block_allstats,
             trace::ufs_continuity_stats,
+            trace::busy_idle_stats,
+            trace::iostat_report,
+            trace::block_ufs_match,
+            trace::top_slow_requests,
+            trace::barrier_impact,
+            trace::lba_heatmap,
+            trace::reuse_distance,
             trace::block_continuity_stats,
+            trace::block_devices,
+            trace::ufs_tag_anomalies,
             trace::ufscustom_latencystats,
             trace::ufscustom_sizestats,
             trace::ufscustom_allstats,
@@ -90,14 +101,30 @@ pub fn run() {
             // Pattern management commands
             trace::add_pattern,
             trace::set_active_pattern,
+            trace::set_active_patterns,
             trace::get_patterns,
             trace::get_active_patterns,
             trace::delete_pattern,
+            trace::export_patterns,
+            trace::import_patterns,
             // Reparse command
             trace::reparse_trace,
             trace::delete_parquet_files,
             // Pattern testing
             trace::test_regex_pattern,
+            trace::dry_run_pattern,
+            trace::generate_pattern,
+            trace::get_pattern_fields,
+            trace::load_event_formats,
+            trace::get_event_parsers,
+            trace::parse_event_log,
+            trace::parse_generic_events,
+            trace::filter_events,
+            trace::event_summary,
+            trace::marker_sections,
+            trace::marker_section_stats,
+            trace::set_clock_sync,
+            trace::get_clock_sync,
             trace::delete_folder,
             trace::cancel_trace_process,
             trace::reset_cancel_signal,
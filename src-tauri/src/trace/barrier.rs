@@ -0,0 +1,385 @@
+// src-tauri/src/trace/barrier.rs
+//
+// flush / FUA / discard / UNMAP 영향 분석
+// 배리어(SYNCHRONIZE CACHE 0x35, block flush / preflush / FUA)와 유지보수 명령(UNMAP 0x42, block discard)을 찾아
+// 명령 자체의 레이턴시와, 직후 요청들의 레이턴시를 baseline과 비교한다.
+// - 직후 N개: 배리어 issue 이후에 issue된 일반 요청 N개
+// - 직후 T ms: 배리어 issue ~ 배리어 complete + T 사이에 issue된 일반 요청
+// - baseline: 분석 구간 전체 일반 요청의 op(read / write / other)별 평균 레이턴시
+//   직후 요청의 op 구성이 달라도 비교할 수 있도록 ratio = 실제 평균 / op별 baseline 평균
+// discard / UNMAP으로 해제된 lba 양은 interval_ms 구간별로 집계한다.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::trace::block::parse_rwbs;
+use crate::trace::crosslayer::ufs_op;
+use crate::trace::forensics::{block_requests, ufs_requests, TracedRequest};
+use crate::trace::timestamp;
+use crate::trace::utils::calculate_statistics;
+use crate::trace::LatencySummary;
+
+// 결과에 포함할 최대 명령 수 (종류별 요약은 전체 기준)
+const MAX_REPORTED_COMMANDS: usize = 10_000;
+// discard 구간 최대 수
+const MAX_BUCKETS: usize = 100_000;
+
+// 배리어 / 유지보수 명령 종류
+pub fn command_kind(trace_type: &str, op: &str) -> Option<&'static str> {
+    match trace_type {
+        "ufs" => match op {
+            "0x35" => Some("sync_cache"),
+            "0x42" => Some("unmap"),
+            _ => None,
+        },
+        _ => {
+            let rwbs = parse_rwbs(op);
+            match rwbs.op {
+                "flush" => Some("flush"),
+                "discard" => Some("discard"),
+                _ if rwbs.preflush => Some("preflush"),
+                _ if rwbs.fua => Some("fua"),
+                _ => None,
+            }
+        }
+    }
+}
+
+// baseline 비교용 op 분류
+fn op_class(trace_type: &str, op: &str) -> &'static str {
+    let op = match trace_type {
+        "ufs" => ufs_op(op),
+        _ => parse_rwbs(op).op,
+    };
+    match op {
+        "read" | "write" => op,
+        _ => "other",
+    }
+}
+
+// 해제된 lba 양의 바이트 환산 (UFS 4KB, block 512B 단위)
+fn unit_bytes(trace_type: &str) -> u64 {
+    if trace_type == "ufs" {
+        4096
+    } else {
+        512
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct FollowingImpact {
+    pub requests: usize,
+    pub avg_latency_ms: f64,
+    pub max_latency_ms: f64,
+    // 같은 op 구성일 때 기대되는 평균 레이턴시
+    pub baseline_latency_ms: f64,
+    // avg / baseline (1보다 크면 직후 요청이 느려짐)
+    pub ratio: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BarrierCommand {
+    // complete 행 번호 (트레이스 전체 기준)
+    pub index: usize,
+    pub issue_index: Option<usize>,
+    pub kind: String,
+    pub op: String,
+    pub lba: u64,
+    pub size: u32,
+    pub issue_time: f64,
+    pub complete_time: f64,
+    pub latency_ms: f64,
+    pub next_requests: FollowingImpact,
+    pub next_window: FollowingImpact,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BarrierKindSummary {
+    pub count: usize,
+    pub latency: LatencySummary,
+    pub avg_next_requests_ratio: f64,
+    pub avg_next_window_ratio: f64,
+    // 직후 요청이 baseline보다 2배 이상 느렸던 명령 수
+    pub slowdowns: usize,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct DiscardBucket {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub commands: usize,
+    pub blocks: u64, // trace 단위 (UFS 4KB, block sector)
+    pub bytes: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BarrierReport {
+    pub trace_type: String,
+    pub next_n: usize,
+    pub window_ms: f64,
+    pub interval_ms: f64,
+    // op별 baseline 평균 레이턴시 (ms)
+    pub baseline: BTreeMap<String, f64>,
+    pub summary: BTreeMap<String, BarrierKindSummary>,
+    // 명령 목록이 MAX_REPORTED_COMMANDS에서 잘렸는지 여부
+    pub truncated: bool,
+    pub commands: Vec<BarrierCommand>,
+    pub discard_total_bytes: u64,
+    pub discard_timeline: Vec<DiscardBucket>,
+}
+
+fn latency_ms(request: &TracedRequest) -> f64 {
+    timestamp::ns_to_ms(request.complete - request.issue)
+}
+
+fn following_impact(
+    trace_type: &str,
+    requests: &[&TracedRequest],
+    baseline: &HashMap<&'static str, f64>,
+) -> FollowingImpact {
+    if requests.is_empty() {
+        return FollowingImpact::default();
+    }
+    let n = requests.len() as f64;
+    let avg = requests.iter().map(|r| latency_ms(r)).sum::<f64>() / n;
+    let expected = requests
+        .iter()
+        .map(|r| baseline.get(op_class(trace_type, &r.op)).copied().unwrap_or(0.0))
+        .sum::<f64>()
+        / n;
+    FollowingImpact {
+        requests: requests.len(),
+        avg_latency_ms: avg,
+        max_latency_ms: requests.iter().map(|r| latency_ms(r)).fold(0.0, f64::max),
+        baseline_latency_ms: expected,
+        ratio: if expected > 0.0 { avg / expected } else { 0.0 },
+    }
+}
+
+/// 배리어 / 유지보수 명령 영향 분석 (요청은 트레이스 종류에 맞게 짝지어진 상태)
+pub fn analyze(
+    trace_type: &str,
+    requests: &[TracedRequest],
+    next_n: usize,
+    window: i64,
+    interval: i64,
+) -> Result<BarrierReport, String> {
+    let mut by_issue: Vec<&TracedRequest> = requests.iter().collect();
+    by_issue.sort_by_key(|r| (r.issue, r.index));
+    let (barriers, normal): (Vec<&TracedRequest>, Vec<&TracedRequest>) = by_issue
+        .into_iter()
+        .partition(|r| command_kind(trace_type, &r.op).is_some());
+
+    // op별 baseline (일반 요청 평균)
+    let mut sums: HashMap<&'static str, (f64, usize)> = HashMap::new();
+    for r in &normal {
+        let entry = sums.entry(op_class(trace_type, &r.op)).or_insert((0.0, 0));
+        entry.0 += latency_ms(r);
+        entry.1 += 1;
+    }
+    let baseline: HashMap<&'static str, f64> = sums
+        .into_iter()
+        .map(|(op, (sum, count))| (op, sum / count as f64))
+        .collect();
+
+    let mut commands = Vec::with_capacity(barriers.len());
+    for b in &barriers {
+        let start = normal.partition_point(|r| r.issue < b.issue);
+        let next: Vec<&TracedRequest> = normal[start..].iter().take(next_n).copied().collect();
+        let window_end = b.complete + window;
+        let in_window: Vec<&TracedRequest> = normal[start..]
+            .iter()
+            .take_while(|r| r.issue <= window_end)
+            .copied()
+            .collect();
+        commands.push(BarrierCommand {
+            index: b.index,
+            issue_index: b.issue_index,
+            kind: command_kind(trace_type, &b.op).unwrap_or_default().to_string(),
+            op: b.op.clone(),
+            lba: b.lba,
+            size: b.size,
+            issue_time: timestamp::ns_to_secs(b.issue),
+            complete_time: timestamp::ns_to_secs(b.complete),
+            latency_ms: latency_ms(b),
+            next_requests: following_impact(trace_type, &next, &baseline),
+            next_window: following_impact(trace_type, &in_window, &baseline),
+        });
+    }
+
+    // 종류별 요약
+    let mut grouped: BTreeMap<String, Vec<&BarrierCommand>> = BTreeMap::new();
+    for c in &commands {
+        grouped.entry(c.kind.clone()).or_default().push(c);
+    }
+    let summary = grouped
+        .into_iter()
+        .map(|(kind, list)| {
+            let n = list.len() as f64;
+            let mut latencies: Vec<f64> = list.iter().map(|c| c.latency_ms).collect();
+            let summary = BarrierKindSummary {
+                count: list.len(),
+                latency: calculate_statistics(&mut latencies),
+                avg_next_requests_ratio: list.iter().map(|c| c.next_requests.ratio).sum::<f64>() / n,
+                avg_next_window_ratio: list.iter().map(|c| c.next_window.ratio).sum::<f64>() / n,
+                slowdowns: list
+                    .iter()
+                    .filter(|c| c.next_requests.ratio >= 2.0 || c.next_window.ratio >= 2.0)
+                    .count(),
+            };
+            (kind, summary)
+        })
+        .collect();
+
+    // discard / UNMAP 해제량 타임라인
+    let discards: Vec<&&TracedRequest> = barriers
+        .iter()
+        .filter(|b| matches!(command_kind(trace_type, &b.op), Some("unmap" | "discard")))
+        .collect();
+    let mut discard_timeline = Vec::new();
+    if let (Some(first), Some(last)) = (discards.first(), discards.last()) {
+        if interval <= 0 {
+            return Err("구간 길이(interval_ms)는 0보다 커야 합니다".to_string());
+        }
+        let origin = first.issue - first.issue.rem_euclid(interval);
+        let count = ((last.issue - origin) / interval + 1) as usize;
+        if count > MAX_BUCKETS {
+            return Err(format!(
+                "구간이 너무 많습니다 ({}개, 최대 {}개) - interval_ms를 늘려 주세요",
+                count, MAX_BUCKETS
+            ));
+        }
+        discard_timeline = (0..count as i64)
+            .map(|i| DiscardBucket {
+                start_time: timestamp::ns_to_secs(origin + i * interval),
+                end_time: timestamp::ns_to_secs(origin + (i + 1) * interval),
+                ..Default::default()
+            })
+            .collect();
+        for d in &discards {
+            let bucket = &mut discard_timeline[((d.issue - origin) / interval) as usize];
+            bucket.commands += 1;
+            bucket.blocks += d.size as u64;
+            bucket.bytes += d.size as u64 * unit_bytes(trace_type);
+        }
+    }
+
+    let truncated = commands.len() > MAX_REPORTED_COMMANDS;
+    commands.truncate(MAX_REPORTED_COMMANDS);
+    Ok(BarrierReport {
+        trace_type: trace_type.to_string(),
+        next_n,
+        window_ms: timestamp::ns_to_ms(window),
+        interval_ms: timestamp::ns_to_ms(interval),
+        baseline: baseline.into_iter().map(|(op, avg)| (op.to_string(), avg)).collect(),
+        summary,
+        truncated,
+        commands,
+        discard_total_bytes: discard_timeline.iter().map(|b| b.bytes).sum(),
+        discard_timeline,
+    })
+}
+
+/// barrier_impact 명령 - trace_type: "ufs" | "block"
+/// device는 block 장치 필터 ("major:minor") - 직후 요청 / baseline은 같은 장치 요청끼리 비교
+#[allow(clippy::too_many_arguments)]
+pub fn barrier_impact(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    next_n: usize,
+    window_ms: f64,
+    interval_ms: f64,
+    device: Option<&str>,
+) -> Result<String, String> {
+    let mut requests = match trace_type {
+        "ufs" => ufs_requests(logname)?,
+        "block" => block_requests(logname, device)?,
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    };
+    let from = time_from.map(timestamp::secs_to_ns);
+    let to = time_to.map(timestamp::secs_to_ns);
+    requests.retain(|r| from.is_none_or(|t| r.complete >= t) && to.is_none_or(|t| r.complete <= t));
+    if requests.is_empty() {
+        return Err(format!("분석할 {} 요청이 없습니다", trace_type));
+    }
+
+    let to_ns = |ms: f64| (ms.max(0.0) * timestamp::NS_PER_MS as f64).round() as i64;
+    let report = analyze(trace_type, &requests, next_n, to_ns(window_ms), to_ns(interval_ms))?;
+    println!(
+        "🚧 {} 배리어 / discard 명령 {}개 분석 (해제 {} bytes)",
+        trace_type,
+        report.summary.values().map(|s| s.count).sum::<usize>(),
+        report.discard_total_bytes
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(index: usize, op: &str, issue_ms: i64, complete_ms: i64, size: u32) -> TracedRequest {
+        TracedRequest {
+            index,
+            issue_index: None,
+            op: op.to_string(),
+            barrier: false,
+            lba: 0,
+            size,
+            issue: issue_ms * timestamp::NS_PER_MS,
+            complete: complete_ms * timestamp::NS_PER_MS,
+            dtoc: (complete_ms - issue_ms) as f64,
+            ctod: 0.0,
+            ctoc: 0.0,
+            qd_at_issue: 0,
+            qd_at_complete: 0,
+            process: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_command_kind() {
+        assert_eq!(command_kind("ufs", "0x35"), Some("sync_cache"));
+        assert_eq!(command_kind("ufs", "0x2a"), None);
+        assert_eq!(command_kind("block", "FF"), Some("flush"));
+        assert_eq!(command_kind("block", "DS"), Some("discard"));
+        assert_eq!(command_kind("block", "FWS"), Some("preflush"));
+        assert_eq!(command_kind("block", "WFS"), Some("fua"));
+        assert_eq!(command_kind("block", "WS"), None);
+    }
+
+    #[test]
+    fn test_impact_against_baseline() {
+        let requests = vec![
+            req(0, "0x2a", 0, 1, 8),
+            req(1, "0x28", 2, 3, 8),
+            req(2, "0x35", 10, 20, 0),
+            req(3, "0x2a", 12, 16, 8), // flush 중 write 4ms
+            req(4, "0x28", 22, 24, 8), // flush 직후 read 2ms
+            req(5, "0x42", 1500, 1502, 256),
+            req(6, "0x2a", 1600, 1601, 8),
+        ];
+        let ms = timestamp::NS_PER_MS;
+        let report = analyze("ufs", &requests, 2, 5 * ms, 1000 * ms).unwrap();
+
+        // baseline: write (1 + 4 + 1) / 3 = 2, read (1 + 2) / 2 = 1.5
+        assert_eq!(report.baseline["write"], 2.0);
+        assert_eq!(report.baseline["read"], 1.5);
+
+        let flush = &report.commands[0];
+        assert_eq!((flush.kind.as_str(), flush.latency_ms), ("sync_cache", 10.0));
+        assert_eq!(flush.next_requests.requests, 2);
+        assert_eq!(flush.next_requests.avg_latency_ms, 3.0);
+        assert_eq!(flush.next_requests.baseline_latency_ms, 1.75);
+        // 완료 후 5ms까지 issue된 요청 (3, 4)
+        assert_eq!(flush.next_window.requests, 2);
+        assert_eq!(flush.next_window.max_latency_ms, 4.0);
+
+        assert_eq!(report.summary["unmap"].count, 1);
+        assert_eq!(report.discard_timeline.len(), 1);
+        assert_eq!(report.discard_timeline[0].start_time, 1.0);
+        assert_eq!(report.discard_total_bytes, 256 * 4096);
+    }
+}
@@ -1,18 +1,23 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs::{create_dir_all, File};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, BooleanArray, Float64Array, StringArray, UInt32Array, UInt64Array};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, UInt32Array, UInt64Array};
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
-use arrow::temporal_conversions::MILLISECONDS;
 use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
 use tauri::Emitter;
 
+use crate::trace::clock_sync;
+use crate::trace::timestamp;
 use crate::trace::filter::{filter_block_data};
+use crate::trace::provenance::TraceProvenance;
 use crate::trace::utils::{
     calculate_statistics, create_range_key, initialize_ranges, normalize_io_type, parse_time_to_ms,
+    pattern_array, pattern_field,
 };
 use crate::trace::{
     Block, ContinuityCount, ContinuityStats, LatencyStat, LatencyStats, LatencyValue, SizeStats,
@@ -31,6 +36,8 @@ pub struct LatencyStatsParams {
     pub col_to: Option<f64>,
     pub thresholds: Vec<String>,
     pub group: bool,
+    pub device: Option<String>, // "major:minor" 장치 필터
+    pub group_by_device: bool,  // 장치별로 나누어 통계 (키: "major:minor io_type")
 }
 
 // 크기 통계 분석을 위한 매개변수 구조체
@@ -44,6 +51,8 @@ pub struct SizeStatsParams {
     pub col_from: Option<f64>,
     pub col_to: Option<f64>,
     pub group: bool,
+    pub device: Option<String>, // "major:minor" 장치 필터
+    pub group_by_device: bool,  // 장치별로 나누어 통계 (키: "major:minor io_type")
 }
 
 // 종합 통계 분석을 위한 매개변수 구조체
@@ -57,13 +66,223 @@ pub struct AllStatsParams {
     pub col_to: Option<f64>,
     pub thresholds: Vec<String>,
     pub group: bool,
+    pub device: Option<String>, // "major:minor" 장치 필터
+    pub group_by_device: bool,  // 장치별로 나누어 통계 (키: "major:minor io_type")
+}
+
+// 장치 키 ("major:minor")
+pub fn device_key(block: &Block) -> String {
+    format!("{}:{}", block.devmajor, block.devminor)
+}
+
+// "major:minor" 문자열을 (devmajor, devminor)로 변환
+pub fn parse_device(device: &str) -> Result<(u32, u32), String> {
+    let (major, minor) = device
+        .trim()
+        .split_once(':')
+        .ok_or_else(|| format!("장치는 major:minor 형식이어야 합니다: {}", device))?;
+    let major = major.trim().parse().map_err(|_| format!("잘못된 devmajor: {}", device))?;
+    let minor = minor.trim().parse().map_err(|_| format!("잘못된 devminor: {}", device))?;
+    Ok((major, minor))
+}
+
+// 장치 필터 적용 (device가 없거나 비어 있으면 전체)
+pub fn filter_device(blocks: Vec<Block>, device: Option<&str>) -> Result<Vec<Block>, String> {
+    match device.filter(|d| !d.trim().is_empty()) {
+        Some(device) => {
+            let (major, minor) = parse_device(device)?;
+            Ok(blocks
+                .into_iter()
+                .filter(|b| b.devmajor == major && b.devminor == minor)
+                .collect())
+        }
+        None => Ok(blocks),
+    }
+}
+
+// 통계 그룹 키 - io_type(group이면 첫 글자), group_by_device면 앞에 장치 키 추가
+fn group_key(io_key: String, block: &Block, group_by_device: bool) -> String {
+    if group_by_device {
+        format!("{} {}", device_key(block), io_key)
+    } else {
+        io_key
+    }
+}
+
+// 장치별 요약 (block_devices 명령 결과)
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct DeviceSummary {
+    pub device: String,
+    pub devmajor: u32,
+    pub devminor: u32,
+    pub issues: usize,
+    pub completes: usize,
+    pub total_bytes: u64,
+    pub max_qd: u32,
+}
+
+// 트레이스에 나타난 장치 목록 (major:minor 순)
+pub fn summarize_devices(blocks: &[Block]) -> Vec<DeviceSummary> {
+    let mut devices: BTreeMap<(u32, u32), DeviceSummary> = BTreeMap::new();
+    for block in blocks {
+        let summary = devices
+            .entry((block.devmajor, block.devminor))
+            .or_insert_with(|| DeviceSummary {
+                device: device_key(block),
+                devmajor: block.devmajor,
+                devminor: block.devminor,
+                ..Default::default()
+            });
+        match block.action.as_str() {
+            "block_rq_issue" => {
+                summary.issues += 1;
+                summary.total_bytes += block.size as u64 * 512;
+            }
+            "block_rq_complete" => summary.completes += 1,
+            _ => {}
+        }
+        summary.max_qd = summary.max_qd.max(block.qd);
+    }
+    devices.into_values().collect()
+}
+
+/// block_devices 명령 - 장치별 요청 수 / 바이트 / 최대 QD
+pub fn devices(logname: &str) -> Result<String, String> {
+    let blocks = filter_block_data(logname, None, None, "sector", None, None, None)?;
+    let devices = summarize_devices(&blocks);
+    println!("💽 Block 장치 {}개 발견", devices.len());
+    serde_json::to_string(&devices).map_err(|e| e.to_string())
+}
+
+// 요청 추적 / 중복 판정 결과 (parquet 출처 정보와 파싱 결과에 기록)
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BlockDedupReport {
+    // 중복 제거(dedup) 옵션 사용 여부
+    pub enabled: bool,
+    // block_rq_requeue 이벤트 수와 그 뒤 다시 issue된 요청 수
+    pub requeues: usize,
+    pub reissues: usize,
+    // 같은 요청(장치, sector, 연산, size)이 완료 전에 다시 issue된 횟수
+    // requeue 이벤트가 없는 트레이스에서 dedup이 켜져 있으면 재전송으로 보고 제거
+    pub repeat_issues: usize,
+    // block_rq_merge / block_bio_*merge 이벤트 수와 그중 완료 대기 중인 요청에 반영된 수
+    pub merges: usize,
+    pub merged_in_flight: usize,
+    // flush 시퀀스: FLUSH 요청(FF), PREFLUSH / FUA가 붙은 요청 - 요청마다 한 항목에만 집계
+    // (FLUSH 연산이 우선, PREFLUSH + FUA 요청은 preflush로 집계)
+    pub flush_requests: usize,
+    pub preflush_requests: usize,
+    pub fua_requests: usize,
+    // issue 없이 들어온 size 0 write 완료 (flush 시퀀스가 끝난 원본 요청, dedup 시 제거)
+    pub flush_seq_completions: usize,
+    // 짝이 없는 완료 (트레이스 시작 전 issue) / 트레이스 끝까지 완료되지 않은 issue
+    pub unmatched_completes: usize,
+    pub unmatched_issues: usize,
+    // dedup으로 제거된 이벤트 수
+    pub removed: usize,
+}
+
+// rwbs(io_type) 해석 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rwbs {
+    pub op: &'static str,
+    pub preflush: bool,
+    pub fua: bool,
+}
+
+// rwbs 해석 - [F(PREFLUSH)] 연산(R/W/D/F/N) [F(FUA)] [A][S][M]...
+// 첫 글자 F 뒤에 연산 문자가 더 있으면 PREFLUSH, "F" 단독이면 FLUSH 연산
+pub fn parse_rwbs(io_type: &str) -> Rwbs {
+    let bytes = io_type.as_bytes();
+    let (preflush, rest) = match bytes {
+        [b'F', rest @ ..] if !rest.is_empty() => (true, rest),
+        _ => (false, bytes),
+    };
+    let op = match rest.first() {
+        Some(b'R') => "read",
+        Some(b'W') => "write",
+        Some(b'D') => "discard",
+        Some(b'F') => "flush",
+        _ => "other",
+    };
+    let fua = rest.len() > 1 && rest[1..].contains(&b'F');
+    Rwbs { op, preflush, fua }
+}
+
+// rwbs의 연산 종류 (read / write / discard / flush / other)
+pub fn block_op(io_type: &str) -> &'static str {
+    parse_rwbs(io_type).op
+}
+
+// 장치(devmajor, devminor)별 QD / ctoc / ctod / 연속성 계산 상태
+#[derive(Debug, Default)]
+struct DeviceState {
+    qd: u32,
+    last_complete_time: Option<i64>,
+    last_complete_qd0_time: Option<i64>,
+    prev_end_sector: Option<u64>,
+    prev_io_type: Option<&'static str>,
+    first_c: bool,
+    first_complete_time: i64,
+}
+
+// 완료 대기 중인 issue (최초 issue 시각, size)
+type PendingIssue = (i64, u32);
+// 요청 키 (devmajor, devminor, 시작 sector, 연산)
+type RequestKey = (u32, u32, u64, &'static str);
+
+// 키의 대기열에서 요청을 꺼내고, 비면 키를 제거 (merge 탐색 범위 유지)
+fn take_pending(
+    pending: &mut HashMap<RequestKey, VecDeque<PendingIssue>>,
+    key: &RequestKey,
+    newest: bool,
+) -> Option<PendingIssue> {
+    let queue = pending.get_mut(key)?;
+    let taken = if newest { queue.pop_back() } else { queue.pop_front() };
+    if queue.is_empty() {
+        pending.remove(key);
+    }
+    taken
+}
+
+// merge 이벤트를 완료 대기 중인 요청에 반영
+// back merge: 끝 sector가 merge 시작 sector인 요청의 size 확장
+// front merge: merge 끝 sector에서 시작하는 요청을 merge 시작 sector 키로 옮기고 size 확장
+fn apply_merge(in_flight: &mut HashMap<RequestKey, VecDeque<PendingIssue>>, merge: &Block, op: &'static str) -> bool {
+    let (major, minor) = (merge.devmajor, merge.devminor);
+    let back = in_flight.iter_mut().find_map(|(&(key_major, key_minor, start, key_op), queue)| {
+        if (key_major, key_minor) != (major, minor) || key_op != op {
+            return None;
+        }
+        queue.iter_mut().rev().find(|pending| start + pending.1 as u64 == merge.sector)
+    });
+    if let Some(pending) = back {
+        pending.1 += merge.size;
+        return true;
+    }
+
+    let front_key = (major, minor, merge.sector + merge.size as u64, op);
+    match take_pending(in_flight, &front_key, true) {
+        Some((issue_time, size)) => {
+            in_flight
+                .entry((major, minor, merge.sector, op))
+                .or_default()
+                .push_back((issue_time, size + merge.size));
+            true
+        }
+        None => false,
+    }
 }
 
 // Block 레이턴시 후처리 함수
-pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
+// issue / requeue / complete를 (장치, sector, 연산) 단위로 짝지어 QD, dtoc, ctoc, ctod, 연속성을 계산한다.
+// dedup이 켜져 있으면 requeue 이벤트 없는 재전송과 flush 시퀀스 종료 완료를 제거한다.
+pub fn block_bottom_half_latency_process(block_list: Vec<Block>, dedup: bool) -> (Vec<Block>, BlockDedupReport) {
+    let mut report = BlockDedupReport { enabled: dedup, ..Default::default() };
+
     // 이벤트가 없으면 빈 벡터 반환
     if block_list.is_empty() {
-        return block_list;
+        return (block_list, report);
     }
     
     // 시작 시간 기록
@@ -71,10 +290,11 @@ pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
     println!("\n🔄 ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("📊 Block Latency 후처리 시작");
     println!("   총 이벤트 수: {}", block_list.len());
+    println!("   중복 제거: {}", if dedup { "사용" } else { "사용 안 함" });
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
     // 1. 정렬 여부 확인 (이미 정렬되어 있으면 정렬 스킵)
-    println!("\n[1/3] ⏱️  데이터 순서 확인 중...");
+    println!("\n[1/2] ⏱️  데이터 순서 확인 중...");
     let sort_start = std::time::Instant::now();
     let mut sorted_blocks = block_list;
     let mut needs_sort = false;
@@ -87,9 +307,7 @@ pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
     
     let sort_elapsed = if needs_sort {
         println!("      ⚠️  정렬되지 않은 데이터 감지, 정렬 중...");
-        sorted_blocks.sort_unstable_by(|a, b| {
-            a.time.partial_cmp(&b.time).unwrap_or(std::cmp::Ordering::Equal)
-        });
+        sorted_blocks.sort_by_key(|b| b.time);
         let elapsed = sort_start.elapsed().as_secs_f64();
         println!("      ✅ 정렬 완료: {:.2}초", elapsed);
         elapsed
@@ -99,22 +317,31 @@ pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
         elapsed
     };
 
-    // 2. 중복 block_rq_issue 제거 (사전 작업)
-    println!("\n[2/3] 🔍 중복 이벤트 필터링 중...");
-    let dedup_start = std::time::Instant::now();
-    // 키를 (sector, io_type, size)로 확장하여 동일 크기의 요청만 중복으로 처리
-    let mut processed_issues = HashSet::with_capacity(sorted_blocks.len() / 4);
-    let mut deduplicated_blocks = Vec::with_capacity(sorted_blocks.len());
+    // requeue 이벤트가 기록된 트레이스면 재전송은 requeue로 추적하고, 반복 issue는 실제 요청으로 본다
+    let has_requeue_events = sorted_blocks.iter().any(|b| b.action == "block_rq_requeue");
 
-    // 프로그레스 카운터 최적화 - 중복 제거 단계
+    // 2. 요청 추적 및 Latency / 연속성 계산
+    println!("\n[2/2] ⚙️  요청 추적 및 Latency / 연속성 계산 중...");
+    let processing_start = std::time::Instant::now();
+    let mut filtered_blocks = Vec::with_capacity(sorted_blocks.len());
+    // 요청 키와 QD / ctoc / ctod / 연속성은 모두 장치(devmajor, devminor)별로 계산
+    // (dm-crypt, loop, zram 등 여러 장치가 섞인 트레이스)
+    // 같은 키의 요청이 여러 개 대기 중이면 issue 순서대로 완료와 짝지음
+    let mut in_flight: HashMap<RequestKey, VecDeque<PendingIssue>> =
+        HashMap::with_capacity(sorted_blocks.len() / 4);
+    // requeue되어 다시 issue를 기다리는 요청
+    let mut requeued: HashMap<RequestKey, VecDeque<PendingIssue>> = HashMap::new();
+    let mut devices: HashMap<(u32, u32), DeviceState> = HashMap::new();
+
+    // 프로그레스 카운터 최적화 - 5% 간격
     let total_blocks = sorted_blocks.len();
-    let report_threshold = total_blocks / 20; // 5% 간격
+    let report_threshold = total_blocks / 20;
     
-    for (idx, block) in sorted_blocks.into_iter().enumerate() {
+    for (idx, mut block) in sorted_blocks.into_iter().enumerate() {
         // 진행 상황 보고 (5% 간격, 모듈로 연산 사용)
         if report_threshold > 0 && idx % report_threshold == 0 && idx > 0 {
             let progress = (idx * 100) / total_blocks;
-            let elapsed = dedup_start.elapsed().as_secs_f64();
+            let elapsed = processing_start.elapsed().as_secs_f64();
             let rate = idx as f64 / elapsed;
             let remaining = total_blocks - idx;
             let eta = if rate > 0.0 { remaining as f64 / rate } else { 0.0 };
@@ -122,137 +349,140 @@ pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
                      progress, idx, total_blocks, rate, eta);
         }
         
-        // 성능 최적화: io_type 파싱 함수화
-        let io_operation = get_io_operation(&block.io_type);
-
-        if block.action == "block_rq_issue" {
-            // 키를 (sector, io_operation, size)로 확장
-            let key = (block.sector, io_operation, block.size);
-
-            if processed_issues.contains(&key) {
-                continue;
-            }
-
-            processed_issues.insert(key);
-        } else if block.action == "block_rq_complete" {
-            // write 이고 size가 0인 경우에 Flush 표시가 2번 발생 (중복 제거) FF->WS 이런식으로 들어올 수 있음
-            if block.io_type.starts_with('W') && block.size == 0 {
-                continue;
-            }
-
-            let key = (block.sector, io_operation, block.size);
-            processed_issues.remove(&key);
-        }
-
-        deduplicated_blocks.push(block);
-    }
-
-    let dedup_elapsed = dedup_start.elapsed().as_secs_f64();
-    let dedup_rate = deduplicated_blocks.len() as f64 / dedup_elapsed;
-    println!("      ✅ 중복 제거 완료: {} 이벤트 | {:.2}초 | {:.0} events/s", 
-             deduplicated_blocks.len(), dedup_elapsed, dedup_rate);
-    
-    // 메모리 최적화를 위한 용량 조절
-    processed_issues.clear();
-    processed_issues.shrink_to_fit();
-    
-    // 3. 중복이 제거된 데이터에 대해 후처리 진행
-    // (연속성, Latency 등 처리)
-    println!("\n[3/3] ⚙️  Latency 및 연속성 계산 중...");
-    let processing_start = std::time::Instant::now();
-    let mut filtered_blocks = Vec::with_capacity(deduplicated_blocks.len());
-    let mut req_times: HashMap<(u64, &'static str), f64> = HashMap::with_capacity(deduplicated_blocks.len() / 4);
-    let mut current_qd: u32 = 0;
-    let mut last_complete_time: Option<f64> = None;
-    let mut last_complete_qd0_time: Option<f64> = None;
-    let mut prev_end_sector: Option<u64> = None;
-    let mut prev_io_type: Option<&'static str> = None;
-    let mut first_c: bool = false;
-    let mut first_complete_time: f64 = 0.0;
-
-    // 프로그레스 카운터 최적화 - Latency 계산 단계
-    let total_dedup = deduplicated_blocks.len();
-    let report_threshold_2 = total_dedup / 20; // 5% 간격
-    
-    for (idx, mut block) in deduplicated_blocks.into_iter().enumerate() {
-        // 진행 상황 보고 (5% 간격, 모듈로 연산 사용)
-        if report_threshold_2 > 0 && idx % report_threshold_2 == 0 && idx > 0 {
-            let progress = (idx * 100) / total_dedup;
-            let elapsed = processing_start.elapsed().as_secs_f64();
-            let rate = idx as f64 / elapsed;
-            let remaining = total_dedup - idx;
-            let eta = if rate > 0.0 { remaining as f64 / rate } else { 0.0 };
-            println!("      📌 진행률: {}% ({}/{}) | 속도: {:.0} events/s | 예상 남은 시간: {:.1}초", 
-                     progress, idx, total_dedup, rate, eta);
-        }
-        
         // 기본적으로 continuous를 false로 설정
         block.continuous = false;
 
-        // 성능 최적화: io_type 파싱 함수 재사용
-        let io_operation = get_io_operation(&block.io_type);
+        let rwbs = parse_rwbs(&block.io_type);
+        let io_operation = rwbs.op;
 
-        let key = (block.sector, io_operation);
+        let key = (block.devmajor, block.devminor, block.sector, io_operation);
+        let device = devices.entry((block.devmajor, block.devminor)).or_default();
 
-        // 성능 최적화: 문자열 비교를 바이트 비교로
-        let action_bytes = block.action.as_bytes();
-
-        if action_bytes == b"block_rq_issue" {
-            // 연속성 체크
-            if io_operation != "other" {
-                if let (Some(end_sector), Some(prev_type)) =
-                    (prev_end_sector, prev_io_type)
-                {
-                    if block.sector == end_sector && io_operation == prev_type {
-                        block.continuous = true;
+        match block.action.as_str() {
+            "block_rq_issue" => {
+                // requeue된 요청의 재전송: dtoc는 최초 issue 기준
+                let reissued = take_pending(&mut requeued, &key, false);
+                let issue = match reissued {
+                    Some(pending) => {
+                        report.reissues += 1;
+                        pending
+                    }
+                    None => {
+                        let repeated = in_flight
+                            .get(&key)
+                            .is_some_and(|q| q.iter().any(|&(_, size)| size == block.size));
+                        if repeated {
+                            report.repeat_issues += 1;
+                            if dedup && !has_requeue_events {
+                                report.removed += 1;
+                                continue;
+                            }
+                        }
+                        if io_operation == "flush" {
+                            report.flush_requests += 1;
+                        } else if rwbs.preflush {
+                            report.preflush_requests += 1;
+                        } else if rwbs.fua {
+                            report.fua_requests += 1;
+                        }
+                        (block.time, block.size)
+                    }
+                };
+
+                // 연속성 체크 (flush / other 제외)
+                if matches!(io_operation, "read" | "write" | "discard") {
+                    if let (Some(end_sector), Some(prev_type)) =
+                        (device.prev_end_sector, device.prev_io_type)
+                    {
+                        if block.sector == end_sector && io_operation == prev_type {
+                            block.continuous = true;
+                        }
                     }
-                }
 
-                // 현재 요청의 끝 sector 및 io_type 업데이트
-                prev_end_sector = Some(block.sector + block.size as u64);
-                prev_io_type = Some(io_operation);
-            }
+                    // 현재 요청의 끝 sector 및 io_type 업데이트
+                    device.prev_end_sector = Some(block.sector + block.size as u64);
+                    device.prev_io_type = Some(io_operation);
+                }
 
-            // 요청 시간 기록 및 QD 업데이트
-            req_times.insert(key, block.time);
-            current_qd += 1;
+                // 요청 시간 기록 및 QD 업데이트
+                in_flight.entry(key).or_default().push_back(issue);
+                device.qd += 1;
 
-            if current_qd == 1 {
-                if let Some(t) = last_complete_qd0_time {
-                    block.ctod = (block.time - t) * MILLISECONDS as f64;
+                if device.qd == 1 {
+                    if let Some(t) = device.last_complete_qd0_time {
+                        block.ctod = timestamp::ns_to_ms(block.time - t);
+                    }
+                    device.first_c = true;
+                    device.first_complete_time = block.time;
                 }
-                first_c = true;
-                first_complete_time = block.time;
             }
-        } else if action_bytes == b"block_rq_complete" {
-            // complete는 항상 continuous = false
-            if let Some(first_issue_time) = req_times.remove(&key) {
-                block.dtoc = (block.time - first_issue_time) * MILLISECONDS as f64;
+            "block_rq_requeue" => {
+                // 드라이버에서 되돌아온 요청 - 다시 issue될 때까지 QD에서 제외
+                report.requeues += 1;
+                if let Some(pending) = take_pending(&mut in_flight, &key, true) {
+                    requeued.entry(key).or_default().push_back(pending);
+                    device.qd = device.qd.saturating_sub(1);
+                }
             }
+            "block_rq_complete" => {
+                match take_pending(&mut in_flight, &key, false) {
+                    Some((issue_time, _)) => {
+                        block.dtoc = timestamp::ns_to_ms(block.time - issue_time);
+                        device.qd = device.qd.saturating_sub(1);
+                    }
+                    // flush 시퀀스(PREFLUSH -> 데이터 -> POSTFLUSH)가 끝난 원본 요청의 size 0 write 완료
+                    // 장치 명령 완료가 아니므로 QD / ctoc에 반영하지 않음
+                    None if io_operation == "write" && block.size == 0 => {
+                        report.flush_seq_completions += 1;
+                        if dedup {
+                            report.removed += 1;
+                            continue;
+                        }
+                        block.qd = device.qd;
+                        filtered_blocks.push(block);
+                        continue;
+                    }
+                    None => report.unmatched_completes += 1,
+                }
 
-            // 조건 분기 최적화
-            if first_c {
-                block.ctoc = (block.time - first_complete_time) * MILLISECONDS as f64;
-                first_c = false;
-            } else if let Some(t) = last_complete_time {
-                block.ctoc = (block.time - t) * MILLISECONDS as f64;
-            }
+                // 조건 분기 최적화
+                if device.first_c {
+                    block.ctoc = timestamp::ns_to_ms(block.time - device.first_complete_time);
+                    device.first_c = false;
+                } else if let Some(t) = device.last_complete_time {
+                    block.ctoc = timestamp::ns_to_ms(block.time - t);
+                }
 
-            current_qd = current_qd.saturating_sub(1);
-            if current_qd == 0 {
-                last_complete_qd0_time = Some(block.time);
+                if device.qd == 0 {
+                    device.last_complete_qd0_time = Some(block.time);
+                }
+                device.last_complete_time = Some(block.time);
             }
-            last_complete_time = Some(block.time);
+            "block_rq_merge" | "block_bio_backmerge" | "block_bio_frontmerge" => {
+                report.merges += 1;
+                if apply_merge(&mut in_flight, &block, io_operation) {
+                    report.merged_in_flight += 1;
+                }
+            }
+            _ => {}
         }
 
-        block.qd = current_qd;
+        block.qd = device.qd;
         filtered_blocks.push(block);
     }
 
+    report.unmatched_issues = in_flight.values().chain(requeued.values()).map(|q| q.len()).sum();
+
     let processing_elapsed = processing_start.elapsed().as_secs_f64();
     let processing_rate = filtered_blocks.len() as f64 / processing_elapsed;
     println!("      ✅ 계산 완료: {} 이벤트 | {:.2}초 | {:.0} events/s", 
              filtered_blocks.len(), processing_elapsed, processing_rate);
+    println!("      🔁 requeue {}개 (재전송 {}개) | 반복 issue {}개 | merge {}개 (대기 요청 반영 {}개)",
+             report.requeues, report.reissues, report.repeat_issues, report.merges, report.merged_in_flight);
+    println!("      💾 flush {}개 | preflush {}개 | FUA {}개 | flush 시퀀스 완료 {}개",
+             report.flush_requests, report.preflush_requests, report.fua_requests, report.flush_seq_completions);
+    println!("      🧹 제거된 이벤트 {}개 | 짝 없는 완료 {}개 | 미완료 issue {}개",
+             report.removed, report.unmatched_completes, report.unmatched_issues);
     
     // 메모리 최적화를 위해 벡터 크기 조정
     filtered_blocks.shrink_to_fit();
@@ -266,28 +496,15 @@ pub fn block_bottom_half_latency_process(block_list: Vec<Block>) -> Vec<Block> {
     println!("   최종 이벤트 수: {}", filtered_blocks.len());
     println!("   단계별 시간:");
     println!("     - 정렬: {:.2}초 ({:.1}%)", sort_elapsed, (sort_elapsed / total_elapsed) * 100.0);
-    println!("     - 중복 제거: {:.2}초 ({:.1}%)", dedup_elapsed, (dedup_elapsed / total_elapsed) * 100.0);
-    println!("     - Latency 계산: {:.2}초 ({:.1}%)", processing_elapsed, (processing_elapsed / total_elapsed) * 100.0);
+    println!("     - 요청 추적 / Latency 계산: {:.2}초 ({:.1}%)", processing_elapsed, (processing_elapsed / total_elapsed) * 100.0);
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
     
-    filtered_blocks
-}
-
-// io_type 파싱 헬퍼 함수 (성능 최적화)
-#[inline]
-fn get_io_operation(io_type: &str) -> &'static str {
-    let first_char = io_type.as_bytes().get(0);
-    match first_char {
-        Some(b'R') => "read",
-        Some(b'W') => "write",
-        Some(b'D') => "discard",
-        _ => "other",
-    }
+    (filtered_blocks, report)
 }
 
 // Vec<Block>을 Arrow RecordBatch로 변환하는 함수
 pub fn block_to_record_batch(block_list: &[Block]) -> Result<RecordBatch, String> {
-    let time_array = Float64Array::from(block_list.iter().map(|b| b.time).collect::<Vec<_>>());
+    let time_array = Int64Array::from(block_list.iter().map(|b| b.time).collect::<Vec<_>>());
     let process_array = StringArray::from(
         block_list
             .iter()
@@ -336,9 +553,10 @@ pub fn block_to_record_batch(block_list: &[Block]) -> Result<RecordBatch, String
             .map(|b| b.continuous)
             .collect::<Vec<bool>>(),
     );
+    let pattern_array = pattern_array(block_list.iter().map(|b| &*b.pattern));
 
     let schema = Arc::new(Schema::new(vec![
-        Field::new("time", DataType::Float64, false),
+        timestamp::time_field("time"),
         Field::new("process", DataType::Utf8, false),
         Field::new("cpu", DataType::UInt32, false),
         Field::new("flags", DataType::Utf8, false),
@@ -355,6 +573,7 @@ pub fn block_to_record_batch(block_list: &[Block]) -> Result<RecordBatch, String
         Field::new("ctoc", DataType::Float64, false),
         Field::new("ctod", DataType::Float64, false),
         Field::new("continuous", DataType::Boolean, false),
+        pattern_field(),
     ]));
 
     RecordBatch::try_new(
@@ -377,6 +596,7 @@ pub fn block_to_record_batch(block_list: &[Block]) -> Result<RecordBatch, String
             Arc::new(ctoc_array) as ArrayRef,
             Arc::new(ctod_array) as ArrayRef,
             Arc::new(continuous_array) as ArrayRef,
+            Arc::new(pattern_array) as ArrayRef,
         ],
     )
     .map_err(|e| e.to_string())
@@ -389,6 +609,7 @@ pub fn save_block_to_parquet(
     fname: String,
     timestamp: &str,
     window: Option<&tauri::Window>,
+    provenance: Option<&TraceProvenance>,
 ) -> Result<String, String> {
     let stem = PathBuf::from(&fname)
         .file_stem()
@@ -425,7 +646,12 @@ pub fn save_block_to_parquet(
     let first_batch = block_to_record_batch(first_chunk)?;
     let schema = first_batch.schema();
     let file = File::create(&path).map_err(|e| e.to_string())?;
-    let mut writer = ArrowWriter::try_new(file, schema.clone(), None).map_err(|e| e.to_string())?;
+    // 출처 정보는 footer key-value metadata로 기록
+    let props = provenance
+        .map(|provenance| provenance.key_value_metadata("block"))
+        .transpose()?
+        .map(|kvs| WriterProperties::builder().set_key_value_metadata(Some(kvs)).build());
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), props).map_err(|e| e.to_string())?;
     
     // 첫 번째 Chunk 쓰기
     writer.write(&first_batch).map_err(|e| e.to_string())?;
@@ -491,6 +717,7 @@ pub async fn latencystats(params: LatencyStatsParams) -> Result<Vec<u8>, String>
     // 필터링 적용
     let filtered_blocks =
         filter_block_data(&params.logname, params.time_from, params.time_to, &params.zoom_column, params.col_from, params.col_to, None)?;
+    let filtered_blocks = filter_device(filtered_blocks, params.device.as_deref())?;
 
     // LatencyStat 생성 - column에 따라 데이터 매핑
     let latency_stats: Vec<LatencyStat> = match params.column.as_str() {
@@ -498,13 +725,13 @@ pub async fn latencystats(params: LatencyStatsParams) -> Result<Vec<u8>, String>
             .iter()
             .filter(|b| b.action == "block_rq_complete")
             .map(|b| LatencyStat {
-                time: b.time,
+                time: timestamp::ns_to_secs(b.time),
                 // grouping key로 io_type 사용
-                opcode: if params.group {
-                    normalize_io_type(&b.io_type)
-                } else {
-                    b.io_type.clone()
-                },
+                opcode: group_key(
+                    if params.group { normalize_io_type(&b.io_type) } else { b.io_type.clone() },
+                    b,
+                    params.group_by_device,
+                ),
                 value: if params.column == "dtoc" {
                     LatencyValue::F64(b.dtoc)
                 } else {
@@ -516,12 +743,12 @@ pub async fn latencystats(params: LatencyStatsParams) -> Result<Vec<u8>, String>
             .iter()
             .filter(|b| b.action == "block_rq_issue")
             .map(|b| LatencyStat {
-                time: b.time,
-                opcode: if params.group {
-                    normalize_io_type(&b.io_type)
-                } else {
-                    b.io_type.clone()
-                },
+                time: timestamp::ns_to_secs(b.time),
+                opcode: group_key(
+                    if params.group { normalize_io_type(&b.io_type) } else { b.io_type.clone() },
+                    b,
+                    params.group_by_device,
+                ),
                 value: LatencyValue::F64(b.ctod),
             })
             .collect(),
@@ -529,12 +756,12 @@ pub async fn latencystats(params: LatencyStatsParams) -> Result<Vec<u8>, String>
             .iter()
             .filter(|b| b.action == "block_rq_issue")
             .map(|b| LatencyStat {
-                time: b.time,
-                opcode: if params.group {
-                    normalize_io_type(&b.io_type)
-                } else {
-                    b.io_type.clone()
-                },
+                time: timestamp::ns_to_secs(b.time),
+                opcode: group_key(
+                    if params.group { normalize_io_type(&b.io_type) } else { b.io_type.clone() },
+                    b,
+                    params.group_by_device,
+                ),
                 value: LatencyValue::F64(b.sector as f64),
             })
             .collect(),
@@ -597,6 +824,7 @@ pub async fn sizestats(params: SizeStatsParams) -> Result<Vec<u8>, String> {
     // 필터링 적용
     let filtered_blocks =
         filter_block_data(&params.logname, params.time_from, params.time_to, &params.zoom_column, params.col_from, params.col_to, None)?;
+    let filtered_blocks = filter_device(filtered_blocks, params.device.as_deref())?;
 
     // column 조건에 따라 유효한 데이터만 필터링
     let filtered_blocks: Vec<&Block> = filtered_blocks
@@ -612,11 +840,12 @@ pub async fn sizestats(params: SizeStatsParams) -> Result<Vec<u8>, String> {
     let target_io_types: Vec<String> = filtered_blocks
         .iter()
         .map(|b| {
-            if params.group {
+            let io_key = if params.group {
                 normalize_io_type(&b.io_type)
             } else {
                 b.io_type.clone()
-            }
+            };
+            group_key(io_key, b, params.group_by_device)
         })
         .collect::<std::collections::HashSet<_>>()
         .into_iter()
@@ -638,6 +867,7 @@ pub async fn sizestats(params: SizeStatsParams) -> Result<Vec<u8>, String> {
         } else {
             block.io_type.clone()
         };
+        let io_key = group_key(io_key, block, params.group_by_device);
 
         if let Some(size_counts) = io_stats.get_mut(&io_key) {
             *size_counts.entry(block.size).or_insert(0) += 1;
@@ -654,6 +884,7 @@ pub async fn sizestats(params: SizeStatsParams) -> Result<Vec<u8>, String> {
 }
 
 // Block 연속성 통계 함수
+#[allow(clippy::too_many_arguments)]
 pub async fn continuity_stats(
     logname: String,
     zoom_column: String,
@@ -661,10 +892,13 @@ pub async fn continuity_stats(
     time_to: Option<f64>,
     col_from: Option<f64>,
     col_to: Option<f64>,
+    device: Option<String>,
+    group_by_device: bool,
 ) -> Result<Vec<u8>, String> {
     // 필터링 적용
     let filtered_blocks =
         filter_block_data(&logname, time_from, time_to, &zoom_column, col_from, col_to, None)?;
+    let filtered_blocks = filter_device(filtered_blocks, device.as_deref())?;
 
     // block_rq_issue 동작만 필터링
     // R*(read) 또는 W*(write) D*(discard)로 시작하는 IO 타입만 포함
@@ -686,7 +920,7 @@ pub async fn continuity_stats(
     let mut continuous_bytes: u64 = 0;
 
     for block in &issues {
-        let io_type = normalize_io_type(&block.io_type);
+        let io_type = group_key(normalize_io_type(&block.io_type), block, group_by_device);
 
         // io_type별 통계 업데이트
         let stats = op_stats.entry(io_type).or_insert(ContinuityCount {
@@ -747,6 +981,7 @@ pub async fn continuity_stats(
             continuous_bytes,
             bytes_ratio,
         },
+        streams: None,
     };
 
     serde_json::to_vec(&result).map_err(|e| e.to_string())
@@ -762,10 +997,14 @@ pub async fn allstats(params: AllStatsParams) -> Result<Vec<u8>, String> {
 
     let filtered_blocks =
         filter_block_data(&params.logname, params.time_from, params.time_to, &params.zoom_column, params.col_from, params.col_to, None)?;
+    let filtered_blocks = filter_device(filtered_blocks, params.device.as_deref())?;
 
     let unique_io_types: std::collections::HashSet<String> = filtered_blocks
         .iter()
-        .map(|b| if params.group { normalize_io_type(&b.io_type) } else { b.io_type.clone() })
+        .map(|b| {
+            let io_key = if params.group { normalize_io_type(&b.io_type) } else { b.io_type.clone() };
+            group_key(io_key, b, params.group_by_device)
+        })
         .collect();
 
     let mut dtoc_counts = std::collections::BTreeMap::new();
@@ -804,6 +1043,7 @@ pub async fn allstats(params: AllStatsParams) -> Result<Vec<u8>, String> {
         } else {
             block.io_type.clone()
         };
+        let io_key = group_key(io_key, block, params.group_by_device);
 
         if block.action == "block_rq_complete" {
             let range_key = create_range_key(block.dtoc, &threshold_values, &params.thresholds);
@@ -838,7 +1078,8 @@ pub async fn allstats(params: AllStatsParams) -> Result<Vec<u8>, String> {
             ctod_groups.entry(io_key.clone()).or_default().push(block.ctod);
 
             if block.io_type.starts_with('R') || block.io_type.starts_with('W') || block.io_type.starts_with('D') {
-                let stats = op_stats.entry(normalize_io_type(&block.io_type)).or_insert(ContinuityCount {
+                let op_key = group_key(normalize_io_type(&block.io_type), block, params.group_by_device);
+                let stats = op_stats.entry(op_key).or_insert(ContinuityCount {
                     continuous: 0,
                     non_continuous: 0,
                     ratio: 0.0,
@@ -927,6 +1168,7 @@ pub async fn allstats(params: AllStatsParams) -> Result<Vec<u8>, String> {
             continuous_bytes,
             bytes_ratio,
         },
+        streams: None,
     };
 
     let result = TraceStats {
@@ -935,7 +1177,165 @@ pub async fn allstats(params: AllStatsParams) -> Result<Vec<u8>, String> {
         ctoc_stat,
         size_counts,
         continuity,
+        wall_clock: clock_sync::wall_clock_range(
+            &params.logname,
+            params.time_from,
+            params.time_to,
+            filtered_blocks.iter().map(|b| timestamp::ns_to_secs(b.time)),
+        ),
     };
 
     serde_json::to_vec(&result).map_err(|e| e.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support::block;
+    use crate::trace::utils::parse_block_trace_with_caps;
+
+    // 기본 장치(8:0) 외의 장치로 보낸 요청
+    fn on_device(dev: (u32, u32), block: Block) -> Block {
+        Block { devmajor: dev.0, devminor: dev.1, ..block }
+    }
+
+    #[test]
+    fn test_per_device_queue_depth_and_latency() {
+        let dm = (253, 0);
+        let rows = vec![
+            block(100, "block_rq_issue", 0),
+            // 같은 sector라도 다른 장치(dm)의 요청은 중복이 아님
+            on_device(dm, block(110, "block_rq_issue", 0)),
+            block(120, "block_rq_issue", 8),
+            on_device(dm, block(200, "block_rq_complete", 0)),
+            block(300, "block_rq_complete", 0),
+            block(400, "block_rq_complete", 8),
+        ];
+
+        let (result, report) = block_bottom_half_latency_process(rows, true);
+        assert_eq!(report.removed, 0);
+        assert_eq!(result.len(), 6);
+        let qd: Vec<u32> = result.iter().map(|b| b.qd).collect();
+        assert_eq!(qd, vec![1, 1, 2, 0, 1, 0]);
+        // 연속성도 장치별 (dm 요청이 sda 순차 흐름을 끊지 않음)
+        assert!(result[2].continuous);
+        assert_eq!(result[3].dtoc, 0.09);
+        assert_eq!(result[4].dtoc, 0.2);
+        // sda 첫 완료의 ctoc는 sda 첫 issue 기준
+        assert_eq!(result[4].ctoc, 0.2);
+        assert_eq!(result[5].ctoc, 0.1);
+
+        let devices = summarize_devices(&result);
+        let keys: Vec<&str> = devices.iter().map(|d| d.device.as_str()).collect();
+        assert_eq!(keys, vec!["8:0", "253:0"]);
+        assert_eq!((devices[0].issues, devices[0].max_qd), (2, 2));
+    }
+
+    fn with_io_type(mut block: Block, io_type: &str, size: u32) -> Block {
+        block.io_type = io_type.to_string();
+        block.size = size;
+        block
+    }
+
+    #[test]
+    fn test_parse_rwbs() {
+        assert_eq!(parse_rwbs("FF"), Rwbs { op: "flush", preflush: true, fua: false });
+        assert_eq!(parse_rwbs("FWFS"), Rwbs { op: "write", preflush: true, fua: true });
+        assert_eq!(parse_rwbs("WFS"), Rwbs { op: "write", preflush: false, fua: true });
+        assert_eq!(parse_rwbs("RA"), Rwbs { op: "read", preflush: false, fua: false });
+        assert_eq!(parse_rwbs("N").op, "other");
+    }
+
+    #[test]
+    fn test_requeue_and_flush_sequence_tracking() {
+        let rows = vec![
+            // PREFLUSH + FUA write: FLUSH 요청 -> 데이터 write -> 원본 요청의 size 0 완료
+            with_io_type(block(100, "block_rq_issue", 0), "FF", 0),
+            with_io_type(block(150, "block_rq_complete", 0), "FF", 0),
+            with_io_type(block(200, "block_rq_issue", 64), "WS", 8),
+            with_io_type(block(300, "block_rq_complete", 64), "WS", 8),
+            with_io_type(block(310, "block_rq_complete", 64), "FWFS", 0),
+            // 드라이버가 되돌려 보낸 read가 다시 issue됨
+            block(400, "block_rq_issue", 128),
+            block(410, "block_rq_requeue", 128),
+            block(500, "block_rq_issue", 128),
+            block(600, "block_rq_complete", 128),
+        ];
+
+        let (result, report) = block_bottom_half_latency_process(rows.clone(), true);
+        assert_eq!(result.len(), 8);
+        // FF는 PREFLUSH 표시가 붙은 FLUSH 연산 - flush로만 집계
+        assert_eq!((report.flush_requests, report.preflush_requests, report.fua_requests), (1, 0, 0));
+        assert_eq!((report.flush_seq_completions, report.removed), (1, 1));
+        assert_eq!((report.requeues, report.reissues, report.repeat_issues), (1, 1, 0));
+        assert_eq!((report.unmatched_completes, report.unmatched_issues), (0, 0));
+        let qd: Vec<u32> = result.iter().map(|b| b.qd).collect();
+        assert_eq!(qd, vec![1, 0, 1, 0, 1, 0, 1, 0]);
+        // requeue된 read의 dtoc는 최초 issue 기준
+        assert_eq!(result[7].dtoc, 0.2);
+
+        // dedup을 끄면 flush 시퀀스 완료도 남지만 QD에는 영향 없음
+        let (result, report) = block_bottom_half_latency_process(rows, false);
+        assert_eq!((result.len(), report.removed), (9, 0));
+        assert_eq!(result[4].qd, 0);
+        assert_eq!(result[4].ctoc, 0.0);
+    }
+
+    #[test]
+    fn test_repeat_issue_without_requeue_events() {
+        let rows = vec![
+            block(100, "block_rq_issue", 0),
+            block(150, "block_rq_issue", 0),
+            block(300, "block_rq_complete", 0),
+        ];
+
+        // requeue 이벤트가 없으면 완료 전 같은 요청의 재전송으로 보고 제거
+        let (result, report) = block_bottom_half_latency_process(rows.clone(), true);
+        assert_eq!((result.len(), report.repeat_issues, report.removed), (2, 1, 1));
+        assert_eq!(result[1].dtoc, 0.2);
+        assert_eq!(report.unmatched_issues, 0);
+
+        // dedup을 끄면 서로 다른 요청으로 추적
+        let (result, report) = block_bottom_half_latency_process(rows, false);
+        assert_eq!((result.len(), report.repeat_issues, report.removed), (3, 1, 0));
+        assert_eq!(result[1].qd, 2);
+        assert_eq!(report.unmatched_issues, 1);
+    }
+
+    #[test]
+    fn test_bio_merges_extend_in_flight_request() {
+        // 기본 패턴은 "()" 없는 bio merge 라인도 파싱
+        let regex = regex::Regex::new(crate::trace::patterns::DEFAULT_BLOCK_PATTERN).unwrap();
+        let rows: Vec<Block> = [
+            "fio-100 [000] d..1. 1.000100: block_rq_issue: 8,0 W 4096 () 64 + 8 [fio]",
+            "fio-100 [000] d..1. 1.000150: block_bio_backmerge: 8,0 W 72 + 8 [fio]",
+            "fio-100 [000] d..1. 1.000160: block_bio_frontmerge: 8,0 W 56 + 8 [fio]",
+            "fio-100 [000] d..1. 1.000170: block_bio_backmerge: 8,0 R 200 + 8 [fio]",
+            "<idle>-0 [000] d..1. 1.000400: block_rq_complete: 8,0 W () 56 + 24 [0]",
+        ]
+        .iter()
+        .map(|line| parse_block_trace_with_caps(&regex.captures(line).unwrap()).unwrap())
+        .collect();
+        assert_eq!((rows[1].action.as_str(), rows[1].sector, rows[1].size), ("block_bio_backmerge", 72, 8));
+
+        // back merge는 size 확장, front merge는 시작 sector를 옮겨 완료(56 + 24)와 짝지음
+        let (result, report) = block_bottom_half_latency_process(rows, false);
+        assert_eq!((report.merges, report.merged_in_flight), (3, 2));
+        assert_eq!((report.unmatched_completes, report.unmatched_issues), (0, 0));
+        assert_eq!(result[4].dtoc, 0.3);
+        assert_eq!(result[4].qd, 0);
+    }
+
+    #[test]
+    fn test_device_filter_and_group_key() {
+        assert_eq!(parse_device(" 253:1 "), Ok((253, 1)));
+        assert!(parse_device("sda").is_err());
+
+        let rows = vec![block(1, "block_rq_issue", 0), on_device((253, 1), block(2, "block_rq_issue", 0))];
+        let filtered = filter_device(rows.clone(), Some("253:1")).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filter_device(rows.clone(), None).unwrap().len(), 2);
+        assert_eq!(group_key("R".to_string(), &rows[1], true), "253:1 R");
+        assert_eq!(group_key("R".to_string(), &rows[1], false), "R");
+    }
+}
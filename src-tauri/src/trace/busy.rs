@@ -0,0 +1,318 @@
+// src-tauri/src/trace/busy.rs
+//
+// 장치 busy / idle 분석
+// QD > 0인 구간을 busy, 그 사이를 idle gap으로 보고 사용률(utilization),
+// idle gap 분포(hibern8 진입 / background operation 튜닝용), 가장 긴 busy 구간을 계산한다.
+// - UFS: send_req / complete_rsp 후의 전체 QD
+// - Block: 장치별 QD로 busy 구간을 구한 뒤 장치 전체를 합침 (device 지정 시 해당 장치만)
+// - UFSCUSTOM: 요청의 [start_time, end_time] 구간 합집합
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::trace::block;
+use crate::trace::clock_sync::{self, WallClockRange};
+use crate::trace::filter::{filter_block_data, filter_ufs_data, filter_ufscustom_data};
+use crate::trace::timestamp;
+use crate::trace::utils::{calculate_statistics, create_range_key, initialize_ranges, parse_time_to_ms};
+use crate::trace::LatencySummary;
+
+// 가장 긴 busy 구간 기본 개수
+const DEFAULT_TOP_BUSY: usize = 10;
+
+// busy 구간 [start, end] (나노초) 과 그 안에서 시작된 요청 수
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+    pub requests: usize,
+}
+
+// (시각, 이벤트 후 QD, 요청 시작 여부)
+pub type QdSample = (i64, u32, bool);
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BusyPeriod {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub duration_ms: f64,
+    pub requests: usize,
+    pub wall_clock: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BusyIdleStats {
+    pub trace_type: String,
+    // 관측 구간 (시간 필터, 없으면 데이터 범위)
+    pub window_start: f64,
+    pub window_end: f64,
+    pub total_time_ms: f64,
+    pub busy_time_ms: f64,
+    pub idle_time_ms: f64,
+    pub utilization: f64, // %
+    pub busy_periods: usize,
+    pub busy_summary: LatencySummary,
+    // busy 구간 사이의 idle gap (관측 구간 시작/끝에 걸친 gap은 길이를 알 수 없어 분포에서 제외)
+    pub idle_gaps: usize,
+    pub idle_summary: LatencySummary,
+    pub idle_gap_counts: BTreeMap<String, usize>,
+    pub longest_busy: Vec<BusyPeriod>,
+    pub wall_clock: Option<WallClockRange>,
+}
+
+/// QdSample 순서열에서 QD > 0 구간 추출
+/// initial_busy: 첫 이벤트 이전에 이미 처리 중인 요청이 있었는지 (관측 구간 시작부터 busy)
+pub fn busy_from_qd(
+    samples: impl IntoIterator<Item = QdSample>,
+    initial_busy: bool,
+    window: (i64, i64),
+) -> Vec<Interval> {
+    let mut intervals = Vec::new();
+    let mut current = initial_busy.then_some(Interval { start: window.0, end: window.0, requests: 0 });
+
+    for (time, qd, issue) in samples {
+        match (&mut current, qd > 0) {
+            (None, true) => {
+                current = Some(Interval { start: time, end: time, requests: usize::from(issue) });
+            }
+            (Some(interval), busy) => {
+                interval.requests += usize::from(issue);
+                if !busy {
+                    interval.end = time;
+                    intervals.push(*interval);
+                    current = None;
+                }
+            }
+            (None, false) => {}
+        }
+    }
+    // 관측 구간 끝까지 완료되지 않은 요청
+    if let Some(mut interval) = current {
+        interval.end = window.1;
+        intervals.push(interval);
+    }
+    intervals
+}
+
+/// 겹치거나 맞닿은 구간 합치기 (여러 장치 / 요청 구간의 합집합)
+pub fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort_by_key(|i| (i.start, i.end));
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => {
+                last.end = last.end.max(interval.end);
+                last.requests += interval.requests;
+            }
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/// busy 구간으로 사용률 / idle gap 분포 / 가장 긴 busy 구간 계산
+pub fn analyze(
+    trace_type: &str,
+    busy: &[Interval],
+    window: (i64, i64),
+    thresholds: &[String],
+    top_n: usize,
+) -> Result<BusyIdleStats, String> {
+    let threshold_values = thresholds
+        .iter()
+        .map(|t| parse_time_to_ms(t))
+        .collect::<Result<Vec<f64>, String>>()?;
+
+    // 관측 구간으로 자르기
+    let clipped: Vec<Interval> = busy
+        .iter()
+        .filter_map(|i| {
+            let start = i.start.max(window.0);
+            let end = i.end.min(window.1);
+            (start <= end).then_some(Interval { start, end, requests: i.requests })
+        })
+        .collect();
+
+    let total_ns = (window.1 - window.0).max(0);
+    let busy_ns: i64 = clipped.iter().map(|i| i.end - i.start).sum();
+    let mut busy_ms: Vec<f64> = clipped.iter().map(|i| timestamp::ns_to_ms(i.end - i.start)).collect();
+    let mut idle_ms: Vec<f64> = clipped
+        .windows(2)
+        .map(|pair| timestamp::ns_to_ms(pair[1].start - pair[0].end))
+        .collect();
+
+    let mut idle_gap_counts = initialize_ranges(thresholds);
+    for gap in &idle_ms {
+        let key = create_range_key(*gap, &threshold_values, thresholds);
+        *idle_gap_counts.entry(key).or_insert(0) += 1;
+    }
+
+    let mut longest = clipped.clone();
+    longest.sort_by(|a, b| (b.end - b.start).cmp(&(a.end - a.start)).then(a.start.cmp(&b.start)));
+    let longest_busy = longest
+        .into_iter()
+        .take(top_n)
+        .map(|i| BusyPeriod {
+            start_time: timestamp::ns_to_secs(i.start),
+            end_time: timestamp::ns_to_secs(i.end),
+            duration_ms: timestamp::ns_to_ms(i.end - i.start),
+            requests: i.requests,
+            wall_clock: None,
+        })
+        .collect();
+
+    Ok(BusyIdleStats {
+        trace_type: trace_type.to_string(),
+        window_start: timestamp::ns_to_secs(window.0),
+        window_end: timestamp::ns_to_secs(window.1),
+        total_time_ms: timestamp::ns_to_ms(total_ns),
+        busy_time_ms: timestamp::ns_to_ms(busy_ns),
+        idle_time_ms: timestamp::ns_to_ms(total_ns - busy_ns),
+        utilization: if total_ns > 0 { busy_ns as f64 / total_ns as f64 * 100.0 } else { 0.0 },
+        busy_periods: clipped.len(),
+        busy_summary: calculate_statistics(&mut busy_ms),
+        idle_gaps: idle_ms.len(),
+        idle_summary: calculate_statistics(&mut idle_ms),
+        idle_gap_counts,
+        longest_busy,
+        wall_clock: None,
+    })
+}
+
+// 관측 구간 - 시간 필터가 있으면 그 범위, 없으면 데이터 범위
+fn observation_window(time_from: Option<f64>, time_to: Option<f64>, times: impl Iterator<Item = i64>) -> Option<(i64, i64)> {
+    match (time_from, time_to) {
+        (Some(from), Some(to)) if from != 0.0 || to != 0.0 => {
+            Some((timestamp::secs_to_ns(from), timestamp::secs_to_ns(to)))
+        }
+        _ => times.fold(None, |range, t| match range {
+            Some((min, max)) => Some((t.min(min), t.max(max))),
+            None => Some((t, t)),
+        }),
+    }
+}
+
+/// busy_idle_stats 명령 - trace_type: "ufs" | "block" | "ufscustom"
+pub fn busy_idle_stats(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    thresholds: &[String],
+    top_n: Option<usize>,
+    device: Option<&str>,
+) -> Result<String, String> {
+    let (busy, window) = match trace_type {
+        "ufs" => {
+            let rows = filter_ufs_data(logname, time_from, time_to, "lba", None, None, None)?;
+            let window = observation_window(time_from, time_to, rows.iter().map(|u| u.time))
+                .ok_or("분석할 UFS 데이터가 없습니다")?;
+            // 첫 이벤트 전에 처리 중이던 요청: complete_rsp로 시작하거나 send 후 QD가 2 이상
+            let initial_busy = rows.first().is_some_and(|u| match u.action.as_str() {
+                "send_req" => u.qd > 1,
+                _ => u.qd > 0 || u.action == "complete_rsp",
+            });
+            let samples = rows.iter().map(|u| (u.time, u.qd, u.action == "send_req"));
+            (busy_from_qd(samples, initial_busy, window), window)
+        }
+        "block" => {
+            let rows = filter_block_data(logname, time_from, time_to, "sector", None, None, None)?;
+            let rows = block::filter_device(rows, device)?;
+            let window = observation_window(time_from, time_to, rows.iter().map(|b| b.time))
+                .ok_or("분석할 Block 데이터가 없습니다")?;
+            // QD는 장치별로 계산되어 있으므로 장치별로 busy 구간을 구한 뒤 합침
+            let mut by_device: HashMap<(u32, u32), Vec<QdSample>> = HashMap::new();
+            let mut initial: HashMap<(u32, u32), bool> = HashMap::new();
+            for b in &rows {
+                let key = (b.devmajor, b.devminor);
+                initial.entry(key).or_insert_with(|| match b.action.as_str() {
+                    "block_rq_issue" => b.qd > 1,
+                    _ => b.qd > 0 || b.action == "block_rq_complete",
+                });
+                by_device
+                    .entry(key)
+                    .or_default()
+                    .push((b.time, b.qd, b.action == "block_rq_issue"));
+            }
+            let intervals = by_device
+                .into_iter()
+                .flat_map(|(key, samples)| busy_from_qd(samples, initial[&key], window))
+                .collect();
+            (merge_intervals(intervals), window)
+        }
+        "ufscustom" => {
+            let rows = filter_ufscustom_data(logname, time_from, time_to, "lba", None, None, None)?;
+            let window = observation_window(
+                time_from,
+                time_to,
+                rows.iter().flat_map(|u| [u.start_time, u.end_time]),
+            )
+            .ok_or("분석할 UFSCUSTOM 데이터가 없습니다")?;
+            let intervals = rows
+                .iter()
+                .map(|u| Interval { start: u.start_time, end: u.end_time.max(u.start_time), requests: 1 })
+                .collect();
+            (merge_intervals(intervals), window)
+        }
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    };
+
+    let mut stats = analyze(trace_type, &busy, window, thresholds, top_n.unwrap_or(DEFAULT_TOP_BUSY))?;
+    if let Some(sync) = clock_sync::clock_sync_for_logname(logname) {
+        for period in &mut stats.longest_busy {
+            period.wall_clock = Some(sync.format(period.start_time));
+        }
+        stats.wall_clock = Some(sync.range(stats.window_start, stats.window_end));
+    }
+
+    println!(
+        "⏲️  {} busy/idle: 사용률 {:.1}% (busy {:.3}ms / 전체 {:.3}ms), busy 구간 {}개, idle gap {}개",
+        trace_type, stats.utilization, stats.busy_time_ms, stats.total_time_ms, stats.busy_periods, stats.idle_gaps
+    );
+    serde_json::to_string(&stats).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64, requests: usize) -> Interval {
+        Interval { start, end, requests }
+    }
+
+    #[test]
+    fn test_busy_from_qd_and_merge() {
+        // 시작 전부터 요청 1개 처리 중, 끝날 때도 요청이 남아 있음
+        let samples = vec![
+            (10, 0, false),
+            (20, 1, true),
+            (25, 2, true),
+            (30, 1, false),
+            (40, 0, false),
+            (90, 1, true),
+        ];
+        let busy = busy_from_qd(samples, true, (0, 100));
+        assert_eq!(busy, vec![interval(0, 10, 0), interval(20, 40, 2), interval(90, 100, 1)]);
+
+        let merged = merge_intervals(vec![interval(5, 10, 1), interval(0, 3, 1), interval(8, 20, 2), interval(20, 25, 1)]);
+        assert_eq!(merged, vec![interval(0, 3, 1), interval(5, 25, 4)]);
+    }
+
+    #[test]
+    fn test_analyze_utilization_and_idle_gaps() {
+        let ms = timestamp::NS_PER_MS;
+        let busy = vec![interval(0, 2 * ms, 3), interval(3 * ms, 4 * ms, 1), interval(10 * ms, 15 * ms, 5)];
+        let thresholds = vec!["1ms".to_string(), "5ms".to_string()];
+        let stats = analyze("ufs", &busy, (0, 20 * ms), &thresholds, 2).unwrap();
+
+        assert_eq!(stats.utilization, 40.0);
+        assert_eq!((stats.busy_time_ms, stats.idle_time_ms), (8.0, 12.0));
+        assert_eq!((stats.busy_periods, stats.idle_gaps), (3, 2));
+        // gap: 1ms, 6ms (마지막 5ms idle은 관측 구간 끝에 걸쳐 제외)
+        assert_eq!(stats.idle_gap_counts["01_≤ 1ms"], 1);
+        assert_eq!(stats.idle_gap_counts["99_> 5ms"], 1);
+        let longest: Vec<f64> = stats.longest_busy.iter().map(|p| p.duration_ms).collect();
+        assert_eq!(longest, vec![5.0, 2.0]);
+        assert_eq!(stats.longest_busy[0].requests, 5);
+    }
+}
@@ -0,0 +1,286 @@
+// src-tauri/src/trace/clock_sync.rs
+//
+// 트레이스 time(부팅 후 monotonic 초)과 실제 시각(wall clock) 사이의 offset
+// tracing_mark_write의 시각 동기화 마커 또는 사용자가 입력한 (uptime, 실제 시각) 쌍으로 구하고,
+// parquet footer(estrace.clock_sync)에 저장하여 export/filter/통계에서 실제 시각을 보여준다.
+
+use chrono::{DateTime, Local, SecondsFormat};
+use once_cell::sync::Lazy;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+
+use super::ftrace_format::parse_header;
+
+pub const CLOCK_SYNC_KEY: &str = "estrace.clock_sync";
+// parquet footer 재작성 시 ArrowWriter가 다시 기록하는 키
+const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+
+// atrace: "trace_event_clock_sync: realtime_ts=1697040000123" (ms)
+// 테스트 하네스: "clock_sync: epoch=1697040000.123456" (초), epoch_ms / epoch_us / epoch_ns
+static SYNC_STAMP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?P<unit>realtime_ts|epoch_ms|epoch_us|epoch_ns|epoch)=(?P<value>\d+(?:\.\d+)?)").unwrap()
+});
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClockSync {
+    // wall_clock(epoch 초) = time + offset
+    pub offset: f64,
+    // 기준으로 사용한 트레이스 time과 실제 시각(epoch 초)
+    pub uptime: f64,
+    pub wall_clock: f64,
+    // "marker" 또는 "user"
+    pub source: String,
+    // 마커에서 구한 경우 원본 args
+    pub marker: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallClockRange {
+    pub from: String,
+    pub to: String,
+}
+
+impl ClockSync {
+    pub fn new(uptime: f64, wall_clock: f64, source: &str, marker: Option<String>) -> Self {
+        ClockSync {
+            offset: wall_clock - uptime,
+            uptime,
+            wall_clock,
+            source: source.to_string(),
+            marker,
+        }
+    }
+
+    pub fn to_wall_clock(&self, time: f64) -> f64 {
+        time + self.offset
+    }
+
+    pub fn to_uptime(&self, wall_clock: f64) -> f64 {
+        wall_clock - self.offset
+    }
+
+    /// 트레이스 time을 로컬 시간대 RFC 3339 문자열로 변환 (마이크로초)
+    pub fn format(&self, time: f64) -> String {
+        format_epoch(self.to_wall_clock(time))
+    }
+
+    pub fn range(&self, time_from: f64, time_to: f64) -> WallClockRange {
+        WallClockRange {
+            from: self.format(time_from),
+            to: self.format(time_to),
+        }
+    }
+}
+
+pub fn format_epoch(epoch: f64) -> String {
+    let nanos = (epoch * 1e9).round() as i64;
+    DateTime::from_timestamp_nanos(nanos)
+        .with_timezone(&Local)
+        .to_rfc3339_opts(SecondsFormat::Micros, false)
+}
+
+/// 실제 시각 입력 파싱: RFC 3339 문자열 또는 epoch 초
+pub fn parse_wall_clock(value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    if let Ok(epoch) = value.parse::<f64>() {
+        return Ok(epoch);
+    }
+    let time = DateTime::parse_from_rfc3339(value)
+        .map_err(|e| format!("실제 시각 형식이 잘못되었습니다 ('{}'): {}", value, e))?;
+    Ok(time.timestamp() as f64 + time.timestamp_subsec_nanos() as f64 / 1e9)
+}
+
+/// 마커 args에서 epoch 초 추출
+pub fn parse_sync_stamp(args: &str) -> Option<f64> {
+    let caps = SYNC_STAMP.captures(args)?;
+    let value: f64 = caps["value"].parse().ok()?;
+    let scale = match &caps["unit"] {
+        "realtime_ts" | "epoch_ms" => 1e-3,
+        "epoch_us" => 1e-6,
+        "epoch_ns" => 1e-9,
+        _ => 1.0,
+    };
+    Some(value * scale)
+}
+
+/// 마커 이벤트(tracing_mark_write / print)가 시각 동기화 마커이면 ClockSync 생성
+pub fn clock_sync_from_marker(event: &str, time: f64, args: &str) -> Option<ClockSync> {
+    if event != "tracing_mark_write" && event != "print" {
+        return None;
+    }
+    let wall_clock = parse_sync_stamp(args)?;
+    Some(ClockSync::new(time, wall_clock, "marker", Some(args.to_string())))
+}
+
+/// ftrace 라인이 시각 동기화 마커이면 ClockSync 생성
+pub fn clock_sync_from_line(line: &str) -> Option<ClockSync> {
+    let header = parse_header(line)?;
+    clock_sync_from_marker(header.event, header.time, header.args)
+}
+
+/// 파싱 루프에서 패턴과 일치하지 않은 라인 검사 - 대부분의 라인은 정규식 없이 건너뜀
+#[inline]
+pub fn clock_sync_from_unmatched(line: &str) -> Option<ClockSync> {
+    if !(line.contains("realtime_ts=") || line.contains("epoch")) {
+        return None;
+    }
+    clock_sync_from_line(line.trim_end())
+}
+
+/// 파싱 중 찾은 첫 번째 시각 동기화 마커 로그 출력
+pub fn report_marker(sync: Option<ClockSync>) -> Option<ClockSync> {
+    if let Some(sync) = &sync {
+        println!("🕒 시각 동기화 마커 발견: time {:.6} = {}", sync.uptime, format_epoch(sync.wall_clock));
+    }
+    sync
+}
+
+/// parquet footer의 시각 동기화 정보
+pub fn read_clock_sync(path: &str) -> Result<Option<ClockSync>, String> {
+    let file = File::open(path).map_err(|e| format!("parquet 파일 열기 실패: {}", e))?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+    let value = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kvs| kvs.iter().find(|kv| kv.key == CLOCK_SYNC_KEY))
+        .and_then(|kv| kv.value.clone());
+
+    value
+        .map(|value| {
+            serde_json::from_str(&value)
+                .map_err(|e| format!("{} 메타데이터 파싱 실패: {}", CLOCK_SYNC_KEY, e))
+        })
+        .transpose()
+}
+
+/// 쉼표로 구분된 parquet 목록 중 처음으로 시각 동기화 정보가 있는 파일의 값
+pub fn clock_sync_for_logname(logname: &str) -> Option<ClockSync> {
+    logname
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .find_map(|path| read_clock_sync(path).ok().flatten())
+}
+
+/// 통계 결과에 붙일 분석 구간의 실제 시각
+/// time_from/time_to가 없거나 둘 다 0이면 필터링된 데이터의 시간 범위(times) 사용
+pub fn wall_clock_range(
+    logname: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    times: impl Iterator<Item = f64>,
+) -> Option<WallClockRange> {
+    let sync = clock_sync_for_logname(logname)?;
+    let (from, to) = match (time_from, time_to) {
+        (Some(from), Some(to)) if from != 0.0 || to != 0.0 => (from, to),
+        _ => times.fold(None, |range: Option<(f64, f64)>, t| match range {
+            Some((min, max)) => Some((min.min(t), max.max(t))),
+            None => Some((t, t)),
+        })?,
+    };
+    Some(sync.range(from, to))
+}
+
+// 데이터는 그대로 두고 footer의 estrace.clock_sync만 교체하여 다시 저장
+fn rewrite_clock_sync(path: &str, sync: &ClockSync) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("parquet 파일 열기 실패: {}", e))?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| e.to_string())?;
+
+    let mut key_values: Vec<KeyValue> = builder
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .map(|kvs| {
+            kvs.iter()
+                .filter(|kv| kv.key != CLOCK_SYNC_KEY && kv.key != ARROW_SCHEMA_KEY)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    key_values.push(KeyValue::new(
+        CLOCK_SYNC_KEY.to_string(),
+        serde_json::to_string(sync).map_err(|e| e.to_string())?,
+    ));
+
+    let schema = builder.schema().clone();
+    let reader = builder.build().map_err(|e| e.to_string())?;
+    let tmp_path = format!("{}.tmp", path);
+    let props = WriterProperties::builder()
+        .set_key_value_metadata(Some(key_values))
+        .build();
+    let tmp_file = File::create(&tmp_path).map_err(|e| e.to_string())?;
+    let mut writer = ArrowWriter::try_new(tmp_file, schema, Some(props)).map_err(|e| e.to_string())?;
+
+    for batch in reader {
+        let batch = batch.map_err(|e| e.to_string())?;
+        writer.write(&batch).map_err(|e| e.to_string())?;
+    }
+    writer.close().map_err(|e| e.to_string())?;
+
+    fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+/// set_clock_sync 명령 - 사용자가 입력한 (트레이스 time, 실제 시각) 쌍을 모든 parquet에 저장
+pub fn set_clock_sync(logname: String, uptime: f64, wall_clock: String) -> Result<String, String> {
+    let sync = ClockSync::new(uptime, parse_wall_clock(&wall_clock)?, "user", None);
+
+    for path in logname.split(',').map(str::trim).filter(|path| !path.is_empty()) {
+        rewrite_clock_sync(path, &sync)?;
+        println!("🕒 시각 동기화 정보 저장: {} (offset {:.6}s)", path, sync.offset);
+    }
+
+    serde_json::to_string(&sync).map_err(|e| e.to_string())
+}
+
+/// get_clock_sync 명령
+pub fn get_clock_sync(logname: String) -> Result<String, String> {
+    serde_json::to_string(&clock_sync_for_logname(&logname)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_sync_from_marker_lines() {
+        let sync = clock_sync_from_line(
+            "  atrace-500  [003] ..... 100.250000: tracing_mark_write: trace_event_clock_sync: realtime_ts=1700000000500",
+        )
+        .unwrap();
+        assert!((sync.offset - 1_699_999_900.25).abs() < 1e-6);
+        assert!((sync.to_wall_clock(101.0) - 1_700_000_001.25).abs() < 1e-6);
+
+        let sync = clock_sync_from_line(
+            "  harness-7  [000] ..... 10.000000: print: tracing_mark_write: clock_sync: epoch=1700000000.125",
+        )
+        .unwrap();
+        assert!((sync.to_uptime(1_700_000_000.625) - 10.5).abs() < 1e-6);
+
+        assert!(clock_sync_from_line(
+            "  app-1  [000] ..... 1.0: sched_wakeup: comm=epoch=123 pid=1"
+        )
+        .is_none());
+        assert!(clock_sync_from_unmatched(
+            "  app-1  [000] ..... 1.0: tracing_mark_write: B|1|work"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_parse_wall_clock_and_format() {
+        let epoch = parse_wall_clock("2023-11-14T22:13:20.5+00:00").unwrap();
+        assert!((epoch - 1_700_000_000.5).abs() < 1e-6);
+        assert_eq!(parse_wall_clock("1700000000.5").unwrap(), epoch);
+
+        let sync = ClockSync::new(0.5, epoch, "user", None);
+        let formatted = sync.format(1.0);
+        let parsed = DateTime::parse_from_rfc3339(&formatted).unwrap();
+        assert_eq!(parsed.timestamp(), 1_700_000_001);
+    }
+}
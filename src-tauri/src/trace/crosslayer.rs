@@ -0,0 +1,567 @@
+// src-tauri/src/trace/crosslayer.rs
+//
+// Block -> UFS 계층 간 요청 매칭
+// block_rq_issue ~ block_rq_complete 요청 하나를 실제로 처리한 UFS 명령(send_req ~ complete_rsp)과 짝짓는다.
+// - 주소: block sector(512B)를 4KB lba로 변환한 범위 안에 UFS 명령의 [lba, lba + size)가 포함
+// - 연산: R <-> 0x08/0x28/0x88, W <-> 0x0a/0x2a/0x8a, D <-> 0x42(UNMAP), F <-> 0x35(SYNCHRONIZE CACHE)
+// - 시간: block issue <= UFS send, UFS complete <= block complete (tolerance 허용)
+// 큰 요청은 여러 UFS 명령으로 나뉠 수 있으므로 block 요청 하나에 UFS 명령 여러 개가 매칭될 수 있다.
+// host 오버헤드 = (block issue -> UFS send) + (UFS complete -> block complete)
+// 파싱 후처리에서 매칭된 block 요청의 comm을 UFS send_req / complete_rsp 행(UFS.comm)에 기록한다.
+
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, VecDeque};
+
+use crate::trace::block::{block_op, filter_device};
+use crate::trace::clock_sync::{self, WallClockRange};
+use crate::trace::filter::{filter_block_data, filter_ufs_data};
+use crate::trace::lifecycle::RequestTracker;
+use crate::trace::timestamp;
+use crate::trace::utils::calculate_statistics;
+use crate::trace::{Block, LatencySummary, UFS};
+
+// 결과에 포함할 최대 매칭 행 수 (요약 / comm 통계는 전체 기준)
+const MAX_REPORTED_MATCHES: usize = 50_000;
+// UFS 명령 하나당 확인할 최대 후보 block 요청 수
+const MAX_CANDIDATES: usize = 64;
+// 4KB lba 하나에 해당하는 sector 수
+const SECTORS_PER_LBA: u64 = 8;
+
+// 짝지어진 block 요청 (issue ~ complete, 나노초)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockRequest {
+    // issue / complete 행 번호
+    pub issue_index: usize,
+    pub complete_index: usize,
+    pub issue: i64,
+    pub complete: i64,
+    pub op: &'static str,
+    pub devmajor: u32,
+    pub devminor: u32,
+    pub io_type: String,
+    pub sector: u64,
+    pub size: u32, // sector 단위
+    pub comm: String,
+    pub process: String,
+}
+
+impl BlockRequest {
+    // 4KB lba 범위 [start, end)
+    fn lba_range(&self) -> (u64, u64) {
+        let end_sector = self.sector + self.size as u64;
+        (self.sector / SECTORS_PER_LBA, end_sector.div_ceil(SECTORS_PER_LBA))
+    }
+}
+
+// 완료된 UFS 명령 (send ~ complete, 나노초)
+#[derive(Debug, Clone, PartialEq)]
+pub struct UfsCommand {
+    pub send: i64,
+    pub complete: i64,
+    pub op: &'static str,
+    pub opcode: String,
+    pub tag: u32,
+    pub lba: u64,
+    pub size: u32, // 4KB 단위
+    pub process: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CrossLayerMatch {
+    pub op: String,
+    pub comm: String,
+    pub process: String,
+    pub device: String,
+    pub io_type: String,
+    pub sector: u64,
+    pub block_size: u32,
+    pub block_issue_time: f64,
+    pub block_complete_time: f64,
+    pub opcode: String,
+    pub tag: u32,
+    pub lba: u64,
+    pub ufs_size: u32,
+    // UFS 명령이 실행된 컨텍스트 (complete_rsp 행의 process)
+    pub ufs_process: String,
+    pub ufs_send_time: f64,
+    pub ufs_complete_time: f64,
+    pub block_latency_ms: f64,
+    pub ufs_latency_ms: f64,
+    // block issue -> UFS send
+    pub issue_overhead_ms: f64,
+    // UFS complete -> block complete
+    pub complete_overhead_ms: f64,
+    // 이 block 요청을 처리한 UFS 명령 수 (분할 요청이면 2 이상)
+    pub ufs_commands: usize,
+    pub wall_clock: Option<String>,
+}
+
+// 요청을 발생시킨 comm별 UFS 명령 집계
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct CommStats {
+    pub comm: String,
+    pub block_requests: usize,
+    pub ufs_commands: usize,
+    pub bytes: u64,
+    pub avg_ufs_latency_ms: f64,
+    pub avg_host_overhead_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CrossLayerReport {
+    pub block_requests: usize,
+    pub matched_block_requests: usize,
+    pub ufs_commands: usize,
+    pub matched_ufs_commands: usize,
+    // UFS 명령 2개 이상으로 나뉜 block 요청 수
+    pub split_requests: usize,
+    // op별 host 오버헤드 (ms)
+    pub issue_overhead: BTreeMap<String, LatencySummary>,
+    pub complete_overhead: BTreeMap<String, LatencySummary>,
+    // UFS 명령 수 순
+    pub comm_stats: Vec<CommStats>,
+    // 매칭 목록이 MAX_REPORTED_MATCHES에서 잘렸는지 여부
+    pub truncated: bool,
+    pub matches: Vec<CrossLayerMatch>,
+    pub wall_clock: Option<WallClockRange>,
+}
+
+// UFS opcode의 block 연산 종류
+pub fn ufs_op(opcode: &str) -> &'static str {
+    match opcode {
+        "0x08" | "0x28" | "0x88" => "read",
+        "0x0a" | "0x2a" | "0x8a" => "write",
+        "0x42" => "discard",
+        "0x35" => "flush",
+        _ => "other",
+    }
+}
+
+/// 시간순 block 행에서 issue / complete 짝짓기 ((장치, sector, 연산) 단위 FIFO)
+/// requeue된 issue는 버리고 다시 들어온 issue 기준으로 짝짓는다.
+pub fn pair_block_requests(rows: &[Block]) -> Vec<BlockRequest> {
+    let mut pending: HashMap<(u32, u32, u64, &'static str), VecDeque<usize>> = HashMap::new();
+    let mut requests = Vec::new();
+    for (index, row) in rows.iter().enumerate() {
+        let op = block_op(&row.io_type);
+        let key = (row.devmajor, row.devminor, row.sector, op);
+        match row.action.as_str() {
+            "block_rq_issue" => pending.entry(key).or_default().push_back(index),
+            "block_rq_requeue" => {
+                if let Some(queue) = pending.get_mut(&key) {
+                    queue.pop_back();
+                }
+            }
+            "block_rq_complete" => {
+                if let Some(issue_index) = pending.get_mut(&key).and_then(|q| q.pop_front()) {
+                    let issue = &rows[issue_index];
+                    requests.push(BlockRequest {
+                        issue_index,
+                        complete_index: index,
+                        issue: issue.time,
+                        complete: row.time,
+                        op,
+                        devmajor: issue.devmajor,
+                        devminor: issue.devminor,
+                        io_type: issue.io_type.clone(),
+                        sector: issue.sector,
+                        size: issue.size,
+                        comm: issue.comm.clone(),
+                        process: issue.process.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    requests
+}
+
+/// UFS 명령마다 처리한 block 요청 번호를 찾는다 (없으면 None)
+/// 주소 범위를 포함하는 후보 중 시작 주소가 가장 가까운 요청, 같으면 먼저 issue된 요청을 고르고
+/// block 요청의 lba를 모두 처리하면 더 이상 매칭하지 않는다.
+pub fn match_commands(blocks: &[BlockRequest], commands: &[UfsCommand], tolerance: i64) -> Vec<Option<usize>> {
+    let mut block_order: Vec<usize> = (0..blocks.len()).collect();
+    block_order.sort_by_key(|&i| (blocks[i].issue, i));
+    let mut command_order: Vec<usize> = (0..commands.len()).collect();
+    command_order.sort_by_key(|&i| (commands[i].send, i));
+
+    // 처리해야 할 남은 lba 수 (flush 등 size 0 요청은 명령 1개)
+    let mut remaining: Vec<u64> = blocks
+        .iter()
+        .map(|b| {
+            let (start, end) = b.lba_range();
+            (end - start).max(1)
+        })
+        .collect();
+    // (연산, 시작 lba, issue 순서, 요청 번호)
+    let mut active: BTreeSet<(&'static str, u64, usize, usize)> = BTreeSet::new();
+    let mut expiry: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+    let mut rank = vec![0usize; blocks.len()];
+    let mut next_block = 0;
+    let mut result = vec![None; commands.len()];
+
+    for &ci in &command_order {
+        let cmd = &commands[ci];
+        while next_block < block_order.len() && blocks[block_order[next_block]].issue <= cmd.send + tolerance {
+            let bi = block_order[next_block];
+            rank[bi] = next_block;
+            active.insert((blocks[bi].op, blocks[bi].lba_range().0, next_block, bi));
+            expiry.push(Reverse((blocks[bi].complete, bi)));
+            next_block += 1;
+        }
+        // send 이전에 끝난 요청은 이후 명령과도 매칭될 수 없음
+        while let Some(&Reverse((complete, bi))) = expiry.peek() {
+            if complete + tolerance >= cmd.send {
+                break;
+            }
+            expiry.pop();
+            active.remove(&(blocks[bi].op, blocks[bi].lba_range().0, rank[bi], bi));
+        }
+
+        let cmd_end = cmd.lba + cmd.size as u64;
+        let mut best: Option<(u64, usize, usize)> = None;
+        for &(_, start, order, bi) in active
+            .range((cmd.op, 0, 0, 0)..=(cmd.op, cmd.lba, usize::MAX, usize::MAX))
+            .rev()
+            .take(MAX_CANDIDATES)
+        {
+            if best.is_some_and(|(best_start, _, _)| best_start != start) {
+                break;
+            }
+            let block = &blocks[bi];
+            if block.lba_range().1 >= cmd_end && block.complete + tolerance >= cmd.complete {
+                best = Some((start, order, bi));
+            }
+        }
+
+        if let Some((start, order, bi)) = best {
+            result[ci] = Some(bi);
+            remaining[bi] = remaining[bi].saturating_sub((cmd.size as u64).max(1));
+            if remaining[bi] == 0 {
+                active.remove(&(blocks[bi].op, start, order, bi));
+            }
+        }
+    }
+    result
+}
+
+fn overhead_summary(values: HashMap<String, Vec<f64>>) -> BTreeMap<String, LatencySummary> {
+    values
+        .into_iter()
+        .map(|(op, mut v)| (op, calculate_statistics(&mut v)))
+        .collect()
+}
+
+/// 매칭 결과로 리포트 생성 (wall_clock 제외)
+pub fn build_report(blocks: &[BlockRequest], commands: &[UfsCommand], matched: &[Option<usize>]) -> CrossLayerReport {
+    let mut per_block: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (ci, bi) in matched.iter().enumerate() {
+        if let Some(bi) = bi {
+            per_block.entry(*bi).or_default().push(ci);
+        }
+    }
+
+    let mut report = CrossLayerReport {
+        block_requests: blocks.len(),
+        matched_block_requests: per_block.len(),
+        ufs_commands: commands.len(),
+        matched_ufs_commands: matched.iter().filter(|m| m.is_some()).count(),
+        split_requests: per_block.values().filter(|c| c.len() > 1).count(),
+        ..Default::default()
+    };
+
+    let mut issue_overhead: HashMap<String, Vec<f64>> = HashMap::new();
+    let mut complete_overhead: HashMap<String, Vec<f64>> = HashMap::new();
+    // comm -> (집계, UFS 레이턴시 합, host 오버헤드 합)
+    let mut comms: HashMap<String, (CommStats, f64, f64)> = HashMap::new();
+
+    let mut block_indices: Vec<usize> = per_block.keys().copied().collect();
+    block_indices.sort_by_key(|&bi| (blocks[bi].issue, bi));
+    for bi in block_indices {
+        let block = &blocks[bi];
+        let mut cmds = per_block.remove(&bi).unwrap_or_default();
+        cmds.sort_by_key(|&ci| (commands[ci].send, ci));
+        let comm = comms.entry(block.comm.clone()).or_insert_with(|| {
+            (CommStats { comm: block.comm.clone(), ..Default::default() }, 0.0, 0.0)
+        });
+        comm.0.block_requests += 1;
+
+        for &ci in &cmds {
+            let cmd = &commands[ci];
+            let issue_ms = timestamp::ns_to_ms(cmd.send - block.issue);
+            let complete_ms = timestamp::ns_to_ms(block.complete - cmd.complete);
+            let ufs_latency_ms = timestamp::ns_to_ms(cmd.complete - cmd.send);
+            issue_overhead.entry(block.op.to_string()).or_default().push(issue_ms);
+            complete_overhead.entry(block.op.to_string()).or_default().push(complete_ms);
+
+            comm.0.ufs_commands += 1;
+            comm.0.bytes += cmd.size as u64 * 4096;
+            comm.1 += ufs_latency_ms;
+            comm.2 += issue_ms + complete_ms;
+
+            if report.matches.len() >= MAX_REPORTED_MATCHES {
+                report.truncated = true;
+                continue;
+            }
+            report.matches.push(CrossLayerMatch {
+                op: block.op.to_string(),
+                comm: block.comm.clone(),
+                process: block.process.clone(),
+                device: format!("{}:{}", block.devmajor, block.devminor),
+                io_type: block.io_type.clone(),
+                sector: block.sector,
+                block_size: block.size,
+                block_issue_time: timestamp::ns_to_secs(block.issue),
+                block_complete_time: timestamp::ns_to_secs(block.complete),
+                opcode: cmd.opcode.clone(),
+                tag: cmd.tag,
+                lba: cmd.lba,
+                ufs_size: cmd.size,
+                ufs_process: cmd.process.clone(),
+                ufs_send_time: timestamp::ns_to_secs(cmd.send),
+                ufs_complete_time: timestamp::ns_to_secs(cmd.complete),
+                block_latency_ms: timestamp::ns_to_ms(block.complete - block.issue),
+                ufs_latency_ms,
+                issue_overhead_ms: issue_ms,
+                complete_overhead_ms: complete_ms,
+                ufs_commands: cmds.len(),
+                wall_clock: None,
+            });
+        }
+    }
+
+    report.issue_overhead = overhead_summary(issue_overhead);
+    report.complete_overhead = overhead_summary(complete_overhead);
+    report.comm_stats = comms
+        .into_values()
+        .map(|(mut stats, latency_sum, overhead_sum)| {
+            if stats.ufs_commands > 0 {
+                stats.avg_ufs_latency_ms = latency_sum / stats.ufs_commands as f64;
+                stats.avg_host_overhead_ms = overhead_sum / stats.ufs_commands as f64;
+            }
+            stats
+        })
+        .collect();
+    report
+        .comm_stats
+        .sort_by(|a, b| b.ufs_commands.cmp(&a.ufs_commands).then(a.comm.cmp(&b.comm)));
+    report
+}
+
+// 완료된 UFS 명령 목록 (orphan 제외, dtoc로 send 시각 복원)
+fn ufs_commands(rows: Vec<UFS>) -> Vec<UfsCommand> {
+    rows.into_iter()
+        .filter(|u| u.action == "complete_rsp" && !u.orphan)
+        .map(|u| UfsCommand {
+            send: u.time - (u.dtoc * timestamp::NS_PER_MS as f64).round() as i64,
+            complete: u.time,
+            op: ufs_op(&u.opcode),
+            opcode: u.opcode,
+            tag: u.tag,
+            lba: u.lba,
+            size: u.size,
+            process: u.process,
+        })
+        .collect()
+}
+
+/// 후처리된 block / UFS 행 전체를 매칭하여 UFS 명령의 send_req / complete_rsp 행에 block 요청의 comm 기록
+/// comm이 기록된 UFS 명령 수 반환
+pub fn assign_ufs_comm(block_rows: &[Block], ufs_rows: &mut [UFS]) -> usize {
+    let blocks = pair_block_requests(block_rows);
+    let mut tracker = RequestTracker::new();
+    // (send_req 행, complete_rsp 행) - commands와 같은 순서
+    let mut command_rows = Vec::new();
+    let mut commands = Vec::new();
+    for (index, ufs) in ufs_rows.iter().enumerate() {
+        match ufs.action.as_str() {
+            "send_req" => {
+                tracker.send(index, ufs);
+            }
+            "complete_rsp" => {
+                if let Some(send) = tracker.complete(index, ufs) {
+                    if ufs.orphan {
+                        continue;
+                    }
+                    command_rows.push((send.index, index));
+                    commands.push(UfsCommand {
+                        send: send.time,
+                        complete: ufs.time,
+                        op: ufs_op(&ufs.opcode),
+                        opcode: ufs.opcode.clone(),
+                        tag: ufs.tag,
+                        lba: ufs.lba,
+                        size: ufs.size,
+                        process: ufs.process.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let matched = match_commands(&blocks, &commands, 0);
+    let mut assigned = 0;
+    for ((send, complete), bi) in command_rows.into_iter().zip(matched) {
+        if let Some(bi) = bi {
+            ufs_rows[send].comm = blocks[bi].comm.clone();
+            ufs_rows[complete].comm = blocks[bi].comm.clone();
+            assigned += 1;
+        }
+    }
+    println!("🔗 UFS 명령 {}/{}개에 block 요청 comm 기록", assigned, commands.len());
+    assigned
+}
+
+/// block_ufs_match 명령 - 구간 안의 block 요청과 UFS 명령 매칭
+pub fn cross_layer_match(
+    logname: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    tolerance_ms: f64,
+    device: Option<&str>,
+) -> Result<String, String> {
+    let block_rows = filter_block_data(logname, time_from, time_to, "sector", None, None, None)?;
+    let block_rows = filter_device(block_rows, device)?;
+    let ufs_rows = filter_ufs_data(logname, time_from, time_to, "lba", None, None, None)?;
+    if block_rows.is_empty() || ufs_rows.is_empty() {
+        return Err("Block / UFS 트레이스가 모두 있어야 계층 간 매칭을 할 수 있습니다".to_string());
+    }
+
+    let blocks = pair_block_requests(&block_rows);
+    let commands = ufs_commands(ufs_rows);
+    let tolerance = (tolerance_ms.max(0.0) * timestamp::NS_PER_MS as f64).round() as i64;
+    let matched = match_commands(&blocks, &commands, tolerance);
+    let mut report = build_report(&blocks, &commands, &matched);
+
+    if let Some(sync) = clock_sync::clock_sync_for_logname(logname) {
+        for m in &mut report.matches {
+            m.wall_clock = Some(sync.format(m.block_issue_time));
+        }
+        let first = blocks.iter().map(|b| b.issue).min().unwrap_or(0);
+        let last = blocks.iter().map(|b| b.complete).max().unwrap_or(0);
+        report.wall_clock = Some(sync.range(
+            time_from.unwrap_or(timestamp::ns_to_secs(first)),
+            time_to.unwrap_or(timestamp::ns_to_secs(last)),
+        ));
+    }
+
+    println!(
+        "🔗 Block-UFS 매칭: block 요청 {}/{}개, UFS 명령 {}/{}개 매칭 (분할 요청 {}개)",
+        report.matched_block_requests,
+        report.block_requests,
+        report.matched_ufs_commands,
+        report.ufs_commands,
+        report.split_requests
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support::{block, ufs};
+
+    fn with_request(block: Block, io_type: &str, size: u32, comm: &str) -> Block {
+        Block { io_type: io_type.to_string(), size, comm: comm.to_string(), ..block }
+    }
+
+    fn cmd(send_us: i64, complete_us: i64, opcode: &str, lba: u64, size: u32) -> UfsCommand {
+        UfsCommand {
+            send: send_us * 1_000,
+            complete: complete_us * 1_000,
+            op: ufs_op(opcode),
+            opcode: opcode.to_string(),
+            tag: 0,
+            lba,
+            size,
+            process: "irq".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pair_and_match_split_request() {
+        let rows = vec![
+            with_request(block(100, "block_rq_issue", 8000), "WS", 2048, "app"), // 1MB write
+            with_request(block(110, "block_rq_issue", 80), "R", 8, "reader"),
+            with_request(block(400, "block_rq_complete", 80), "R", 8, "[0]"),
+            with_request(block(900, "block_rq_complete", 8000), "WS", 2048, "[0]"),
+        ];
+        let blocks = pair_block_requests(&rows);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].comm, "reader");
+        assert_eq!((blocks[0].issue_index, blocks[0].complete_index), (1, 2));
+        assert_eq!(blocks[0].lba_range(), (10, 11));
+
+        let commands = vec![
+            cmd(120, 500, "0x2a", 1000, 128), // write 앞쪽 절반
+            cmd(130, 390, "0x28", 10, 1),
+            cmd(140, 880, "0x2a", 1128, 128), // write 뒤쪽 절반
+            cmd(150, 300, "0x28", 500, 1),    // 대응하는 block 요청 없음
+            cmd(160, 600, "0x2a", 10, 1),     // 연산이 다름
+        ];
+        let matched = match_commands(&blocks, &commands, 0);
+        assert_eq!(matched, vec![Some(1), Some(0), Some(1), None, None]);
+
+        let report = build_report(&blocks, &commands, &matched);
+        assert_eq!((report.matched_block_requests, report.matched_ufs_commands, report.split_requests), (2, 3, 1));
+        assert_eq!(report.matches[0].comm, "app");
+        assert_eq!(report.matches[0].ufs_commands, 2);
+        assert!((report.matches[0].issue_overhead_ms - 0.02).abs() < 1e-9);
+        assert!((report.matches[1].complete_overhead_ms - 0.02).abs() < 1e-9);
+        assert_eq!(report.comm_stats[0].comm, "app");
+        assert_eq!(report.comm_stats[0].bytes, 256 * 4096);
+    }
+
+    #[test]
+    fn test_repeated_sector_matches_in_issue_order() {
+        let rows = vec![
+            with_request(block(100, "block_rq_issue", 64), "R", 8, "first"),
+            with_request(block(105, "block_rq_issue", 64), "R", 8, "second"),
+            with_request(block(300, "block_rq_complete", 64), "R", 8, "[0]"),
+            with_request(block(320, "block_rq_complete", 64), "R", 8, "[0]"),
+            with_request(block(400, "block_rq_issue", 0), "F", 0, "flusher"),
+            with_request(block(500, "block_rq_complete", 0), "F", 0, "[0]"),
+        ];
+        let blocks = pair_block_requests(&rows);
+        let commands = vec![
+            cmd(110, 290, "0x28", 8, 1),
+            cmd(115, 310, "0x28", 8, 1),
+            cmd(410, 490, "0x35", 0, 0),
+        ];
+        let matched = match_commands(&blocks, &commands, 0);
+        assert_eq!(matched, vec![Some(0), Some(1), Some(2)]);
+        assert_eq!(blocks[1].comm, "second");
+        // 허용 오차가 없으면 block complete 이후에 끝난 UFS 명령은 매칭되지 않음
+        let late = vec![cmd(110, 350, "0x28", 8, 1)];
+        assert_eq!(match_commands(&blocks[..1], &late, 0), vec![None]);
+        assert_eq!(match_commands(&blocks[..1], &late, 100_000), vec![Some(0)]);
+    }
+
+    #[test]
+    fn test_assign_ufs_comm_to_matched_commands() {
+        let block_rows = vec![
+            with_request(block(100, "block_rq_issue", 8000), "WS", 2048, "app"),
+            with_request(block(900, "block_rq_complete", 8000), "WS", 2048, "[0]"),
+        ];
+        let write = |time_us, action, tag, lba| UFS {
+            opcode: "0x2a".to_string(),
+            lba,
+            size: 128,
+            ..ufs(time_us, action, tag)
+        };
+        let mut ufs_rows = vec![
+            write(120, "send_req", 1, 1000),
+            write(140, "send_req", 2, 1128),
+            ufs(150, "send_req", 3), // 대응하는 block 요청 없음
+            ufs(300, "complete_rsp", 3),
+            write(500, "complete_rsp", 1, 1000),
+            write(880, "complete_rsp", 2, 1128),
+        ];
+        assert_eq!(assign_ufs_comm(&block_rows, &mut ufs_rows), 2);
+        let comms: Vec<&str> = ufs_rows.iter().map(|u| u.comm.as_str()).collect();
+        assert_eq!(comms, vec!["app", "app", "", "", "app", "app"]);
+    }
+}
@@ -0,0 +1,570 @@
+// src-tauri/src/trace/event_table.rs
+//
+// 패턴/format 파일 없이 모든 ftrace 라인을 하나의 이벤트 테이블로 보관
+// 헤더(time, cpu, process, pid, flags, event)와 원본 args를 그대로 저장하고,
+// args의 key=value / key: value 쌍은 map 컬럼(fields)으로 분리한다.
+// trace_printk 등 임의 이벤트를 조회하거나 UFS/Block 차트에 겹쳐 보기 위함
+
+use arrow::array::{
+    Array, ArrayRef, Float64Array, MapArray, MapBuilder, StringArray, StringBuilder, UInt32Array,
+};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{create_dir_all, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::clock_sync::clock_sync_from_marker;
+use super::ftrace_format::parse_header;
+use super::provenance::{self, ParseReport, SourceLog, TraceProvenance};
+use super::utils::read_file_with_encoding_detection;
+use super::{FtraceEvent, EVENT_CACHE};
+
+// parquet 저장 시 한 번에 변환하는 레코드 수
+const CHUNK_SIZE: usize = 400_000;
+// filter_events에서 limit을 지정하지 않았을 때 반환하는 최대 행 수
+const DEFAULT_EVENT_LIMIT: usize = 10_000;
+
+// "key=value", "key: value" 쌍 (값은 따옴표 문자열 또는 구분자 전까지)
+static ARG_PAIR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?:^|[\s,(\[{])(?P<key>[A-Za-z_][A-Za-z0-9_.]*)(?:=|:\s+)(?P<value>"[^"]*"|[^\s,)\]}]*)"#,
+    )
+    .unwrap()
+});
+
+// 버퍼 overrun으로 유실된 이벤트 ("CPU:2 [LOST 1234 EVENTS]")
+static LOST_EVENTS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*CPU:(\d+)\s+\[LOST (\d+) EVENTS\]").unwrap());
+// 유실 라인을 기록하는 이벤트 이름 (시각은 다음 이벤트 시각)
+pub const LOST_EVENT: &str = "lost_events";
+
+// fields 값 비교 조건 - value는 문자열 일치, min/max는 숫자(10진수/0x 16진수) 범위
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArgFilter {
+    pub key: String,
+    pub value: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventFilter {
+    pub time_from: Option<f64>,
+    pub time_to: Option<f64>,
+    pub events: Option<Vec<String>>,
+    // 프로세스 이름 부분 일치
+    pub process: Option<String>,
+    pub pid: Option<u32>,
+    pub cpu: Option<u32>,
+    // 원본 args 부분 일치
+    pub args_contains: Option<String>,
+    #[serde(default)]
+    pub arg_filters: Vec<ArgFilter>,
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventFilterResult {
+    // offset/limit 적용 전 조건에 맞는 전체 행 수
+    pub total: usize,
+    pub rows: Vec<FtraceEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventSummary {
+    pub event: String,
+    pub count: usize,
+    pub first_time: f64,
+    pub last_time: f64,
+    // 이 이벤트에서 한 번이라도 나온 fields 키
+    pub keys: BTreeSet<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GenericParseReport {
+    pub filename: String,
+    pub total_lines: usize,
+    pub rows: usize,
+    // ftrace 헤더 형식이 아닌 라인 수
+    pub unparsed_lines: usize,
+    pub events: BTreeMap<String, usize>,
+}
+
+/// args에서 key=value / key: value 쌍 추출 (같은 키가 반복되면 첫 값 유지)
+pub fn parse_arg_pairs(args: &str) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    for caps in ARG_PAIR.captures_iter(args) {
+        let value = caps["value"].trim_matches('"').trim_end_matches(':');
+        fields
+            .entry(caps["key"].to_string())
+            .or_insert_with(|| value.to_string());
+    }
+    fields
+}
+
+/// ftrace 라인 하나를 이벤트 레코드로 변환 (헤더 형식이 아니면 None)
+pub fn parse_event_line(line: &str) -> Option<FtraceEvent> {
+    let header = parse_header(line)?;
+    Some(FtraceEvent {
+        time: header.time,
+        cpu: header.cpu,
+        process: header.process.to_string(),
+        pid: header.pid,
+        flags: header.flags.to_string(),
+        event: header.event.to_string(),
+        args: header.args.to_string(),
+        fields: parse_arg_pairs(header.args),
+    })
+}
+
+/// 유실 이벤트 라인 해석 - (cpu, 유실 수)
+pub fn parse_lost_line(line: &str) -> Option<(u32, u64)> {
+    let caps = LOST_EVENTS.captures(line)?;
+    Some((caps[1].parse().ok()?, caps[2].parse().ok()?))
+}
+
+// 유실 라인을 이벤트로 변환 - 유실 라인에는 시각이 없으므로 바로 다음 이벤트 시각 사용 (로그 끝이면 마지막 이벤트)
+fn fill_lost_events(lines: &[&str], parsed: &mut [Option<FtraceEvent>]) {
+    let mut next_time = parsed.iter().rev().flatten().next().map_or(0.0, |e| e.time);
+    for i in (0..parsed.len()).rev() {
+        if let Some(event) = &parsed[i] {
+            next_time = event.time;
+            continue;
+        }
+        if let Some((cpu, count)) = parse_lost_line(lines[i]) {
+            let args = format!("cpu={} lost={}", cpu, count);
+            parsed[i] = Some(FtraceEvent {
+                time: next_time,
+                cpu,
+                process: String::new(),
+                pid: 0,
+                flags: String::new(),
+                event: LOST_EVENT.to_string(),
+                fields: parse_arg_pairs(&args),
+                args,
+            });
+        }
+    }
+}
+
+// fields 값의 숫자 해석 (10진수, 0x 16진수)
+fn numeric_value(value: &str) -> Option<f64> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(|v| v as f64),
+        None => value.parse().ok(),
+    }
+}
+
+fn event_schema() -> Schema {
+    let map_field = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new())
+        .finish()
+        .data_type()
+        .clone();
+    Schema::new(vec![
+        Field::new("time", DataType::Float64, false),
+        Field::new("cpu", DataType::UInt32, false),
+        Field::new("process", DataType::Utf8, false),
+        Field::new("pid", DataType::UInt32, false),
+        Field::new("flags", DataType::Utf8, false),
+        Field::new("event", DataType::Utf8, false),
+        Field::new("args", DataType::Utf8, false),
+        Field::new("fields", map_field, false),
+    ])
+}
+
+/// 이벤트 레코드를 RecordBatch로 변환
+pub fn events_to_record_batch(events: &[FtraceEvent]) -> Result<RecordBatch, String> {
+    let mut fields = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new());
+    for event in events {
+        for (key, value) in &event.fields {
+            fields.keys().append_value(key);
+            fields.values().append_value(value);
+        }
+        fields.append(true).map_err(|e| e.to_string())?;
+    }
+
+    let arrays: Vec<ArrayRef> = vec![
+        Arc::new(Float64Array::from_iter_values(events.iter().map(|e| e.time))),
+        Arc::new(UInt32Array::from_iter_values(events.iter().map(|e| e.cpu))),
+        Arc::new(StringArray::from_iter_values(events.iter().map(|e| e.process.as_str()))),
+        Arc::new(UInt32Array::from_iter_values(events.iter().map(|e| e.pid))),
+        Arc::new(StringArray::from_iter_values(events.iter().map(|e| e.flags.as_str()))),
+        Arc::new(StringArray::from_iter_values(events.iter().map(|e| e.event.as_str()))),
+        Arc::new(StringArray::from_iter_values(events.iter().map(|e| e.args.as_str()))),
+        Arc::new(fields.finish()),
+    ];
+
+    RecordBatch::try_new(Arc::new(event_schema()), arrays).map_err(|e| e.to_string())
+}
+
+fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T, String> {
+    batch
+        .column_by_name(name)
+        .and_then(|c| c.as_any().downcast_ref::<T>())
+        .ok_or_else(|| format!("이벤트 테이블 '{}' 컬럼 형식이 올바르지 않습니다", name))
+}
+
+/// RecordBatch를 이벤트 레코드로 변환
+pub fn record_batch_to_events(batch: &RecordBatch) -> Result<Vec<FtraceEvent>, String> {
+    let time = column::<Float64Array>(batch, "time")?;
+    let cpu = column::<UInt32Array>(batch, "cpu")?;
+    let process = column::<StringArray>(batch, "process")?;
+    let pid = column::<UInt32Array>(batch, "pid")?;
+    let flags = column::<StringArray>(batch, "flags")?;
+    let event = column::<StringArray>(batch, "event")?;
+    let args = column::<StringArray>(batch, "args")?;
+    let fields = column::<MapArray>(batch, "fields")?;
+
+    let keys = fields
+        .keys()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or("이벤트 테이블 fields 키 형식이 올바르지 않습니다")?;
+    let values = fields
+        .values()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or("이벤트 테이블 fields 값 형식이 올바르지 않습니다")?;
+    let offsets = fields.value_offsets();
+
+    Ok((0..batch.num_rows())
+        .map(|i| FtraceEvent {
+            time: time.value(i),
+            cpu: cpu.value(i),
+            process: process.value(i).to_string(),
+            pid: pid.value(i),
+            flags: flags.value(i).to_string(),
+            event: event.value(i).to_string(),
+            args: args.value(i).to_string(),
+            fields: (offsets[i] as usize..offsets[i + 1] as usize)
+                .map(|j| (keys.value(j).to_string(), values.value(j).to_string()))
+                .collect(),
+        })
+        .collect())
+}
+
+// {timestamp}_events.parquet 저장 (readtrace의 ufs/block 파일 구분에 걸리지 않는 이름)
+fn save_events_to_parquet(
+    events: &[FtraceEvent],
+    folder_path: &Path,
+    timestamp: &str,
+    provenance: &TraceProvenance,
+) -> Result<String, String> {
+    let mut path = folder_path.to_path_buf();
+    path.push(format!("{}_events.parquet", timestamp));
+
+    let props = WriterProperties::builder()
+        .set_key_value_metadata(Some(provenance.key_value_metadata("events")?))
+        .build();
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    let mut writer =
+        ArrowWriter::try_new(file, Arc::new(event_schema()), Some(props)).map_err(|e| e.to_string())?;
+
+    for chunk in events.chunks(CHUNK_SIZE) {
+        let batch = events_to_record_batch(chunk)?;
+        writer.write(&batch).map_err(|e| e.to_string())?;
+    }
+
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 로그의 모든 ftrace 라인을 이벤트 테이블로 저장
+/// skip_events가 주어지면 해당 이벤트는 제외 (예: 이미 UFS/Block으로 파싱되는 이벤트)
+pub fn parse_generic_events(
+    fname: String,
+    logfolder: String,
+    skip_events: Option<Vec<String>>,
+) -> Result<String, String> {
+    let file_size = std::fs::metadata(&fname).map(|m| m.len()).unwrap_or(0);
+    let hash_handle = {
+        let path = fname.clone();
+        std::thread::spawn(move || provenance::hash_file(&path))
+    };
+
+    let content = read_file_with_encoding_detection(Path::new(&fname))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let total_lines = lines.len();
+    let skip_events = skip_events.unwrap_or_default();
+
+    // ftrace 헤더 형식이 아닌 라인은 None
+    let mut parsed: Vec<Option<FtraceEvent>> = lines
+        .par_iter()
+        .map(|line| parse_event_line(line))
+        .collect();
+    fill_lost_events(&lines, &mut parsed);
+    let unparsed_lines = parsed.iter().filter(|e| e.is_none()).count();
+
+    let events: Vec<FtraceEvent> = parsed
+        .into_iter()
+        .flatten()
+        .filter(|e| !skip_events.contains(&e.event))
+        .collect();
+    if events.is_empty() {
+        return Err("ftrace 형식의 이벤트 라인이 없습니다".to_string());
+    }
+
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for event in &events {
+        *counts.entry(event.event.clone()).or_insert(0) += 1;
+    }
+
+    let stem = PathBuf::from(&fname)
+        .file_stem()
+        .ok_or("Invalid filename")?
+        .to_string_lossy()
+        .to_string();
+    let mut folder_path = PathBuf::from(logfolder);
+    folder_path.push(&stem);
+    create_dir_all(&folder_path).map_err(|e| e.to_string())?;
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+
+    let provenance = TraceProvenance::new(
+        SourceLog {
+            path: fname.clone(),
+            size: file_size,
+            sha256: provenance::join_hash(hash_handle),
+        },
+        Vec::new(),
+        "generic",
+        ParseReport {
+            total_lines: Some(total_lines),
+            missing_lines: Some(unparsed_lines),
+            ..Default::default()
+        },
+    )
+    .with_clock_sync(
+        events
+            .iter()
+            .find_map(|e| clock_sync_from_marker(&e.event, e.time, &e.args)),
+    );
+    let filename = save_events_to_parquet(&events, &folder_path, &timestamp, &provenance)?;
+    println!("💾 이벤트 테이블 저장: {} ({}개 이벤트, {}종류)", filename, events.len(), counts.len());
+
+    let report = GenericParseReport {
+        filename: filename.clone(),
+        total_lines,
+        rows: events.len(),
+        unparsed_lines,
+        events: counts,
+    };
+
+    // 바로 조회할 수 있도록 캐시에 보관
+    EVENT_CACHE
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(filename, events);
+
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+fn read_events_parquet(path: &str) -> Result<Vec<FtraceEvent>, String> {
+    let file = File::open(path).map_err(|e| format!("parquet 파일 열기 실패: {}", e))?;
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let mut events = Vec::new();
+    for batch in reader {
+        let batch = batch.map_err(|e| e.to_string())?;
+        events.extend(record_batch_to_events(&batch)?);
+    }
+    Ok(events)
+}
+
+// 캐시에 없으면 parquet을 읽어 캐시에 넣은 뒤 f 적용
+pub(crate) fn with_events<R>(path: &str, f: impl FnOnce(&[FtraceEvent]) -> R) -> Result<R, String> {
+    let mut cache = EVENT_CACHE.lock().map_err(|e| e.to_string())?;
+    if !cache.contains_key(path) {
+        println!("⚡ 이벤트 테이블 캐시 없음, parquet 로드: '{}'", path);
+        let events = read_events_parquet(path)?;
+        cache.insert(path.to_string(), events);
+    }
+    Ok(f(&cache[path]))
+}
+
+fn matches_filter(event: &FtraceEvent, filter: &EventFilter) -> bool {
+    if filter.time_from.is_some_and(|t| event.time < t) || filter.time_to.is_some_and(|t| event.time > t) {
+        return false;
+    }
+    if filter.events.as_ref().is_some_and(|events| !events.contains(&event.event)) {
+        return false;
+    }
+    if filter.process.as_ref().is_some_and(|p| !event.process.contains(p.as_str())) {
+        return false;
+    }
+    if filter.pid.is_some_and(|pid| event.pid != pid) || filter.cpu.is_some_and(|cpu| event.cpu != cpu) {
+        return false;
+    }
+    if filter.args_contains.as_ref().is_some_and(|s| !event.args.contains(s.as_str())) {
+        return false;
+    }
+
+    filter.arg_filters.iter().all(|cond| {
+        let Some(value) = event.fields.get(&cond.key) else {
+            return false;
+        };
+        if cond.value.as_ref().is_some_and(|v| v != value) {
+            return false;
+        }
+        if cond.min.is_none() && cond.max.is_none() {
+            return true;
+        }
+        numeric_value(value).is_some_and(|n| {
+            cond.min.is_none_or(|min| n >= min) && cond.max.is_none_or(|max| n <= max)
+        })
+    })
+}
+
+/// 조건에 맞는 이벤트 (시간순, offset/limit 적용)
+pub fn filter_events(events: &[FtraceEvent], filter: &EventFilter) -> EventFilterResult {
+    let matched: Vec<&FtraceEvent> = events
+        .par_iter()
+        .filter(|event| matches_filter(event, filter))
+        .collect();
+
+    let offset = filter.offset.unwrap_or(0);
+    let limit = filter.limit.unwrap_or(DEFAULT_EVENT_LIMIT);
+    EventFilterResult {
+        total: matched.len(),
+        rows: matched.into_iter().skip(offset).take(limit).cloned().collect(),
+    }
+}
+
+/// 이벤트별 개수, 시간 범위, fields 키 목록
+pub fn summarize_events(events: &[FtraceEvent]) -> Vec<EventSummary> {
+    let mut summaries: BTreeMap<&str, EventSummary> = BTreeMap::new();
+    for event in events {
+        let summary = summaries.entry(&event.event).or_insert_with(|| EventSummary {
+            event: event.event.clone(),
+            count: 0,
+            first_time: event.time,
+            last_time: event.time,
+            keys: BTreeSet::new(),
+        });
+        summary.count += 1;
+        summary.first_time = summary.first_time.min(event.time);
+        summary.last_time = summary.last_time.max(event.time);
+        summary.keys.extend(event.fields.keys().cloned());
+    }
+    summaries.into_values().collect()
+}
+
+/// filter_events 명령 - logname은 events parquet 경로
+pub fn filter_event_table(logname: String, filter: EventFilter) -> Result<String, String> {
+    let result = with_events(&logname, |events| filter_events(events, &filter))?;
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+/// event_summary 명령
+pub fn event_summary(logname: String) -> Result<String, String> {
+    let summaries = with_events(&logname, summarize_events)?;
+    serde_json::to_string(&summaries).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_line_extracts_pairs() {
+        let event = parse_event_line(
+            "  kworker/u16:3-811   [007] d..1. 123.456789: sched_switch: prev_comm=kworker/u16:3 prev_pid=811 prev_state=I ==> next_comm=swapper/7 next_pid=0",
+        )
+        .unwrap();
+        assert_eq!(event.event, "sched_switch");
+        assert_eq!(event.flags, "d..1.");
+        assert_eq!(event.fields["prev_comm"], "kworker/u16:3");
+        assert_eq!(event.fields["next_pid"], "0");
+
+        // key: value 형식과 trace_printk 메시지
+        let event = parse_event_line(
+            "  app-1234  [001] ..... 50.000001: bprint: my_func: gc start victim=0x1a0 cost: 12",
+        )
+        .unwrap();
+        assert_eq!(event.event, "bprint");
+        assert_eq!(event.args, "my_func: gc start victim=0x1a0 cost: 12");
+        assert_eq!(event.fields["victim"], "0x1a0");
+        assert_eq!(event.fields["cost"], "12");
+    }
+
+    #[test]
+    fn test_record_batch_round_trip() {
+        let events: Vec<FtraceEvent> = [
+            "  <idle>-0     [002] d.h2. 55.000100: sched_wakeup: comm=kworker/2:1 pid=120 prio=120 target_cpu=002",
+            "  app-1234  [001] ..... 55.000200: tracing_mark_write: B|1234|draw",
+        ]
+        .iter()
+        .map(|line| parse_event_line(line).unwrap())
+        .collect();
+
+        let batch = events_to_record_batch(&events).unwrap();
+        let restored = record_batch_to_events(&batch).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].fields, events[0].fields);
+        assert!(restored[1].fields.is_empty());
+        assert_eq!(restored[1].args, "B|1234|draw");
+    }
+
+    #[test]
+    fn test_lost_events_take_next_event_time() {
+        let lines = [
+            "  app-1234  [001] ..... 10.000000: tracing_mark_write: B|1234|draw",
+            "CPU:3 [LOST 42 EVENTS]",
+            "  app-1234  [001] ..... 10.500000: tracing_mark_write: E|1234",
+            "CPU:1 [LOST 7 EVENTS]",
+        ];
+        let mut parsed: Vec<Option<FtraceEvent>> = lines.iter().map(|l| parse_event_line(l)).collect();
+        fill_lost_events(&lines, &mut parsed);
+        let lost: Vec<&FtraceEvent> = parsed.iter().flatten().filter(|e| e.event == LOST_EVENT).collect();
+        assert_eq!(lost.len(), 2);
+        assert_eq!((lost[0].time, lost[0].cpu, lost[0].fields["lost"].as_str()), (10.5, 3, "42"));
+        assert_eq!(lost[1].time, 10.5);
+    }
+
+    #[test]
+    fn test_filter_events_by_field_range() {
+        let events: Vec<FtraceEvent> = (0..5)
+            .map(|i| {
+                parse_event_line(&format!(
+                    "  app-1  [000] ..... {}.0: my_event: tag={} lba=0x{:x}",
+                    i, i, i * 16
+                ))
+                .unwrap()
+            })
+            .collect();
+
+        let filter = EventFilter {
+            time_from: Some(1.0),
+            arg_filters: vec![ArgFilter {
+                key: "lba".to_string(),
+                value: None,
+                min: Some(16.0),
+                max: Some(48.0),
+            }],
+            limit: Some(2),
+            ..Default::default()
+        };
+        let result = filter_events(&events, &filter);
+        assert_eq!(result.total, 3);
+        assert_eq!(result.rows.len(), 2);
+        assert_eq!(result.rows[0].fields["tag"], "1");
+
+        let summary = summarize_events(&events);
+        assert_eq!(summary[0].count, 5);
+        assert_eq!(summary[0].last_time, 4.0);
+        assert!(summary[0].keys.contains("lba"));
+    }
+}
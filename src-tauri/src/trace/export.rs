@@ -1,13 +1,17 @@
 use std::fs::File;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use arrow::array::{RecordBatchWriter, Float64Array, Array};
-use arrow::datatypes::DataType;
+use arrow::array::{RecordBatchWriter, Float64Array, Array, ArrayRef, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
 use datafusion::arrow::csv::WriterBuilder;
 use datafusion::prelude::*; // RecordBatchWriter 트레이트 추가
 use serde::Deserialize;
 use tauri::Emitter;
 
+use crate::trace::clock_sync::{self, ClockSync};
+use crate::trace::timestamp;
 use crate::trace::ProgressEvent;
 
 // Excel의 최대 행 수 (헤더 제외)
@@ -23,11 +27,32 @@ pub struct FilterParams {
     pub col_to: Option<f64>,
 }
 
+// 시간 컬럼 바로 뒤에 실제 시각(wall_clock) 컬럼 추가
+fn add_wall_clock_column(batch: &RecordBatch, time_column: &str, sync: &ClockSync) -> Result<RecordBatch, String> {
+    let schema = batch.schema();
+    let time_index = schema.index_of(time_column).map_err(|e| e.to_string())?;
+    let times = batch
+        .column(time_index)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| format!("'{}' 컬럼이 Float64가 아닙니다", time_column))?;
+    let wall_clock: StringArray = times.iter().map(|t| t.map(|t| sync.format(t))).collect();
+
+    let mut fields: Vec<Field> = schema.fields().iter().map(|f| f.as_ref().clone()).collect();
+    let mut columns: Vec<ArrayRef> = batch.columns().to_vec();
+    fields.insert(time_index + 1, Field::new("wall_clock", DataType::Utf8, true));
+    columns.insert(time_index + 1, Arc::new(wall_clock));
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(|e| e.to_string())
+}
+
 // CSV 내보내기 공통 함수 (필터 지원)
+// wall_clock이 true이고 parquet에 시각 동기화 정보가 있으면 wall_clock 컬럼 추가
 pub async fn export_to_csv(
     parquet_path: String,
     output_dir: Option<String>,
     filter: Option<FilterParams>,
+    wall_clock: bool,
     window: Option<tauri::Window>,
 ) -> Result<Vec<String>, String> {
     let start_time = std::time::Instant::now();
@@ -80,8 +105,19 @@ pub async fn export_to_csv(
                     "time"
                 };
                 
+                // 현재 parquet는 Int64 나노초, 이전 버전은 Float64 초
+                let is_ns = schema
+                    .fields()
+                    .iter()
+                    .any(|f| f.name() == time_column && f.data_type() == &DataType::Int64);
+                let (from, to) = if is_ns {
+                    (lit(timestamp::secs_to_ns(t_from)), lit(timestamp::secs_to_ns(t_to)))
+                } else {
+                    (lit(t_from), lit(t_to))
+                };
+
                 df = df
-                    .filter(col(time_column).gt_eq(lit(t_from)).and(col(time_column).lt_eq(lit(t_to))))
+                    .filter(col(time_column).gt_eq(from).and(col(time_column).lt_eq(to)))
                     .map_err(|e| e.to_string())?;
                 
                 println!("⏱️  [Export] 시간 필터: {} ~ {}", t_from, t_to);
@@ -114,8 +150,27 @@ pub async fn export_to_csv(
     df = df.sort(vec![col(time_column).sort(true, false)])
         .map_err(|e| e.to_string())?;
 
-    // 데이터프레임에서 레코드 배치 가져오기
-    let batches = df.collect().await.map_err(|e| e.to_string())?;
+    // 데이터프레임에서 레코드 배치 가져오기 (CSV에는 시간 컬럼을 초 단위로 기록)
+    let mut batches = df
+        .collect()
+        .await
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(timestamp::seconds_view)
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if wall_clock {
+        match clock_sync::read_clock_sync(&parquet_path)? {
+            Some(sync) => {
+                println!("🕒 [Export] wall_clock 컬럼 추가 (offset {:.6}s)", sync.offset);
+                batches = batches
+                    .iter()
+                    .map(|batch| add_wall_clock_column(batch, time_column, &sync))
+                    .collect::<Result<Vec<_>, String>>()?;
+            }
+            None => println!("ℹ️  [Export] 시각 동기화 정보가 없어 wall_clock 컬럼을 추가하지 않습니다"),
+        }
+    }
 
     // 총 행 수 계산
     let total_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
@@ -1,3 +1,4 @@
+use crate::trace::timestamp;
 use crate::trace::{Block, BLOCK_CACHE, UFS, UFS_CACHE, UFSCUSTOM, UFSCUSTOM_CACHE};
 use rayon::prelude::*;
 
@@ -82,6 +83,8 @@ pub fn filter_ufs_data(
         if t_from == 0.0 && t_to == 0.0 {
             cached_ufs_list
         } else {
+            // UI 시간 범위(초)를 데이터 단위(나노초)로 변환
+            let (t_from, t_to) = (timestamp::secs_to_ns(t_from), timestamp::secs_to_ns(t_to));
             if use_parallel {
                 println!("⚡ [Performance] UFS 병렬 시간 필터링: {} 레코드", data_size);
                 cached_ufs_list
@@ -249,6 +252,8 @@ pub fn filter_block_data(
         if t_from == 0.0 && t_to == 0.0 {
             cached_block_list
         } else {
+            // UI 시간 범위(초)를 데이터 단위(나노초)로 변환
+            let (t_from, t_to) = (timestamp::secs_to_ns(t_from), timestamp::secs_to_ns(t_to));
             if use_parallel {
                 println!("⚡ [Performance] Block 병렬 시간 필터링: {} 레코드", data_size);
                 cached_block_list
@@ -401,6 +406,8 @@ pub fn filter_ufscustom_data(
         if t_from == 0.0 && t_to == 0.0 {
             cached_ufscustom_list
         } else {
+            // UI 시간 범위(초)를 데이터 단위(나노초)로 변환
+            let (t_from, t_to) = (timestamp::secs_to_ns(t_from), timestamp::secs_to_ns(t_to));
             if use_parallel {
                 println!("⚡ [Performance] UFSCUSTOM 병렬 시간 필터링: {} - {}", t_from, t_to);
                 cached_ufscustom_list
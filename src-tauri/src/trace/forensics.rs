@@ -0,0 +1,473 @@
+// src-tauri/src/trace/forensics.rs
+//
+// 느린 요청 분석 (top_slow_requests)
+// dtoc / ctod / ctoc 기준으로 가장 느린 N개 요청을 고르고 각 요청의 주변 상황을 함께 반환한다.
+// - issue / complete 시점의 QD
+// - 요청이 처리되는 동안 함께 in-flight였던 다른 요청
+// - 직전에 실행된 flush / discard / unmap 명령
+// - 주변의 전원 관리 / 이벤트 유실 마커 (이벤트 테이블이 있을 때)
+// 모든 요청은 트레이스 전체 기준 행 번호로 연결되어 UI에서 바로 이동할 수 있다.
+
+use serde::Serialize;
+
+use crate::trace::block;
+use crate::trace::clock_sync;
+use crate::trace::crosslayer::pair_block_requests;
+use crate::trace::event_table::{with_events, LOST_EVENT};
+use crate::trace::filter::{filter_block_data, filter_ufs_data, filter_ufscustom_data};
+use crate::trace::lifecycle::RequestTracker;
+use crate::trace::timestamp;
+use crate::trace::Block;
+
+// 한 번에 조회할 수 있는 최대 요청 수
+const MAX_TOP_N: usize = 1_000;
+// 요청 하나당 목록에 포함할 최대 in-flight 요청 수 (개수는 전체 기준)
+const MAX_LISTED_IN_FLIGHT: usize = 32;
+// 요청 하나당 포함할 최대 선행 flush / discard 수
+const MAX_PRECEDING: usize = 5;
+// 요청 하나당 포함할 최대 주변 마커 수
+const MAX_MARKERS: usize = 20;
+// 전원 관리 관련 이벤트 이름에 포함되는 문자열 (ufshcd_runtime_suspend, ufshcd_clk_gating 등)
+const POWER_EVENT_KEYWORDS: [&str; 5] = ["suspend", "resume", "clk_gating", "clk_scaling", "hibern8"];
+
+// issue ~ complete로 짝지어진 요청 (나노초, 행 번호는 트레이스 전체 기준)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracedRequest {
+    // complete 행 번호 (UFSCUSTOM은 요청 행)
+    pub index: usize,
+    pub issue_index: Option<usize>,
+    pub op: String,
+    // flush / discard / unmap 여부
+    pub barrier: bool,
+    pub lba: u64,
+    pub size: u32,
+    pub issue: i64,
+    pub complete: i64,
+    pub dtoc: f64,
+    pub ctod: f64,
+    pub ctoc: f64,
+    pub qd_at_issue: u32,
+    pub qd_at_complete: u32,
+    pub process: String,
+}
+
+impl TracedRequest {
+    fn metric(&self, metric: &str) -> f64 {
+        match metric {
+            "ctod" => self.ctod,
+            "ctoc" => self.ctoc,
+            _ => self.dtoc,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RelatedRequest {
+    pub index: usize,
+    pub issue_index: Option<usize>,
+    pub op: String,
+    pub lba: u64,
+    pub size: u32,
+    pub issue_time: f64,
+    pub complete_time: f64,
+    pub latency_ms: f64,
+    // 느린 요청 issue 시각 기준 (+면 이전에 완료, -면 issue 시점에 아직 처리 중)
+    pub gap_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct NearbyMarker {
+    // "power" | "lost"
+    pub kind: String,
+    pub time: f64,
+    pub event: String,
+    pub process: String,
+    pub args: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SlowRequest {
+    pub rank: usize,
+    pub value_ms: f64,
+    pub request: RelatedRequest,
+    pub process: String,
+    pub dtoc: f64,
+    pub ctod: f64,
+    pub ctoc: f64,
+    pub qd_at_issue: u32,
+    pub qd_at_complete: u32,
+    pub in_flight_count: usize,
+    pub in_flight: Vec<RelatedRequest>,
+    pub preceding_barriers: Vec<RelatedRequest>,
+    pub markers: Vec<NearbyMarker>,
+    pub wall_clock: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SlowRequestReport {
+    pub trace_type: String,
+    pub metric: String,
+    pub total_requests: usize,
+    // 선행 flush / discard / 마커를 찾는 범위 (ms)
+    pub window_ms: f64,
+    pub requests: Vec<SlowRequest>,
+}
+
+fn related(request: &TracedRequest, reference: i64) -> RelatedRequest {
+    RelatedRequest {
+        index: request.index,
+        issue_index: request.issue_index,
+        op: request.op.clone(),
+        lba: request.lba,
+        size: request.size,
+        issue_time: timestamp::ns_to_secs(request.issue),
+        complete_time: timestamp::ns_to_secs(request.complete),
+        latency_ms: timestamp::ns_to_ms(request.complete - request.issue),
+        gap_ms: timestamp::ns_to_ms(reference - request.complete),
+    }
+}
+
+/// 느린 요청 N개와 주변 요청 정보 (마커 제외)
+pub fn find_slow_requests(requests: &[TracedRequest], metric: &str, top_n: usize, window: i64) -> Vec<SlowRequest> {
+    let mut ranked: Vec<usize> = (0..requests.len()).collect();
+    ranked.sort_by(|&a, &b| {
+        requests[b]
+            .metric(metric)
+            .total_cmp(&requests[a].metric(metric))
+            .then(a.cmp(&b))
+    });
+    ranked.truncate(top_n.min(MAX_TOP_N));
+
+    // in-flight / 선행 명령 검색용 issue 시각 순서
+    let mut by_issue: Vec<usize> = (0..requests.len()).collect();
+    by_issue.sort_by_key(|&i| (requests[i].issue, i));
+    let barriers: Vec<usize> = by_issue.iter().copied().filter(|&i| requests[i].barrier).collect();
+    let max_duration = requests.iter().map(|r| r.complete - r.issue).max().unwrap_or(0);
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(rank, ri)| {
+            let req = &requests[ri];
+            // [issue, complete]가 겹치는 요청 (issue가 req.issue - max_duration 이전인 요청은 겹칠 수 없음)
+            let lo = by_issue.partition_point(|&i| requests[i].issue < req.issue - max_duration);
+            let hi = by_issue.partition_point(|&i| requests[i].issue < req.complete);
+            let overlapping: Vec<usize> = by_issue[lo..hi]
+                .iter()
+                .copied()
+                .filter(|&i| i != ri && requests[i].complete > req.issue)
+                .collect();
+
+            let end = barriers.partition_point(|&i| requests[i].issue < req.issue);
+            let preceding_barriers = barriers[..end]
+                .iter()
+                .rev()
+                .filter(|&&i| i != ri)
+                .take_while(|&&i| requests[i].issue >= req.issue - window)
+                .take(MAX_PRECEDING)
+                .map(|&i| related(&requests[i], req.issue))
+                .collect();
+
+            SlowRequest {
+                rank: rank + 1,
+                value_ms: req.metric(metric),
+                request: related(req, req.issue),
+                process: req.process.clone(),
+                dtoc: req.dtoc,
+                ctod: req.ctod,
+                ctoc: req.ctoc,
+                qd_at_issue: req.qd_at_issue,
+                qd_at_complete: req.qd_at_complete,
+                in_flight_count: overlapping.len(),
+                in_flight: overlapping
+                    .iter()
+                    .take(MAX_LISTED_IN_FLIGHT)
+                    .map(|&i| related(&requests[i], req.issue))
+                    .collect(),
+                preceding_barriers,
+                markers: Vec::new(),
+                wall_clock: None,
+            }
+        })
+        .collect()
+}
+
+// 주변 마커로 볼 이벤트 종류
+fn marker_kind(event: &str) -> Option<&'static str> {
+    if event == LOST_EVENT {
+        Some("lost")
+    } else if POWER_EVENT_KEYWORDS.iter().any(|k| event.contains(k)) {
+        Some("power")
+    } else {
+        None
+    }
+}
+
+// 이벤트 테이블에서 전원 관리 / 유실 마커만 시간순으로 추출
+fn load_markers(events_logname: &str) -> Result<Vec<NearbyMarker>, String> {
+    let mut markers = with_events(events_logname, |events| {
+        events
+            .iter()
+            .filter_map(|e| {
+                marker_kind(&e.event).map(|kind| NearbyMarker {
+                    kind: kind.to_string(),
+                    time: e.time,
+                    event: e.event.clone(),
+                    process: e.process.clone(),
+                    args: e.args.clone(),
+                })
+            })
+            .collect::<Vec<_>>()
+    })?;
+    markers.sort_by(|a, b| a.time.total_cmp(&b.time));
+    Ok(markers)
+}
+
+pub fn ufs_requests(logname: &str) -> Result<Vec<TracedRequest>, String> {
+    let rows = filter_ufs_data(logname, None, None, "lba", None, None, None)?;
+    let mut tracker = RequestTracker::new();
+    let mut requests = Vec::new();
+    for (index, ufs) in rows.iter().enumerate() {
+        match ufs.action.as_str() {
+            "send_req" => {
+                tracker.send(index, ufs);
+            }
+            "complete_rsp" => {
+                if let Some(send) = tracker.complete(index, ufs) {
+                    if ufs.orphan {
+                        continue;
+                    }
+                    let send_row = &rows[send.index];
+                    requests.push(TracedRequest {
+                        index,
+                        issue_index: Some(send.index),
+                        op: ufs.opcode.clone(),
+                        barrier: matches!(ufs.opcode.as_str(), "0x35" | "0x42"),
+                        lba: ufs.lba,
+                        size: ufs.size,
+                        issue: send.time,
+                        complete: ufs.time,
+                        dtoc: ufs.dtoc,
+                        ctod: send_row.ctod,
+                        ctoc: ufs.ctoc,
+                        qd_at_issue: send_row.qd,
+                        qd_at_complete: ufs.qd,
+                        process: send_row.process.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(requests)
+}
+
+// device("major:minor")가 있으면 해당 장치 요청만 - 행 번호는 트레이스 전체 기준 유지
+pub fn block_requests(logname: &str, device: Option<&str>) -> Result<Vec<TracedRequest>, String> {
+    let rows = filter_block_data(logname, None, None, "sector", None, None, None)?;
+    traced_block_requests(&rows, device)
+}
+
+fn traced_block_requests(rows: &[Block], device: Option<&str>) -> Result<Vec<TracedRequest>, String> {
+    let device = device.filter(|d| !d.trim().is_empty()).map(block::parse_device).transpose()?;
+    Ok(pair_block_requests(rows)
+        .into_iter()
+        .filter(|b| device.is_none_or(|d| (b.devmajor, b.devminor) == d))
+        .map(|b| {
+            let issue_row = &rows[b.issue_index];
+            let complete_row = &rows[b.complete_index];
+            TracedRequest {
+                index: b.complete_index,
+                issue_index: Some(b.issue_index),
+                barrier: matches!(b.op, "flush" | "discard"),
+                op: b.io_type,
+                lba: b.sector,
+                size: b.size,
+                issue: b.issue,
+                complete: b.complete,
+                dtoc: complete_row.dtoc,
+                ctod: issue_row.ctod,
+                ctoc: complete_row.ctoc,
+                qd_at_issue: issue_row.qd,
+                qd_at_complete: complete_row.qd,
+                process: b.comm,
+            }
+        })
+        .collect())
+}
+
+fn ufscustom_requests(logname: &str) -> Result<Vec<TracedRequest>, String> {
+    let rows = filter_ufscustom_data(logname, None, None, "lba", None, None, None)?;
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, u)| TracedRequest {
+            index,
+            issue_index: None,
+            barrier: matches!(u.opcode.as_str(), "0x35" | "0x42"),
+            op: u.opcode,
+            lba: u.lba,
+            size: u.size,
+            issue: u.start_time,
+            complete: u.end_time,
+            dtoc: u.dtoc,
+            ctod: u.ctod,
+            ctoc: u.ctoc,
+            qd_at_issue: u.start_qd,
+            qd_at_complete: u.end_qd,
+            process: String::new(),
+        })
+        .collect())
+}
+
+/// top_slow_requests 명령
+/// 행 번호를 트레이스 전체 기준으로 유지하기 위해 전체 요청을 읽은 뒤 구간(complete 시각 기준)으로 거른다.
+#[allow(clippy::too_many_arguments)]
+pub fn top_slow_requests(
+    logname: &str,
+    trace_type: &str,
+    metric: &str,
+    top_n: usize,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    window_ms: f64,
+    events_logname: Option<&str>,
+) -> Result<String, String> {
+    if !matches!(metric, "dtoc" | "ctod" | "ctoc") {
+        return Err(format!("지원하지 않는 기준: {} (dtoc / ctod / ctoc)", metric));
+    }
+    let mut requests = match trace_type {
+        "ufs" => ufs_requests(logname)?,
+        "block" => block_requests(logname, None)?,
+        "ufscustom" => ufscustom_requests(logname)?,
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    };
+    let from = time_from.map(timestamp::secs_to_ns);
+    let to = time_to.map(timestamp::secs_to_ns);
+    requests.retain(|r| from.is_none_or(|t| r.complete >= t) && to.is_none_or(|t| r.complete <= t));
+    if requests.is_empty() {
+        return Err(format!("분석할 {} 요청이 없습니다", trace_type));
+    }
+
+    let window = (window_ms.max(0.0) * timestamp::NS_PER_MS as f64).round() as i64;
+    let mut slow = find_slow_requests(&requests, metric, top_n, window);
+
+    if let Some(events_logname) = events_logname.filter(|name| !name.is_empty()) {
+        let markers = load_markers(events_logname)?;
+        let window_secs = timestamp::ns_to_secs(window);
+        for s in &mut slow {
+            let from = s.request.issue_time - window_secs;
+            let to = s.request.complete_time + window_secs;
+            let start = markers.partition_point(|m| m.time < from);
+            s.markers = markers[start..]
+                .iter()
+                .take_while(|m| m.time <= to)
+                .take(MAX_MARKERS)
+                .cloned()
+                .collect();
+        }
+    }
+    if let Some(sync) = clock_sync::clock_sync_for_logname(logname) {
+        for s in &mut slow {
+            s.wall_clock = Some(sync.format(s.request.issue_time));
+        }
+    }
+
+    let report = SlowRequestReport {
+        trace_type: trace_type.to_string(),
+        metric: metric.to_string(),
+        total_requests: requests.len(),
+        window_ms,
+        requests: slow,
+    };
+    println!(
+        "🐢 {} 느린 요청 {}개 ({} 기준, 전체 {}개)",
+        trace_type,
+        report.requests.len(),
+        metric,
+        report.total_requests
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support;
+
+    fn req(index: usize, op: &str, issue_us: i64, complete_us: i64) -> TracedRequest {
+        TracedRequest {
+            index,
+            issue_index: Some(index.saturating_sub(1)),
+            op: op.to_string(),
+            barrier: matches!(op, "0x35" | "0x42"),
+            lba: index as u64 * 8,
+            size: 8,
+            issue: issue_us * 1_000,
+            complete: complete_us * 1_000,
+            dtoc: (complete_us - issue_us) as f64 / 1000.0,
+            ctod: 0.0,
+            ctoc: 0.0,
+            qd_at_issue: 1,
+            qd_at_complete: 1,
+            process: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_slow_request_context() {
+        let requests = vec![
+            req(1, "0x42", 0, 50),     // unmap
+            req(3, "0x2a", 40, 60),    // 느린 요청 issue 전에 끝남
+            req(5, "0x28", 100, 900),  // 가장 느린 요청
+            req(7, "0x28", 200, 300),  // 처리 중에 함께 in-flight
+            req(9, "0x35", 850, 1200), // 완료 직전 flush (선행 아님)
+            req(11, "0x28", 1300, 1400),
+        ];
+        let slow = find_slow_requests(&requests, "dtoc", 2, 1_000_000);
+        assert_eq!(slow.len(), 2);
+        let top = &slow[0];
+        assert_eq!((top.rank, top.request.index, top.request.issue_index), (1, 5, Some(4)));
+        assert_eq!(top.value_ms, 0.8);
+        assert_eq!(top.in_flight_count, 2);
+        let in_flight: Vec<usize> = top.in_flight.iter().map(|r| r.index).collect();
+        assert_eq!(in_flight, vec![7, 9]);
+        assert_eq!(top.preceding_barriers.len(), 1);
+        assert_eq!(top.preceding_barriers[0].index, 1);
+        assert_eq!(top.preceding_barriers[0].gap_ms, 0.05);
+
+        // 두 번째로 느린 flush
+        assert_eq!(slow[1].request.index, 9);
+        assert_eq!(slow[1].in_flight.iter().map(|r| r.index).collect::<Vec<_>>(), vec![5]);
+
+        // 범위 밖의 선행 명령은 제외
+        let narrow = find_slow_requests(&requests, "dtoc", 1, 10_000);
+        assert!(narrow[0].preceding_barriers.is_empty());
+    }
+
+    #[test]
+    fn test_block_requests_device_filter() {
+        let dm = |b: Block| Block { devmajor: 253, devminor: 1, ..b };
+        let rows = vec![
+            test_support::block(100, "block_rq_issue", 64),
+            dm(test_support::block(110, "block_rq_issue", 64)),
+            dm(test_support::block(200, "block_rq_complete", 64)),
+            test_support::block(300, "block_rq_complete", 64),
+        ];
+        assert_eq!(traced_block_requests(&rows, None).unwrap().len(), 2);
+
+        // 행 번호는 필터 전 트레이스 기준
+        let requests = traced_block_requests(&rows, Some("253:1")).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!((requests[0].issue_index, requests[0].index), (Some(1), 2));
+        assert!(traced_block_requests(&rows, Some("sda")).is_err());
+    }
+
+    #[test]
+    fn test_marker_kind() {
+        assert_eq!(marker_kind("ufshcd_runtime_suspend"), Some("power"));
+        assert_eq!(marker_kind("ufshcd_clk_gating"), Some("power"));
+        assert_eq!(marker_kind(LOST_EVENT), Some("lost"));
+        assert_eq!(marker_kind("sched_switch"), None);
+    }
+}
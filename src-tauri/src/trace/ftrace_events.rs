@@ -0,0 +1,268 @@
+// src-tauri/src/trace/ftrace_events.rs
+//
+// format 파일로 만든 이벤트 파서(EVENT_PARSERS)를 로그에 적용하여 이벤트별 parquet 저장
+// 헤더 값(time, cpu, process, pid) 뒤에 print fmt 인자 순서대로 타입 있는 컬럼이 붙는다.
+
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray, UInt32Array, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use chrono::Local;
+use parquet::arrow::ArrowWriter;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir_all, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::ftrace_format::{parse_header, ColumnKind, EventParser, EventValue};
+use super::utils::read_file_with_encoding_detection;
+use super::EVENT_PARSERS;
+
+// parquet 저장 시 한 번에 변환하는 레코드 수
+const CHUNK_SIZE: usize = 400_000;
+
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    pub time: f64,
+    pub cpu: u32,
+    pub process: String,
+    pub pid: u32,
+    pub values: Vec<EventValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventFileResult {
+    pub event: String,
+    pub system: String,
+    pub filename: String,
+    pub rows: usize,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventParseReport {
+    pub total_lines: usize,
+    pub files: Vec<EventFileResult>,
+    // 파서는 있지만 args 형식이 맞지 않은 라인 수 (이벤트별)
+    pub mismatched: BTreeMap<String, usize>,
+}
+
+/// 이벤트 레코드를 파서 컬럼 정의에 맞는 RecordBatch로 변환
+pub fn event_to_record_batch(parser: &EventParser, records: &[EventRecord]) -> Result<RecordBatch, String> {
+    let mut fields = vec![
+        Field::new("time", DataType::Float64, false),
+        Field::new("cpu", DataType::UInt32, false),
+        Field::new("process", DataType::Utf8, false),
+        Field::new("pid", DataType::UInt32, false),
+    ];
+    let mut arrays: Vec<ArrayRef> = vec![
+        Arc::new(Float64Array::from_iter_values(records.iter().map(|r| r.time))),
+        Arc::new(UInt32Array::from_iter_values(records.iter().map(|r| r.cpu))),
+        Arc::new(StringArray::from_iter_values(records.iter().map(|r| r.process.as_str()))),
+        Arc::new(UInt32Array::from_iter_values(records.iter().map(|r| r.pid))),
+    ];
+
+    for (i, column) in parser.columns.iter().enumerate() {
+        let values = records.iter().map(|r| &r.values[i]);
+        let (data_type, array): (DataType, ArrayRef) = match column.kind {
+            ColumnKind::Int => (
+                DataType::Int64,
+                Arc::new(
+                    values
+                        .map(|v| match v {
+                            EventValue::Int(v) => Some(*v),
+                            _ => None,
+                        })
+                        .collect::<Int64Array>(),
+                ),
+            ),
+            ColumnKind::UInt | ColumnKind::Hex => (
+                DataType::UInt64,
+                Arc::new(
+                    values
+                        .map(|v| match v {
+                            EventValue::UInt(v) => Some(*v),
+                            _ => None,
+                        })
+                        .collect::<UInt64Array>(),
+                ),
+            ),
+            ColumnKind::Text => (
+                DataType::Utf8,
+                Arc::new(
+                    values
+                        .map(|v| match v {
+                            EventValue::Text(v) => Some(v.as_str()),
+                            _ => None,
+                        })
+                        .collect::<StringArray>(),
+                ),
+            ),
+        };
+        fields.push(Field::new(&column.name, data_type, true));
+        arrays.push(array);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).map_err(|e| e.to_string())
+}
+
+// 이벤트 하나의 레코드를 {timestamp}_event_{name}.parquet로 저장
+fn save_event_to_parquet(
+    parser: &EventParser,
+    records: &[EventRecord],
+    folder_path: &Path,
+    timestamp: &str,
+) -> Result<String, String> {
+    let mut path = folder_path.to_path_buf();
+    path.push(format!("{}_event_{}.parquet", timestamp, parser.name));
+
+    let first_batch = event_to_record_batch(parser, &records[..records.len().min(CHUNK_SIZE)])?;
+    let file = File::create(&path).map_err(|e| e.to_string())?;
+    let mut writer = ArrowWriter::try_new(file, first_batch.schema(), None).map_err(|e| e.to_string())?;
+    writer.write(&first_batch).map_err(|e| e.to_string())?;
+
+    for chunk in records[first_batch.num_rows()..].chunks(CHUNK_SIZE) {
+        let batch = event_to_record_batch(parser, chunk)?;
+        writer.write(&batch).map_err(|e| e.to_string())?;
+    }
+
+    writer.close().map_err(|e| e.to_string())?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 로그 파일에 등록된 이벤트 파서를 적용하여 이벤트별 parquet 저장
+/// events가 주어지면 해당 이벤트만 처리
+pub fn parse_event_log(
+    fname: String,
+    logfolder: String,
+    events: Option<Vec<String>>,
+) -> Result<String, String> {
+    let parsers: HashMap<String, EventParser> = {
+        let registry = EVENT_PARSERS.read().map_err(|e| e.to_string())?;
+        registry
+            .iter()
+            .filter(|(name, _)| events.as_ref().is_none_or(|events| events.contains(name)))
+            .map(|(name, parser)| (name.clone(), parser.clone()))
+            .collect()
+    };
+    if parsers.is_empty() {
+        return Err("적용할 이벤트 파서가 없습니다. format 파일을 먼저 불러와주세요.".to_string());
+    }
+
+    let content = read_file_with_encoding_detection(Path::new(&fname))?;
+    let lines: Vec<&str> = content.lines().collect();
+    let total_lines = lines.len();
+
+    // (이벤트 이름, 파싱 결과) - args 형식이 맞지 않으면 None
+    let parsed: Vec<(&str, Option<EventRecord>)> = lines
+        .par_iter()
+        .filter_map(|line| {
+            let header = parse_header(line)?;
+            let parser = parsers.get(header.event)?;
+            let record = parser.parse_args(header.args).map(|values| EventRecord {
+                time: header.time,
+                cpu: header.cpu,
+                process: header.process.to_string(),
+                pid: header.pid,
+                values,
+            });
+            Some((parser.name.as_str(), record))
+        })
+        .collect();
+
+    let mut records_by_event: BTreeMap<&str, Vec<EventRecord>> = BTreeMap::new();
+    let mut mismatched: BTreeMap<String, usize> = BTreeMap::new();
+    for (event, record) in parsed {
+        match record {
+            Some(record) => records_by_event.entry(event).or_default().push(record),
+            None => *mismatched.entry(event.to_string()).or_insert(0) += 1,
+        }
+    }
+
+    let stem = PathBuf::from(&fname)
+        .file_stem()
+        .ok_or("Invalid filename")?
+        .to_string_lossy()
+        .to_string();
+    let mut folder_path = PathBuf::from(logfolder);
+    folder_path.push(&stem);
+    create_dir_all(&folder_path).map_err(|e| e.to_string())?;
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+
+    let mut files = Vec::new();
+    for (event, records) in &records_by_event {
+        let parser = &parsers[*event];
+        let filename = save_event_to_parquet(parser, records, &folder_path, &timestamp)?;
+        println!("💾 {} 이벤트 {}개 저장: {}", event, records.len(), filename);
+
+        files.push(EventFileResult {
+            event: event.to_string(),
+            system: parser.system.clone(),
+            filename,
+            rows: records.len(),
+            columns: parser.columns.iter().map(|c| c.name.clone()).collect(),
+        });
+    }
+
+    for (event, count) in &mismatched {
+        println!("⚠️  {} 이벤트 중 print fmt 형식과 맞지 않는 라인: {}개", event, count);
+    }
+
+    let report = EventParseReport {
+        total_lines,
+        files,
+        mismatched,
+    };
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::ftrace_format::{build_parser, EventFormat};
+
+    #[test]
+    fn test_event_to_record_batch_typed_columns() {
+        let parser = build_parser(&EventFormat {
+            system: "sched".to_string(),
+            name: "sched_wakeup".to_string(),
+            id: None,
+            fields: Vec::new(),
+            print_fmt: "comm=%s pid=%d prio=%d target_cpu=%03d".to_string(),
+            args: vec![
+                "REC->comm".to_string(),
+                "REC->pid".to_string(),
+                "REC->prio".to_string(),
+                "REC->target_cpu".to_string(),
+            ],
+        })
+        .unwrap();
+
+        let header = parse_header(
+            "  <idle>-0     [002] d.h2. 55.000100: sched_wakeup: comm=kworker/2:1 pid=120 prio=120 target_cpu=002",
+        )
+        .unwrap();
+        let record = EventRecord {
+            time: header.time,
+            cpu: header.cpu,
+            process: header.process.to_string(),
+            pid: header.pid,
+            values: parser.parse_args(header.args).unwrap(),
+        };
+
+        let batch = event_to_record_batch(&parser, &[record]).unwrap();
+        let schema = batch.schema();
+        // 헤더의 pid와 겹치는 인자 컬럼은 arg_ 접두사
+        assert!(schema.field_with_name("arg_pid").is_ok());
+        assert_eq!(schema.field_with_name("prio").unwrap().data_type(), &DataType::Int64);
+        assert_eq!(schema.field_with_name("comm").unwrap().data_type(), &DataType::Utf8);
+
+        let target_cpu = batch
+            .column(schema.index_of("target_cpu").unwrap())
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(target_cpu.value(0), 2);
+    }
+}
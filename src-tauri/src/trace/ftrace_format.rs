@@ -0,0 +1,713 @@
+// src-tauri/src/trace/ftrace_format.rs
+//
+// tracefs의 events/<sys>/<event>/format 파일로 이벤트별 파서 생성
+// print fmt의 변환 지정자와 인자 목록에서 정규식과 타입 있는 컬럼을 만든다.
+// 정규식을 직접 작성하지 않고도 새 tracepoint나 벤더 tracepoint를 분석하기 위함
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use super::EVENT_PARSERS;
+
+// ftrace 라인 공통 헤더: "comm-pid (tgid) [cpu] flags time: event: args"
+// tgid와 flags는 trace_options에 따라 없을 수 있음
+static FTRACE_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?P<process>.*?)-(?P<pid>\d+)\s+(?:\(\s*[\d-]+\)\s+)?\[(?P<cpu>\d+)\]\s+(?:(?P<flags>\S+)\s+)?(?P<time>\d+\.\d+):\s+(?P<event>[A-Za-z0-9_]+):\s?(?P<args>.*)$",
+    )
+    .unwrap()
+});
+
+// 이벤트별 parquet에서 헤더 값이 사용하는 컬럼 이름 (인자 컬럼과 겹치면 arg_ 접두사)
+pub const HEADER_COLUMNS: [&str; 4] = ["time", "cpu", "process", "pid"];
+
+static REC_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"REC->([A-Za-z_][A-Za-z0-9_]*)").unwrap());
+static GET_STR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"__get_(?:str|bitmask|dynamic_array)\(\s*([A-Za-z_][A-Za-z0-9_]*)\s*\)").unwrap()
+});
+
+// format 파일의 field: 라인
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatField {
+    pub c_type: String,
+    pub name: String,
+    pub offset: u32,
+    pub size: u32,
+    pub signed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventFormat {
+    pub system: String,
+    pub name: String,
+    pub id: Option<u32>,
+    pub fields: Vec<FormatField>,
+    pub print_fmt: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnKind {
+    Int,
+    UInt,
+    // %x - UInt64 컬럼으로 저장
+    Hex,
+    Text,
+}
+
+// print fmt 인자 하나에 대응하는 컬럼
+#[derive(Debug, Clone, Serialize)]
+pub struct EventColumn {
+    pub name: String,
+    pub kind: ColumnKind,
+    // 인자가 참조하는 format 필드 (REC->field, __get_str(field))
+    pub field: Option<String>,
+    pub arg: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EventParser {
+    pub system: String,
+    pub name: String,
+    pub id: Option<u32>,
+    pub fields: Vec<FormatField>,
+    pub columns: Vec<EventColumn>,
+    // 이벤트 args 부분(헤더 제외)에 적용하는 정규식
+    pub pattern: String,
+    #[serde(skip)]
+    regex: Regex,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum EventValue {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    Null,
+}
+
+// ftrace 라인 공통 헤더 파싱 결과
+#[derive(Debug, Clone, Copy)]
+pub struct EventHeader<'a> {
+    pub process: &'a str,
+    pub pid: u32,
+    pub cpu: u32,
+    pub flags: &'a str,
+    pub time: f64,
+    pub event: &'a str,
+    pub args: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormatLoadError {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FormatLoadReport {
+    pub loaded: Vec<EventParser>,
+    pub failed: Vec<FormatLoadError>,
+}
+
+/// ftrace 라인에서 공통 헤더 분리
+pub fn parse_header(line: &str) -> Option<EventHeader<'_>> {
+    let caps = FTRACE_LINE.captures(line)?;
+    Some(EventHeader {
+        process: caps.name("process")?.as_str().trim(),
+        pid: caps["pid"].parse().ok()?,
+        cpu: caps["cpu"].parse().ok()?,
+        flags: caps.name("flags").map_or("", |m| m.as_str()),
+        time: caps["time"].parse().ok()?,
+        event: caps.name("event")?.as_str(),
+        args: caps.name("args")?.as_str(),
+    })
+}
+
+// "field:unsigned int nr_sector;\toffset:24;\tsize:4;\tsigned:0;"
+fn parse_field_line(line: &str) -> Option<FormatField> {
+    let mut parts = line.split(';').map(str::trim);
+    let decl = parts.next()?.strip_prefix("field:")?.trim();
+
+    let (c_type, name) = decl.rsplit_once(char::is_whitespace)?;
+    // 배열 필드 "char rwbs[8]"은 이름에서 [] 제거하고 타입에 붙임
+    let (name, c_type) = match name.split_once('[') {
+        Some((name, array)) => (name, format!("{}[{}", c_type.trim(), array)),
+        None => (name, c_type.trim().to_string()),
+    };
+
+    let mut field = FormatField {
+        c_type,
+        name: name.to_string(),
+        offset: 0,
+        size: 0,
+        signed: false,
+    };
+    for part in parts {
+        if let Some(v) = part.strip_prefix("offset:") {
+            field.offset = v.parse().unwrap_or(0);
+        } else if let Some(v) = part.strip_prefix("size:") {
+            field.size = v.parse().unwrap_or(0);
+        } else if let Some(v) = part.strip_prefix("signed:") {
+            field.signed = v == "1";
+        }
+    }
+    Some(field)
+}
+
+// 최상위 쉼표로 인자 분리 (괄호와 문자열/문자 리터럴 내부 제외)
+fn split_args(text: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            current.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                current.push(c);
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' | ']' | '}' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' if depth == 0 => args.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    args.push(current);
+
+    args.into_iter()
+        .map(|arg| arg.trim().to_string())
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+// print fmt: "fmt" "fmt2", arg1, arg2 -> (fmt 문자열, 인자 목록)
+fn split_print_fmt(text: &str) -> Result<(String, Vec<String>), String> {
+    let mut chars = text.chars().peekable();
+    let mut fmt = String::new();
+    let mut found = false;
+
+    // 인접한 문자열 리터럴은 하나로 이어붙임
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek() != Some(&'"') {
+            break;
+        }
+        chars.next();
+        found = true;
+
+        let mut closed = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    closed = true;
+                    break;
+                }
+                '\\' => match chars.next() {
+                    Some('n') => fmt.push('\n'),
+                    Some('t') => fmt.push('\t'),
+                    Some(other) => fmt.push(other),
+                    None => break,
+                },
+                _ => fmt.push(c),
+            }
+        }
+        if !closed {
+            return Err("print fmt 문자열이 닫히지 않았습니다".to_string());
+        }
+    }
+
+    if !found {
+        return Err("print fmt 문자열이 없습니다".to_string());
+    }
+
+    let rest: String = chars.collect();
+    let rest = rest.trim();
+    let rest = rest.strip_prefix(',').unwrap_or(rest);
+    Ok((fmt, split_args(rest)))
+}
+
+/// format 파일 내용 파싱 (system은 상위 디렉터리 이름)
+pub fn parse_format(system: &str, text: &str) -> Result<EventFormat, String> {
+    let mut name = None;
+    let mut id = None;
+    let mut fields = Vec::new();
+    let mut print_fmt = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(v) = line.strip_prefix("name:") {
+            name = Some(v.trim().to_string());
+        } else if let Some(v) = line.strip_prefix("ID:") {
+            id = v.trim().parse().ok();
+        } else if line.starts_with("field:") {
+            if let Some(field) = parse_field_line(line) {
+                fields.push(field);
+            }
+        } else if let Some(v) = line.strip_prefix("print fmt:") {
+            print_fmt = Some(v.trim().to_string());
+        }
+    }
+
+    let name = name.ok_or("format 파일에 name이 없습니다")?;
+    let print_fmt = print_fmt.ok_or_else(|| format!("{}: print fmt가 없습니다", name))?;
+    let (print_fmt, args) = split_print_fmt(&print_fmt).map_err(|e| format!("{}: {}", name, e))?;
+
+    Ok(EventFormat {
+        system: system.to_string(),
+        name,
+        id,
+        fields,
+        print_fmt,
+        args,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpecKind {
+    Int,
+    UInt,
+    Hex,
+    Char,
+    Str,
+    Pointer,
+}
+
+#[derive(Debug, PartialEq)]
+enum FmtPiece {
+    Literal(String),
+    Spec {
+        kind: SpecKind,
+        // 폭이 지정되면 공백 padding이 붙을 수 있음
+        padded: bool,
+        // '*' 폭/정밀도가 소비하는 인자 수
+        star_args: usize,
+    },
+}
+
+// printf 형식 문자열을 리터럴과 변환 지정자로 분리
+fn parse_fmt(fmt: &str) -> Result<Vec<FmtPiece>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            literal.push('%');
+            continue;
+        }
+
+        while chars.peek().is_some_and(|c| "-+ #0".contains(*c)) {
+            chars.next();
+        }
+
+        let mut padded = false;
+        let mut star_args = 0;
+        if chars.peek() == Some(&'*') {
+            chars.next();
+            padded = true;
+            star_args += 1;
+        }
+        while chars.peek().is_some_and(char::is_ascii_digit) {
+            chars.next();
+            padded = true;
+        }
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                star_args += 1;
+            }
+            while chars.peek().is_some_and(char::is_ascii_digit) {
+                chars.next();
+            }
+        }
+        while chars.peek().is_some_and(|c| "hlLqjzt".contains(*c)) {
+            chars.next();
+        }
+
+        let kind = match chars.next() {
+            Some('d') | Some('i') => SpecKind::Int,
+            Some('u') => SpecKind::UInt,
+            Some('x') | Some('X') => SpecKind::Hex,
+            Some('c') => SpecKind::Char,
+            Some('s') => SpecKind::Str,
+            Some('p') => {
+                // 커널 %p 확장 (%pS, %pI4, %pU 등)
+                while chars.peek().is_some_and(char::is_ascii_alphanumeric) {
+                    chars.next();
+                }
+                SpecKind::Pointer
+            }
+            Some(other) => return Err(format!("지원하지 않는 변환 지정자: %{}", other)),
+            None => return Err("형식 문자열이 %로 끝납니다".to_string()),
+        };
+
+        if !literal.is_empty() {
+            pieces.push(FmtPiece::Literal(std::mem::take(&mut literal)));
+        }
+        pieces.push(FmtPiece::Spec {
+            kind,
+            padded,
+            star_args,
+        });
+    }
+
+    if !literal.is_empty() {
+        pieces.push(FmtPiece::Literal(literal));
+    }
+    Ok(pieces)
+}
+
+// 인자 식에서 컬럼 이름 추출
+fn column_name(arg: &str, index: usize) -> String {
+    if let Some(caps) = GET_STR.captures(arg) {
+        return caps[1].to_string();
+    }
+    let Some(caps) = REC_FIELD.captures(arg) else {
+        return format!("arg{}", index);
+    };
+    let field = &caps[1];
+
+    // dev_t를 MAJOR()/MINOR()로 전개한 식 (MINORBITS = 20)
+    let compact: String = arg.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.contains(">>20") {
+        format!("{}_major", field)
+    } else if compact.contains("<<20)-1") {
+        format!("{}_minor", field)
+    } else {
+        field.to_string()
+    }
+}
+
+fn referenced_field(arg: &str, fields: &[FormatField]) -> Option<String> {
+    GET_STR
+        .captures(arg)
+        .or_else(|| REC_FIELD.captures(arg))
+        .map(|caps| caps[1].to_string())
+        .filter(|name| fields.iter().any(|f| &f.name == name))
+}
+
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{}_{}", name, n);
+        n += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+/// format 정보로 이벤트 파서 생성
+pub fn build_parser(format: &EventFormat) -> Result<EventParser, String> {
+    let pieces = parse_fmt(&format.print_fmt).map_err(|e| format!("{}: {}", format.name, e))?;
+
+    let arg_count: usize = pieces
+        .iter()
+        .map(|piece| match piece {
+            FmtPiece::Spec { star_args, .. } => 1 + star_args,
+            FmtPiece::Literal(_) => 0,
+        })
+        .sum();
+    if arg_count != format.args.len() {
+        return Err(format!(
+            "{}: 변환 지정자 {}개와 인자 {}개가 맞지 않습니다",
+            format.name,
+            arg_count,
+            format.args.len()
+        ));
+    }
+
+    let mut args = format.args.iter();
+    let mut columns: Vec<EventColumn> = Vec::new();
+    let mut used = HashSet::new();
+    let mut pattern = String::from("^");
+
+    for (index, piece) in pieces.iter().enumerate() {
+        let (kind, padded, star_args) = match piece {
+            FmtPiece::Literal(text) => {
+                pattern.push_str(&regex::escape(text));
+                continue;
+            }
+            FmtPiece::Spec { kind, padded, star_args } => (*kind, *padded, *star_args),
+        };
+
+        for _ in 0..star_args {
+            args.next();
+        }
+        let Some(arg) = args.next() else {
+            return Err(format!("{}: 인자가 부족합니다", format.name));
+        };
+
+        let is_last = index + 1 == pieces.len();
+        let (column_kind, value_regex) = match kind {
+            SpecKind::Int => (ColumnKind::Int, r"-?\d+"),
+            SpecKind::UInt => (ColumnKind::UInt, r"\d+"),
+            SpecKind::Hex => (ColumnKind::Hex, r"(?:0[xX])?[0-9a-fA-F]+"),
+            SpecKind::Char => (ColumnKind::Text, r"."),
+            SpecKind::Pointer => (ColumnKind::Text, r"\S+"),
+            // 마지막 %s는 라인 끝까지, 그 외에는 다음 리터럴까지
+            SpecKind::Str if is_last => (ColumnKind::Text, r".*"),
+            SpecKind::Str => (ColumnKind::Text, r".*?"),
+        };
+
+        if padded {
+            pattern.push_str(r"\s*");
+        }
+        pattern.push('(');
+        pattern.push_str(value_regex);
+        pattern.push(')');
+
+        let mut name = column_name(arg, columns.len());
+        if HEADER_COLUMNS.contains(&name.as_str()) {
+            name = format!("arg_{}", name);
+        }
+        columns.push(EventColumn {
+            name: unique_name(name, &mut used),
+            kind: column_kind,
+            field: referenced_field(arg, &format.fields),
+            arg: arg.clone(),
+        });
+    }
+    pattern.push('$');
+
+    let regex = Regex::new(&pattern).map_err(|e| format!("{}: 정규식 생성 실패: {}", format.name, e))?;
+
+    Ok(EventParser {
+        system: format.system.clone(),
+        name: format.name.clone(),
+        id: format.id,
+        fields: format.fields.clone(),
+        columns,
+        pattern,
+        regex,
+    })
+}
+
+fn convert_value(kind: ColumnKind, text: &str) -> EventValue {
+    let text = text.trim();
+    let value = match kind {
+        ColumnKind::Int => text.parse().ok().map(EventValue::Int),
+        ColumnKind::UInt => text.parse().ok().map(EventValue::UInt),
+        ColumnKind::Hex => {
+            let digits = text.trim_start_matches("0x").trim_start_matches("0X");
+            u64::from_str_radix(digits, 16).ok().map(EventValue::UInt)
+        }
+        ColumnKind::Text => Some(EventValue::Text(text.to_string())),
+    };
+    value.unwrap_or(EventValue::Null)
+}
+
+impl EventParser {
+    /// 이벤트 args 부분을 컬럼 값으로 변환 (형식이 맞지 않으면 None)
+    pub fn parse_args(&self, args: &str) -> Option<Vec<EventValue>> {
+        let caps = self.regex.captures(args.trim_end())?;
+        // 값 그룹 외에는 캡처 그룹이 없으므로 i + 1번 그룹이 i번 컬럼
+        Some(
+            self.columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    caps.get(i + 1)
+                        .map_or(EventValue::Null, |m| convert_value(column.kind, m.as_str()))
+                })
+                .collect(),
+        )
+    }
+}
+
+// 디렉터리를 재귀적으로 돌며 "format" 파일 수집
+fn collect_format_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("디렉터리 읽기 실패 {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            collect_format_files(&path, files)?;
+        } else if path.file_name().is_some_and(|name| name == "format") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// events 디렉터리(또는 format 파일 하나)를 읽어 파서 생성
+/// 경로 구조는 <sys>/<event>/format 으로 가정하고 <sys>를 system 이름으로 사용
+pub fn load_format_dir(path: &str) -> Result<FormatLoadReport, String> {
+    let root = Path::new(path);
+    let mut files = Vec::new();
+    if root.is_file() {
+        files.push(root.to_path_buf());
+    } else {
+        collect_format_files(root, &mut files)?;
+    }
+    files.sort();
+
+    let mut report = FormatLoadReport {
+        loaded: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for file in files {
+        let system = file
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let result = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_format(&system, &text))
+            .and_then(|format| build_parser(&format));
+
+        match result {
+            Ok(parser) => report.loaded.push(parser),
+            Err(error) => report.failed.push(FormatLoadError {
+                path: file.to_string_lossy().to_string(),
+                error,
+            }),
+        }
+    }
+
+    println!(
+        "📄 이벤트 format 로드: {}개 성공, {}개 실패",
+        report.loaded.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// format 파일을 읽어 EVENT_PARSERS에 등록 (같은 이벤트 이름은 덮어씀)
+pub fn load_event_formats(path: &str) -> Result<String, String> {
+    let report = load_format_dir(path)?;
+    {
+        let mut parsers = EVENT_PARSERS.write().map_err(|e| e.to_string())?;
+        for parser in &report.loaded {
+            parsers.insert(parser.name.clone(), parser.clone());
+        }
+    }
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+/// 등록된 이벤트 파서 목록 (이름 순)
+pub fn get_event_parsers() -> Result<String, String> {
+    let parsers = EVENT_PARSERS.read().map_err(|e| e.to_string())?;
+    let mut list: Vec<&EventParser> = parsers.values().collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    serde_json::to_string(&list).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK_RQ_ISSUE_FORMAT: &str = r#"name: block_rq_issue
+ID: 1234
+format:
+	field:unsigned short common_type;	offset:0;	size:2;	signed:0;
+	field:int common_pid;	offset:4;	size:4;	signed:1;
+
+	field:dev_t dev;	offset:8;	size:4;	signed:0;
+	field:sector_t sector;	offset:16;	size:8;	signed:0;
+	field:unsigned int nr_sector;	offset:24;	size:4;	signed:0;
+	field:unsigned int bytes;	offset:28;	size:4;	signed:0;
+	field:char rwbs[8];	offset:32;	size:8;	signed:1;
+	field:char comm[16];	offset:40;	size:16;	signed:1;
+	field:__data_loc char[] cmd;	offset:56;	size:4;	signed:1;
+
+print fmt: "%d,%d %s %u (%s) %llu + %u [%s]", ((unsigned int) ((REC->dev) >> 20)), ((unsigned int) ((REC->dev) & ((1U << 20) - 1))), REC->rwbs, REC->bytes, __get_str(cmd), (unsigned long long)REC->sector, REC->nr_sector, REC->comm
+"#;
+
+    #[test]
+    fn test_parse_format_fields_and_args() {
+        let format = parse_format("block", BLOCK_RQ_ISSUE_FORMAT).unwrap();
+        assert_eq!(format.name, "block_rq_issue");
+        assert_eq!(format.id, Some(1234));
+        assert_eq!(format.args.len(), 8);
+
+        let rwbs = format.fields.iter().find(|f| f.name == "rwbs").unwrap();
+        assert_eq!(rwbs.c_type, "char[8]");
+        let cmd = format.fields.iter().find(|f| f.name == "cmd").unwrap();
+        assert_eq!(cmd.c_type, "__data_loc char[]");
+    }
+
+    #[test]
+    fn test_build_parser_maps_typed_columns() {
+        let format = parse_format("block", BLOCK_RQ_ISSUE_FORMAT).unwrap();
+        let parser = build_parser(&format).unwrap();
+
+        let names: Vec<&str> = parser.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["dev_major", "dev_minor", "rwbs", "bytes", "cmd", "sector", "nr_sector", "comm"]
+        );
+        assert_eq!(parser.columns[0].kind, ColumnKind::Int);
+        assert_eq!(parser.columns[5].kind, ColumnKind::UInt);
+        assert_eq!(parser.columns[0].field.as_deref(), Some("dev"));
+
+        let values = parser.parse_args("8,0 WS 4096 () 123456 + 8 [kworker/u16:1]").unwrap();
+        assert_eq!(values[0], EventValue::Int(8));
+        assert_eq!(values[2], EventValue::Text("WS".to_string()));
+        assert_eq!(values[4], EventValue::Text(String::new()));
+        assert_eq!(values[5], EventValue::UInt(123456));
+        assert_eq!(values[7], EventValue::Text("kworker/u16:1".to_string()));
+    }
+
+    #[test]
+    fn test_parse_header_and_hex_args() {
+        let line = "  kworker/u16:3-811   (  811) [007] d..1. 123.456789: ufshcd_clk: tag=0x1f flags=00ff";
+        let header = parse_header(line).unwrap();
+        assert_eq!(header.process, "kworker/u16:3");
+        assert_eq!(header.pid, 811);
+        assert_eq!(header.cpu, 7);
+        assert_eq!(header.flags, "d..1.");
+        assert_eq!(header.event, "ufshcd_clk");
+
+        let format = EventFormat {
+            system: "ufs".to_string(),
+            name: "ufshcd_clk".to_string(),
+            id: None,
+            fields: Vec::new(),
+            print_fmt: "tag=%#x flags=%04x".to_string(),
+            args: vec!["REC->tag".to_string(), "REC->flags".to_string()],
+        };
+        let parser = build_parser(&format).unwrap();
+        let values = parser.parse_args(header.args).unwrap();
+        assert_eq!(values, vec![EventValue::UInt(0x1f), EventValue::UInt(0xff)]);
+    }
+}
@@ -0,0 +1,317 @@
+// src-tauri/src/trace/heatmap.rs
+//
+// LBA hot / cold 히트맵 집계
+// 주소 공간(UFS / UFSCUSTOM lba, block sector)을 구간으로 나누고, 트레이스 전체 또는 시간 구간별로
+// 구간마다 read / write 횟수, 바이트, 평균 / 최대 레이턴시를 계산한다.
+// 프런트엔드가 모든 행을 읽지 않고 바로 그릴 수 있도록 [시간 구간][주소 구간] 2차원 행렬로 반환한다.
+// 요청은 시작 주소가 속한 구간, 완료 시각이 속한 시간 구간에 집계한다.
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::trace::block;
+use crate::trace::filter::{filter_block_data, filter_ufs_data, filter_ufscustom_data};
+use crate::trace::iostat::rw_kind;
+use crate::trace::timestamp;
+use crate::trace::Block;
+
+// 행렬 최대 셀 수 (시간 구간 x 주소 구간)
+const MAX_CELLS: usize = 1_000_000;
+
+// 완료된 요청 하나 (주소는 트레이스 단위)
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapSample {
+    pub time: i64,
+    pub addr: u64,
+    // None이면 read / write가 아닌 요청 (횟수 / 바이트에서 제외, 레이턴시는 포함)
+    pub is_read: Option<bool>,
+    pub bytes: u64,
+    pub latency_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct HeatmapMatrix {
+    pub trace_type: String,
+    // 시간 구간 경계 (초, 길이 = 행 수 + 1)
+    pub time_edges: Vec<f64>,
+    // 주소 구간 경계 (트레이스 단위, 길이 = 열 수 + 1)
+    pub addr_edges: Vec<u64>,
+    // [시간 구간][주소 구간]
+    pub read_count: Vec<Vec<u32>>,
+    pub write_count: Vec<Vec<u32>>,
+    pub read_bytes: Vec<Vec<u64>>,
+    pub write_bytes: Vec<Vec<u64>>,
+    pub mean_latency_ms: Vec<Vec<f64>>,
+    pub max_latency_ms: Vec<Vec<f64>>,
+    // 주소 / 시간 범위 밖이라 제외된 요청 수
+    pub skipped: usize,
+}
+
+fn matrix<T: Clone>(rows: usize, cols: usize, value: T) -> Vec<Vec<T>> {
+    vec![vec![value; cols]; rows]
+}
+
+/// 요청 목록을 히트맵 행렬로 집계
+/// addr_range: [from, to) 주소 범위, time_range: [from, to] (ns), interval: 시간 구간 길이 (None이면 전체 1개)
+pub fn build_heatmap(
+    trace_type: &str,
+    samples: &[HeatmapSample],
+    addr_range: (u64, u64),
+    addr_bins: usize,
+    time_range: (i64, i64),
+    interval: Option<i64>,
+) -> Result<HeatmapMatrix, String> {
+    let (addr_from, addr_to) = addr_range;
+    if addr_to <= addr_from || addr_bins == 0 {
+        return Err("주소 범위 또는 구간 수가 올바르지 않습니다".to_string());
+    }
+    // 구간 수가 주소 범위보다 많으면 구간 하나 = 주소 하나
+    let region = (addr_to - addr_from).div_ceil(addr_bins as u64);
+    let cols = (addr_to - addr_from).div_ceil(region) as usize;
+
+    let (time_from, time_to) = time_range;
+    let span = (time_to - time_from).max(0);
+    let (interval, rows) = match interval.filter(|i| *i > 0) {
+        Some(interval) => (interval, (span / interval + 1) as usize),
+        None => (span.max(1), 1),
+    };
+    if rows.saturating_mul(cols) > MAX_CELLS {
+        return Err(format!(
+            "히트맵 셀이 너무 많습니다 ({} x {}, 최대 {}개) - 구간 수를 줄여 주세요",
+            rows, cols, MAX_CELLS
+        ));
+    }
+
+    let mut heatmap = HeatmapMatrix {
+        trace_type: trace_type.to_string(),
+        time_edges: (0..=rows as i64)
+            .map(|i| timestamp::ns_to_secs(time_from + i * interval))
+            .collect(),
+        addr_edges: (0..=cols as u64).map(|i| (addr_from + i * region).min(addr_to)).collect(),
+        read_count: matrix(rows, cols, 0),
+        write_count: matrix(rows, cols, 0),
+        read_bytes: matrix(rows, cols, 0),
+        write_bytes: matrix(rows, cols, 0),
+        mean_latency_ms: matrix(rows, cols, 0.0),
+        max_latency_ms: matrix(rows, cols, 0.0),
+        skipped: 0,
+    };
+    let mut latency_counts: Vec<Vec<u32>> = matrix(rows, cols, 0);
+
+    for s in samples {
+        if s.addr < addr_from || s.addr >= addr_to || s.time < time_from || s.time > time_to {
+            heatmap.skipped += 1;
+            continue;
+        }
+        let row = (((s.time - time_from) / interval) as usize).min(rows - 1);
+        let col = ((s.addr - addr_from) / region) as usize;
+        match s.is_read {
+            Some(true) => {
+                heatmap.read_count[row][col] += 1;
+                heatmap.read_bytes[row][col] += s.bytes;
+            }
+            Some(false) => {
+                heatmap.write_count[row][col] += 1;
+                heatmap.write_bytes[row][col] += s.bytes;
+            }
+            None => {}
+        }
+        // 평균은 합계를 모은 뒤 마지막에 나눔
+        heatmap.mean_latency_ms[row][col] += s.latency_ms;
+        latency_counts[row][col] += 1;
+        let max = &mut heatmap.max_latency_ms[row][col];
+        *max = max.max(s.latency_ms);
+    }
+    for (sums, counts) in heatmap.mean_latency_ms.iter_mut().zip(&latency_counts) {
+        for (sum, &count) in sums.iter_mut().zip(counts) {
+            if count > 0 {
+                *sum /= count as f64;
+            }
+        }
+    }
+    Ok(heatmap)
+}
+
+// Block 완료 요청 - sector는 장치마다 다른 주소 공간이므로 장치 하나만 집계
+// device가 없으면 트레이스에 장치가 하나일 때만 허용
+fn block_samples(rows: Vec<Block>, device: Option<&str>) -> Result<Vec<HeatmapSample>, String> {
+    let rows = block::filter_device(rows, device)?;
+    let devices: BTreeSet<String> = rows.iter().map(block::device_key).collect();
+    if devices.len() > 1 {
+        return Err(format!(
+            "여러 Block 장치가 섞여 있습니다 ({}) - 히트맵을 그릴 장치를 선택해 주세요",
+            devices.into_iter().collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter(|b| b.action == "block_rq_complete")
+        .map(|b| HeatmapSample {
+            time: b.time,
+            addr: b.sector,
+            is_read: match block::parse_rwbs(&b.io_type).op {
+                "read" => Some(true),
+                "write" => Some(false),
+                _ => None,
+            },
+            bytes: b.size as u64 * 512,
+            latency_ms: b.dtoc,
+        })
+        .collect())
+}
+
+fn load_samples(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    device: Option<&str>,
+) -> Result<Vec<HeatmapSample>, String> {
+    Ok(match trace_type {
+        "ufs" => filter_ufs_data(logname, time_from, time_to, "lba", None, None, None)?
+            .into_iter()
+            .filter(|u| u.action == "complete_rsp" && !u.orphan)
+            .map(|u| HeatmapSample {
+                time: u.time,
+                addr: u.lba,
+                is_read: rw_kind("ufs", &u.opcode),
+                bytes: u.size as u64 * 4096,
+                latency_ms: u.dtoc,
+            })
+            .collect(),
+        "block" => block_samples(filter_block_data(logname, time_from, time_to, "sector", None, None, None)?, device)?,
+        "ufscustom" => filter_ufscustom_data(logname, time_from, time_to, "lba", None, None, None)?
+            .into_iter()
+            .map(|u| HeatmapSample {
+                time: u.end_time,
+                addr: u.lba,
+                is_read: rw_kind("ufs", &u.opcode),
+                bytes: u.size as u64 * 4096,
+                latency_ms: u.dtoc,
+            })
+            .collect(),
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    })
+}
+
+/// lba_heatmap 명령
+/// col_from / col_to가 없으면 트레이스의 최소 ~ 최대 주소, interval_ms가 없거나 0이면 트레이스 전체를 한 구간으로 집계
+/// device는 block 장치 필터 ("major:minor")
+#[allow(clippy::too_many_arguments)]
+pub fn lba_heatmap(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    col_from: Option<f64>,
+    col_to: Option<f64>,
+    addr_bins: usize,
+    interval_ms: Option<f64>,
+    device: Option<&str>,
+) -> Result<String, String> {
+    let samples = load_samples(logname, trace_type, time_from, time_to, device)?;
+    if samples.is_empty() {
+        return Err(format!("히트맵을 만들 {} 완료 요청이 없습니다", trace_type));
+    }
+
+    let addr_from = col_from
+        .map(|v| v.max(0.0) as u64)
+        .unwrap_or_else(|| samples.iter().map(|s| s.addr).min().unwrap_or(0));
+    let addr_to = col_to
+        .map(|v| v as u64 + 1)
+        .unwrap_or_else(|| samples.iter().map(|s| s.addr).max().unwrap_or(0) + 1);
+    let time_range = (
+        time_from
+            .map(timestamp::secs_to_ns)
+            .unwrap_or_else(|| samples.iter().map(|s| s.time).min().unwrap_or(0)),
+        time_to
+            .map(timestamp::secs_to_ns)
+            .unwrap_or_else(|| samples.iter().map(|s| s.time).max().unwrap_or(0)),
+    );
+    let interval = interval_ms.map(|ms| (ms * timestamp::NS_PER_MS as f64).round() as i64);
+
+    let heatmap = build_heatmap(trace_type, &samples, (addr_from, addr_to), addr_bins, time_range, interval)?;
+    println!(
+        "🔥 {} LBA 히트맵: {} x {} (요청 {}개, 범위 밖 {}개)",
+        trace_type,
+        heatmap.time_edges.len() - 1,
+        heatmap.addr_edges.len() - 1,
+        samples.len(),
+        heatmap.skipped
+    );
+    serde_json::to_string(&heatmap).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support;
+
+    fn sample(time_ms: i64, addr: u64, is_read: Option<bool>, latency_ms: f64) -> HeatmapSample {
+        HeatmapSample {
+            time: time_ms * timestamp::NS_PER_MS,
+            addr,
+            is_read,
+            bytes: 4096,
+            latency_ms,
+        }
+    }
+
+    #[test]
+    fn test_heatmap_whole_trace() {
+        let samples = vec![
+            sample(0, 0, Some(true), 1.0),
+            sample(5, 10, Some(true), 3.0),
+            sample(9, 99, Some(false), 2.0),
+            sample(9, 150, Some(false), 2.0), // 범위 밖
+            sample(9, 50, None, 8.0),         // flush 등 (횟수 제외, 레이턴시 포함)
+        ];
+        let ms = timestamp::NS_PER_MS;
+        let heatmap = build_heatmap("ufs", &samples, (0, 100), 4, (0, 10 * ms), None).unwrap();
+        assert_eq!(heatmap.time_edges.len(), 2);
+        assert_eq!(heatmap.addr_edges, vec![0, 25, 50, 75, 100]);
+        assert_eq!(heatmap.read_count, vec![vec![2, 0, 0, 0]]);
+        assert_eq!(heatmap.write_count, vec![vec![0, 0, 0, 1]]);
+        assert_eq!(heatmap.read_bytes[0][0], 8192);
+        assert_eq!(heatmap.mean_latency_ms[0][0], 2.0);
+        assert_eq!(heatmap.max_latency_ms[0][0], 3.0);
+        assert_eq!(heatmap.mean_latency_ms[0][2], 8.0);
+        assert_eq!(heatmap.skipped, 1);
+    }
+
+    #[test]
+    fn test_heatmap_time_windows() {
+        let ms = timestamp::NS_PER_MS;
+        let samples = vec![
+            sample(1, 3, Some(false), 1.0),
+            sample(12, 3, Some(false), 1.0),
+            sample(25, 7, Some(true), 1.0),
+        ];
+        let heatmap = build_heatmap("block", &samples, (0, 8), 2, (0, 25 * ms), Some(10 * ms)).unwrap();
+        assert_eq!(heatmap.time_edges, vec![0.0, 0.01, 0.02, 0.03]);
+        assert_eq!(heatmap.write_count, vec![vec![1, 0], vec![1, 0], vec![0, 0]]);
+        assert_eq!(heatmap.read_count[2], vec![0, 1]);
+
+        // 셀 수 제한
+        assert!(build_heatmap("block", &samples, (0, 8), 8, (0, 1_000_000 * ms), Some(ms)).is_err());
+    }
+
+    #[test]
+    fn test_block_samples_per_device() {
+        let rows = vec![
+            test_support::block(100, "block_rq_complete", 64),
+            Block { devmajor: 253, devminor: 1, ..test_support::block(200, "block_rq_complete", 64) },
+            test_support::block(300, "block_rq_issue", 72),
+        ];
+
+        // 장치를 고르지 않으면 주소 공간이 섞이므로 거부
+        let err = block_samples(rows.clone(), None).unwrap_err();
+        assert!(err.contains("253:1, 8:0"));
+
+        let samples = block_samples(rows, Some("253:1")).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!((samples[0].time, samples[0].addr, samples[0].is_read), (200_000, 64, Some(true)));
+    }
+}
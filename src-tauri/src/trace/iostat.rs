@@ -0,0 +1,381 @@
+// src-tauri/src/trace/iostat.rs
+//
+// iostat 형식의 고정 구간 리포트
+// 트레이스를 interval_ms 단위 구간으로 나누고 구간 / op별로
+// IOPS, MB/s, 평균 / p99 레이턴시, 시간 가중 평균 QD, 사용률, R/W 비율을 계산한다.
+// - IOPS / MB/s / 레이턴시: 완료 시각이 구간에 속한 요청 기준
+// - QD / 사용률: 요청의 [issue, complete] 구간이 window와 겹치는 시간 기준 (완료된 요청만)
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::trace::block::{self, parse_rwbs};
+use crate::trace::busy::{merge_intervals, Interval};
+use crate::trace::clock_sync::{self, WallClockRange};
+use crate::trace::filter::{filter_block_data, filter_ufs_data};
+use crate::trace::timestamp;
+use crate::trace::utils::calculate_statistics;
+
+// 한 번에 만들 수 있는 최대 구간 수
+const MAX_WINDOWS: usize = 100_000;
+// 구간 전체 요약 키
+const ALL_OPS: &str = "all";
+
+// 완료된 요청 하나 (issue ~ complete, 나노초)
+#[derive(Debug, Clone, PartialEq)]
+pub struct IoRequest {
+    pub op: String,
+    pub start: i64,
+    pub end: i64,
+    pub bytes: u64,
+    pub latency_ms: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct IostatOpStats {
+    pub requests: usize,
+    pub bytes: u64,
+    pub iops: f64,
+    pub mbps: f64,
+    pub avg_latency_ms: f64,
+    pub p99_latency_ms: f64,
+    pub avg_qd: f64,
+    pub utilization: f64, // %
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IostatWindow {
+    pub start_time: f64,
+    pub end_time: f64,
+    // 완료 요청 수 기준 read / write 비율 (%)
+    pub read_pct: f64,
+    pub write_pct: f64,
+    // op별 통계 + "all"
+    pub ops: BTreeMap<String, IostatOpStats>,
+    pub wall_clock: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IostatReport {
+    pub trace_type: String,
+    pub interval_ms: f64,
+    pub windows: Vec<IostatWindow>,
+    pub wall_clock: Option<WallClockRange>,
+}
+
+// 구간 / op별 누적값
+#[derive(Default)]
+struct Accumulator {
+    requests: usize,
+    bytes: u64,
+    latencies: Vec<f64>,
+    in_flight_ns: i64,
+    busy_ns: i64,
+}
+
+/// read / write 구분 - UFS는 SCSI opcode(READ/WRITE 6/10/16)
+/// Block은 rwbs(WS, RA, FWS ...)의 연산 또는 group으로 묶인 연산 이름(read / write)
+pub fn rw_kind(trace_type: &str, op: &str) -> Option<bool> {
+    match trace_type {
+        "ufs" => match op {
+            "0x08" | "0x28" | "0x88" => Some(true),
+            "0x0a" | "0x2a" | "0x8a" => Some(false),
+            _ => None,
+        },
+        _ => {
+            let op = match op {
+                "read" | "write" => op,
+                _ => parse_rwbs(op).op,
+            };
+            match op {
+                "read" => Some(true),
+                "write" => Some(false),
+                _ => None,
+            }
+        }
+    }
+}
+
+// [start, end)를 window별로 나눠 겹친 시간(ns)을 더함
+fn spread_overlap(
+    start: i64,
+    end: i64,
+    origin: i64,
+    interval_ns: i64,
+    window_count: usize,
+    mut add: impl FnMut(usize, i64),
+) {
+    if end <= start || window_count == 0 {
+        return;
+    }
+    let first = ((start - origin).max(0) / interval_ns) as usize;
+    let last = (((end - origin - 1).max(0) / interval_ns) as usize).min(window_count - 1);
+    for w in first..=last {
+        let w_start = origin + w as i64 * interval_ns;
+        let overlap = end.min(w_start + interval_ns) - start.max(w_start);
+        if overlap > 0 {
+            add(w, overlap);
+        }
+    }
+}
+
+/// 요청 목록을 [window.0, window.1) 구간에서 interval_ns 단위로 집계
+pub fn build_report(
+    trace_type: &str,
+    requests: &[IoRequest],
+    window: (i64, i64),
+    interval_ns: i64,
+) -> Result<Vec<IostatWindow>, String> {
+    if interval_ns <= 0 {
+        return Err("구간 길이(interval)는 0보다 커야 합니다".to_string());
+    }
+    let span = (window.1 - window.0).max(0);
+    let window_count = ((span + interval_ns - 1) / interval_ns).max(1) as usize;
+    if window_count > MAX_WINDOWS {
+        return Err(format!(
+            "구간 수가 너무 많습니다 ({}개, 최대 {}개). interval을 늘려주세요",
+            window_count, MAX_WINDOWS
+        ));
+    }
+
+    let mut acc: Vec<HashMap<String, Accumulator>> = (0..window_count).map(|_| HashMap::new()).collect();
+    let mut op_intervals: HashMap<&str, Vec<Interval>> = HashMap::new();
+    let mut all_intervals = Vec::with_capacity(requests.len());
+
+    for req in requests {
+        if req.end >= window.0 && req.end <= window.1 {
+            let w = (((req.end - window.0) / interval_ns) as usize).min(window_count - 1);
+            for key in [req.op.as_str(), ALL_OPS] {
+                let a = acc[w].entry(key.to_string()).or_default();
+                a.requests += 1;
+                a.bytes += req.bytes;
+                a.latencies.push(req.latency_ms);
+            }
+        }
+        spread_overlap(req.start, req.end, window.0, interval_ns, window_count, |w, ns| {
+            for key in [req.op.as_str(), ALL_OPS] {
+                acc[w].entry(key.to_string()).or_default().in_flight_ns += ns;
+            }
+        });
+        let interval = Interval { start: req.start, end: req.end, requests: 1 };
+        op_intervals.entry(req.op.as_str()).or_default().push(interval);
+        all_intervals.push(interval);
+    }
+
+    // 사용률: op별 / 전체 요청 구간의 합집합이 window와 겹치는 시간
+    let busy_sets = op_intervals
+        .into_iter()
+        .map(|(op, intervals)| (op, merge_intervals(intervals)))
+        .chain(std::iter::once((ALL_OPS, merge_intervals(all_intervals))));
+    for (op, busy) in busy_sets {
+        for interval in busy {
+            spread_overlap(interval.start, interval.end, window.0, interval_ns, window_count, |w, ns| {
+                acc[w].entry(op.to_string()).or_default().busy_ns += ns;
+            });
+        }
+    }
+
+    let interval_secs = timestamp::ns_to_secs(interval_ns);
+    let windows = acc
+        .into_iter()
+        .enumerate()
+        .map(|(w, ops)| {
+            let start = window.0 + w as i64 * interval_ns;
+            let (mut reads, mut writes) = (0usize, 0usize);
+            let ops: BTreeMap<String, IostatOpStats> = ops
+                .into_iter()
+                .map(|(op, mut a)| {
+                    match rw_kind(trace_type, &op) {
+                        Some(true) => reads += a.requests,
+                        Some(false) => writes += a.requests,
+                        None => {}
+                    }
+                    let summary = calculate_statistics(&mut a.latencies);
+                    let stats = IostatOpStats {
+                        requests: a.requests,
+                        bytes: a.bytes,
+                        iops: a.requests as f64 / interval_secs,
+                        mbps: a.bytes as f64 / (1024.0 * 1024.0) / interval_secs,
+                        avg_latency_ms: summary.avg,
+                        p99_latency_ms: summary.percentiles.get("99th").copied().unwrap_or(0.0),
+                        avg_qd: a.in_flight_ns as f64 / interval_ns as f64,
+                        utilization: a.busy_ns as f64 / interval_ns as f64 * 100.0,
+                    };
+                    (op, stats)
+                })
+                .collect();
+            let rw_total = (reads + writes) as f64;
+            IostatWindow {
+                start_time: timestamp::ns_to_secs(start),
+                end_time: timestamp::ns_to_secs(start + interval_ns),
+                read_pct: if rw_total > 0.0 { reads as f64 / rw_total * 100.0 } else { 0.0 },
+                write_pct: if rw_total > 0.0 { writes as f64 / rw_total * 100.0 } else { 0.0 },
+                ops,
+                wall_clock: None,
+            }
+        })
+        .collect();
+    Ok(windows)
+}
+
+// 분석 구간 - 시간 필터가 있으면 그 범위, 없으면 요청 범위
+fn report_window(time_from: Option<f64>, time_to: Option<f64>, requests: &[IoRequest]) -> Option<(i64, i64)> {
+    match (time_from, time_to) {
+        (Some(from), Some(to)) if from != 0.0 || to != 0.0 => {
+            Some((timestamp::secs_to_ns(from), timestamp::secs_to_ns(to)))
+        }
+        _ => {
+            let start = requests.iter().map(|r| r.start).min()?;
+            let end = requests.iter().map(|r| r.end).max()?;
+            Some((start, end))
+        }
+    }
+}
+
+/// iostat_report 명령 - trace_type: "ufs" | "block"
+/// group이면 block op를 rwbs 연산(read / write / discard / flush / other)으로 묶음 (UFS는 opcode 그대로)
+/// device는 block 장치 필터 ("major:minor")
+pub fn iostat_report(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    interval_ms: f64,
+    group: bool,
+    device: Option<&str>,
+) -> Result<String, String> {
+    let interval_ns = (interval_ms * timestamp::NS_PER_MS as f64).round() as i64;
+    // 레이턴시(dtoc, ms)로 issue 시각 복원
+    let issue_time = |end: i64, dtoc: f64| end - (dtoc * timestamp::NS_PER_MS as f64).round() as i64;
+
+    let requests: Vec<IoRequest> = match trace_type {
+        "ufs" => filter_ufs_data(logname, time_from, time_to, "lba", None, None, None)?
+            .into_iter()
+            .filter(|u| u.action == "complete_rsp" && !u.orphan)
+            .map(|u| IoRequest {
+                start: issue_time(u.time, u.dtoc),
+                end: u.time,
+                bytes: u.size as u64 * 4096,
+                latency_ms: u.dtoc,
+                op: u.opcode,
+            })
+            .collect(),
+        "block" => {
+            let rows = filter_block_data(logname, time_from, time_to, "sector", None, None, None)?;
+            block::filter_device(rows, device)?
+                .into_iter()
+                .filter(|b| b.action == "block_rq_complete" && b.dtoc > 0.0)
+                .map(|b| IoRequest {
+                    start: issue_time(b.time, b.dtoc),
+                    end: b.time,
+                    bytes: b.size as u64 * 512,
+                    latency_ms: b.dtoc,
+                    op: if group { parse_rwbs(&b.io_type).op.to_string() } else { b.io_type },
+                })
+                .collect()
+        }
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    };
+
+    let window = report_window(time_from, time_to, &requests)
+        .ok_or_else(|| format!("분석할 {} 완료 요청이 없습니다", trace_type))?;
+    let mut windows = build_report(trace_type, &requests, window, interval_ns)?;
+
+    let sync = clock_sync::clock_sync_for_logname(logname);
+    if let Some(sync) = &sync {
+        for w in &mut windows {
+            w.wall_clock = Some(sync.format(w.start_time));
+        }
+    }
+    let report = IostatReport {
+        trace_type: trace_type.to_string(),
+        interval_ms,
+        wall_clock: sync.map(|s| s.range(timestamp::ns_to_secs(window.0), timestamp::ns_to_secs(window.1))),
+        windows,
+    };
+
+    println!(
+        "📈 {} iostat 리포트: {}ms 구간 {}개, 완료 요청 {}개",
+        trace_type,
+        interval_ms,
+        report.windows.len(),
+        requests.len()
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn req(op: &str, start_ms: i64, end_ms: i64, bytes: u64) -> IoRequest {
+        IoRequest {
+            op: op.to_string(),
+            start: start_ms * timestamp::NS_PER_MS,
+            end: end_ms * timestamp::NS_PER_MS,
+            bytes,
+            latency_ms: (end_ms - start_ms) as f64,
+        }
+    }
+
+    #[test]
+    fn test_iostat_windows() {
+        let ms = timestamp::NS_PER_MS;
+        let requests = vec![
+            req("0x28", 0, 4, 1024 * 1024),
+            req("0x28", 2, 6, 1024 * 1024),
+            req("0x2a", 12, 18, 4096),
+        ];
+        let windows = build_report("ufs", &requests, (0, 20 * ms), 10 * ms).unwrap();
+        assert_eq!(windows.len(), 2);
+
+        let read = &windows[0].ops["0x28"];
+        assert_eq!((read.requests, read.iops, read.mbps), (2, 200.0, 200.0));
+        assert_eq!(read.avg_latency_ms, 4.0);
+        // 0~10ms 중 요청 구간 합 8ms -> 평균 QD 0.8, 합집합 0~6ms -> 사용률 60%
+        assert!((read.avg_qd - 0.8).abs() < 1e-9);
+        assert!((read.utilization - 60.0).abs() < 1e-9);
+        assert_eq!((windows[0].read_pct, windows[0].write_pct), (100.0, 0.0));
+
+        let write = &windows[1].ops["0x2a"];
+        assert_eq!(write.requests, 1);
+        assert!((windows[1].ops[ALL_OPS].utilization - 60.0).abs() < 1e-9);
+        assert_eq!(windows[1].write_pct, 100.0);
+    }
+
+    #[test]
+    fn test_request_spanning_windows_and_limits() {
+        let ms = timestamp::NS_PER_MS;
+        let requests = vec![req("R", 5, 25, 512)];
+        let windows = build_report("block", &requests, (0, 30 * ms), 10 * ms).unwrap();
+        let qd: Vec<f64> = windows.iter().map(|w| w.ops.get("R").map_or(0.0, |s| s.avg_qd)).collect();
+        assert_eq!(qd, vec![0.5, 1.0, 0.5]);
+        // 완료는 마지막 구간에만 집계
+        assert_eq!(windows[2].ops["R"].requests, 1);
+        assert!(!windows[0].ops.contains_key("W"));
+
+        assert!(build_report("block", &requests, (0, 30 * ms), 0).is_err());
+        assert!(build_report("block", &requests, (0, i64::MAX / 2), 1).is_err());
+    }
+
+    #[test]
+    fn test_block_rwbs_read_write_ratio() {
+        assert_eq!(rw_kind("block", "RA"), Some(true));
+        assert_eq!(rw_kind("block", "WS"), Some(false));
+        assert_eq!(rw_kind("block", "FWS"), Some(false));
+        assert_eq!(rw_kind("block", "write"), Some(false));
+        assert_eq!(rw_kind("block", "FF"), None);
+
+        let ms = timestamp::NS_PER_MS;
+        let requests = vec![
+            req("RA", 0, 2, 4096),
+            req("WS", 1, 3, 4096),
+            req("FWS", 2, 4, 4096),
+            req("FF", 3, 5, 0),
+        ];
+        let windows = build_report("block", &requests, (0, 10 * ms), 10 * ms).unwrap();
+        assert!((windows[0].read_pct - 100.0 / 3.0).abs() < 1e-9);
+        assert!((windows[0].write_pct - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(windows[0].ops["FWS"].requests, 1);
+    }
+}
@@ -0,0 +1,294 @@
+// src-tauri/src/trace/lifecycle.rs
+//
+// UFS 요청 생명주기 추적 (send_req -> complete_rsp)
+// (hwqid, tag)로 요청을 짝짓고 다음 이상 상황을 검출한다.
+// tag는 opcode와 관계없이 완료 전까지 하나의 요청만 사용하며, MCQ에서는 hwqid별로 구분된다.
+// - orphan_send: complete_rsp 없이 트레이스가 끝난 요청
+// - orphan_complete: 대응하는 send_req가 없는 완료 (트레이스 시작 직후, 유실된 send 등)
+// - tag_reuse: 완료되지 않은 tag로 다시 send_req가 들어온 경우 (이전 요청의 완료가 유실됨)
+// - opcode_mismatch: complete_rsp의 opcode가 같은 tag의 send_req와 다른 경우
+// 이상 행은 UFS.orphan으로 표시되고 레이턴시 통계에서 제외된다.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::trace::clock_sync;
+use crate::trace::filter::filter_ufs_data;
+use crate::trace::timestamp;
+use crate::trace::UFS;
+
+// 리포트에 포함할 최대 이상 항목 수 (개수 집계는 전체 기준)
+const MAX_REPORTED_ANOMALIES: usize = 10_000;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    OrphanSend,
+    OrphanComplete,
+    TagReuse,
+    OpcodeMismatch,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TagAnomaly {
+    pub kind: AnomalyKind,
+    // 이상이 표시된 행 (tag_reuse는 완료가 유실된 이전 send_req 행)
+    pub index: usize,
+    pub time: f64,
+    pub wall_clock: Option<String>,
+    pub tag: u32,
+    pub opcode: String,
+    pub lba: u64,
+    // tag_reuse: 같은 tag를 다시 사용한 send_req 시각
+    pub reused_at: Option<f64>,
+    // opcode_mismatch: 같은 tag로 보낸 send_req의 opcode
+    pub send_opcode: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct TagAnomalyReport {
+    pub total_sends: usize,
+    pub total_completes: usize,
+    pub matched: usize,
+    pub orphan_sends: usize,
+    pub orphan_completes: usize,
+    pub tag_reuses: usize,
+    pub opcode_mismatches: usize,
+    // 리포트 항목이 MAX_REPORTED_ANOMALIES에서 잘렸는지 여부
+    pub truncated: bool,
+    pub anomalies: Vec<TagAnomaly>,
+}
+
+impl TagAnomalyReport {
+    pub fn anomaly_count(&self) -> usize {
+        self.orphan_sends + self.orphan_completes + self.tag_reuses + self.opcode_mismatches
+    }
+}
+
+// 완료 대기 중인 send_req
+#[derive(Debug, Clone)]
+pub struct InFlight {
+    pub index: usize,
+    pub time: i64,
+    pub hwqid: u32,
+    pub opcode: String,
+}
+
+// 요청 식별 키 (hwqid, tag) - SDB 모드 트레이스는 hwqid가 모두 같으므로 tag만으로 구분
+type RequestKey = (u32, u32);
+
+fn request_key(ufs: &UFS) -> RequestKey {
+    (ufs.hwqid, ufs.tag)
+}
+
+// (종류, 이상 행 번호, 재사용 시각, 짝이 된 send_req 행 번호)
+type PendingAnomaly = (AnomalyKind, usize, Option<i64>, Option<usize>);
+
+/// send_req / complete_rsp 짝짓기 상태
+#[derive(Debug, Default)]
+pub struct RequestTracker {
+    in_flight: HashMap<RequestKey, InFlight>,
+    anomalies: Vec<PendingAnomaly>,
+    total_sends: usize,
+    total_completes: usize,
+    matched: usize,
+}
+
+impl RequestTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 현재 완료 대기 중인 요청 수 (QD)
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// send_req 기록 - 같은 tag가 아직 완료되지 않았다면 이전 요청을 tag_reuse로 처리하고 반환
+    pub fn send(&mut self, index: usize, ufs: &UFS) -> Option<InFlight> {
+        self.total_sends += 1;
+        let previous = self.in_flight.insert(
+            request_key(ufs),
+            InFlight {
+                index,
+                time: ufs.time,
+                hwqid: ufs.hwqid,
+                opcode: ufs.opcode.clone(),
+            },
+        );
+        if let Some(previous) = &previous {
+            self.anomalies
+                .push((AnomalyKind::TagReuse, previous.index, Some(ufs.time), None));
+        }
+        previous
+    }
+
+    /// complete_rsp 기록 - 같은 tag의 send_req가 있으면 반환 (tag는 해제됨)
+    /// opcode가 다르면 짝짓기를 신뢰할 수 없으므로 완료 행을 opcode_mismatch로 처리
+    pub fn complete(&mut self, index: usize, ufs: &UFS) -> Option<InFlight> {
+        self.total_completes += 1;
+        match self.in_flight.remove(&request_key(ufs)) {
+            Some(send) => {
+                if send.opcode == ufs.opcode {
+                    self.matched += 1;
+                } else {
+                    self.anomalies
+                        .push((AnomalyKind::OpcodeMismatch, index, None, Some(send.index)));
+                }
+                Some(send)
+            }
+            None => {
+                self.anomalies.push((AnomalyKind::OrphanComplete, index, None, None));
+                None
+            }
+        }
+    }
+
+    /// 추적 종료 - 남은 요청은 orphan_send
+    /// (리포트, 이상 행 번호 전체) 반환 - rows는 추적에 사용한 행과 같은 순서여야 함
+    pub fn finish(mut self, rows: &[UFS]) -> (TagAnomalyReport, Vec<usize>) {
+        self.anomalies.extend(
+            self.in_flight
+                .values()
+                .map(|send| (AnomalyKind::OrphanSend, send.index, None, None)),
+        );
+        self.anomalies.sort_by_key(|(_, index, _, _)| *index);
+        let orphan_rows: Vec<usize> = self.anomalies.iter().map(|(_, index, _, _)| *index).collect();
+
+        let mut report = TagAnomalyReport {
+            total_sends: self.total_sends,
+            total_completes: self.total_completes,
+            matched: self.matched,
+            ..Default::default()
+        };
+        for (kind, index, reused_at, send_index) in self.anomalies {
+            match kind {
+                AnomalyKind::OrphanSend => report.orphan_sends += 1,
+                AnomalyKind::OrphanComplete => report.orphan_completes += 1,
+                AnomalyKind::TagReuse => report.tag_reuses += 1,
+                AnomalyKind::OpcodeMismatch => report.opcode_mismatches += 1,
+            }
+            if report.anomalies.len() >= MAX_REPORTED_ANOMALIES {
+                report.truncated = true;
+                continue;
+            }
+            let row = &rows[index];
+            report.anomalies.push(TagAnomaly {
+                kind,
+                index,
+                time: timestamp::ns_to_secs(row.time),
+                wall_clock: None,
+                tag: row.tag,
+                opcode: row.opcode.clone(),
+                lba: row.lba,
+                reused_at: reused_at.map(timestamp::ns_to_secs),
+                send_opcode: send_index.map(|send| rows[send].opcode.clone()),
+            });
+        }
+        (report, orphan_rows)
+    }
+}
+
+/// 시간순 UFS 행 전체의 요청 생명주기 추적
+pub fn track_requests(rows: &[UFS]) -> (TagAnomalyReport, Vec<usize>) {
+    let mut tracker = RequestTracker::new();
+    for (index, ufs) in rows.iter().enumerate() {
+        match ufs.action.as_str() {
+            "send_req" => {
+                tracker.send(index, ufs);
+            }
+            "complete_rsp" => {
+                tracker.complete(index, ufs);
+            }
+            _ => {}
+        }
+    }
+    tracker.finish(rows)
+}
+
+/// ufs_tag_anomalies 명령 - 트레이스 전체의 tag 이상 리포트
+pub fn tag_anomalies(logname: String) -> Result<String, String> {
+    let rows = filter_ufs_data(&logname, None, None, "lba", None, None, None)?;
+    let (mut report, _) = track_requests(&rows);
+
+    if let Some(sync) = clock_sync::clock_sync_for_logname(&logname) {
+        for anomaly in &mut report.anomalies {
+            anomaly.wall_clock = Some(sync.format(anomaly.time));
+        }
+    }
+
+    println!(
+        "🏷️  UFS tag 이상: orphan send {}개, orphan complete {}개, tag 재사용 {}개, opcode 불일치 {}개 (send {}개 / complete {}개)",
+        report.orphan_sends,
+        report.orphan_completes,
+        report.tag_reuses,
+        report.opcode_mismatches,
+        report.total_sends,
+        report.total_completes
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support::ufs;
+
+    #[test]
+    fn test_track_requests_detects_anomalies() {
+        let rows = vec![
+            ufs(100, "complete_rsp", 7), // 트레이스 시작 전에 보낸 요청의 완료
+            ufs(200, "send_req", 1),
+            ufs(300, "send_req", 2),
+            ufs(400, "complete_rsp", 1),
+            ufs(500, "send_req", 2), // 2번 tag 완료 유실
+            ufs(600, "complete_rsp", 2),
+            ufs(700, "send_req", 3), // 완료 없이 트레이스 종료
+        ];
+
+        let (report, orphan_rows) = track_requests(&rows);
+        assert_eq!((report.total_sends, report.total_completes, report.matched), (4, 3, 2));
+        assert_eq!(
+            (report.orphan_sends, report.orphan_completes, report.tag_reuses),
+            (1, 1, 1)
+        );
+        assert_eq!(orphan_rows, vec![0, 2, 6]);
+
+        let kinds: Vec<AnomalyKind> = report.anomalies.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![AnomalyKind::OrphanComplete, AnomalyKind::TagReuse, AnomalyKind::OrphanSend]
+        );
+        assert_eq!(report.anomalies[1].time, 0.0003);
+        assert_eq!(report.anomalies[1].reused_at, Some(0.0005));
+    }
+
+    #[test]
+    fn test_track_requests_pairs_by_tag_across_opcodes() {
+        let write = |time_us, action, tag| UFS { opcode: "0x2a".to_string(), ..ufs(time_us, action, tag) };
+        let on_hwq = |time_us, action, tag, hwqid| UFS { hwqid, ..ufs(time_us, action, tag) };
+        let rows = vec![
+            ufs(100, "send_req", 1),
+            write(200, "send_req", 1), // 1번 tag read 완료 유실 후 write로 재사용
+            write(300, "complete_rsp", 1),
+            ufs(400, "send_req", 2),
+            write(500, "complete_rsp", 2), // 같은 tag지만 opcode가 다른 완료
+            on_hwq(600, "send_req", 3, 0),
+            on_hwq(700, "send_req", 3, 1), // 다른 hwq의 같은 tag는 별개 요청
+            on_hwq(800, "complete_rsp", 3, 1),
+            on_hwq(900, "complete_rsp", 3, 0),
+        ];
+
+        let (report, orphan_rows) = track_requests(&rows);
+        assert_eq!((report.total_sends, report.total_completes, report.matched), (5, 4, 3));
+        assert_eq!(
+            (report.orphan_sends, report.orphan_completes, report.tag_reuses, report.opcode_mismatches),
+            (0, 0, 1, 1)
+        );
+        assert_eq!(orphan_rows, vec![0, 4]);
+        assert_eq!(report.anomalies[0].kind, AnomalyKind::TagReuse);
+        assert_eq!(report.anomalies[1].kind, AnomalyKind::OpcodeMismatch);
+        assert_eq!(report.anomalies[1].opcode, "0x2a");
+        assert_eq!(report.anomalies[1].send_opcode.as_deref(), Some("0x28"));
+    }
+}
@@ -0,0 +1,335 @@
+// src-tauri/src/trace/markers.rs
+//
+// tracing_mark_write(atrace) 마커를 구간으로 변환
+// B|pid|name / E|pid 는 스레드별로 중첩된 구간, S|pid|name|cookie / F|pid|name|cookie 는 비동기 구간,
+// C|pid|name|value 는 카운터 샘플로 만든다.
+// 구간 시간 범위로 filter_ufs_data / filter_block_data를 다시 적용하여 구간별 통계를 계산한다.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::event_table::with_events;
+use super::{block, ufs, FtraceEvent, TraceStats};
+
+const MARKER_EVENT: &str = "tracing_mark_write";
+// 최신 커널은 trace_marker 쓰기를 print 이벤트로 기록하고 args 앞에 "tracing_mark_write: "를 붙임
+const PRINT_EVENT: &str = "print";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Marker<'a> {
+    Begin { pid: u32, name: &'a str },
+    End,
+    AsyncBegin { pid: u32, name: &'a str, cookie: i64 },
+    AsyncEnd { pid: u32, name: &'a str, cookie: i64 },
+    Counter { pid: u32, name: &'a str, value: f64 },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkerSection {
+    pub id: usize,
+    pub name: String,
+    // 마커에 기록된 pid (보통 tgid)
+    pub pid: u32,
+    // 마커를 쓴 스레드 (ftrace 헤더의 pid), 비동기 구간은 S를 쓴 스레드
+    pub tid: u32,
+    pub process: String,
+    pub depth: usize,
+    pub parent: Option<usize>,
+    pub is_async: bool,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub duration_ms: f64,
+    // E/F 없이 트레이스가 끝난 구간은 마지막 이벤트 시각을 끝으로 사용
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CounterSample {
+    pub time: f64,
+    pub pid: u32,
+    pub name: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarkerTimeline {
+    pub sections: Vec<MarkerSection>,
+    pub counters: Vec<CounterSample>,
+    // 짝이 맞지 않는 E/F 수
+    pub unmatched_ends: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectionStats {
+    pub section: MarkerSection,
+    pub stats: TraceStats,
+}
+
+/// tracing_mark_write args 파싱 ("B|1234|AppLaunch", "E|1234", "C|1234|count|3" 등)
+pub fn parse_marker(args: &str) -> Option<Marker<'_>> {
+    let mut parts = args.trim_end().splitn(4, '|');
+    let kind = parts.next()?;
+    // E는 pid 없이 "E"만 쓰는 경우도 있음
+    if kind == "E" {
+        return Some(Marker::End);
+    }
+    let pid = parts.next()?.trim().parse().ok()?;
+    let name = parts.next()?;
+
+    match kind {
+        "B" => Some(Marker::Begin { pid, name }),
+        "C" => Some(Marker::Counter {
+            pid,
+            name,
+            value: parts.next()?.trim().parse().ok()?,
+        }),
+        "S" | "F" => {
+            let cookie = parts.next()?.trim().parse().ok()?;
+            if kind == "S" {
+                Some(Marker::AsyncBegin { pid, name, cookie })
+            } else {
+                Some(Marker::AsyncEnd { pid, name, cookie })
+            }
+        }
+        _ => None,
+    }
+}
+
+// 마커 이벤트면 args 반환 (tracing_mark_write 또는 print 이벤트)
+fn marker_args(event: &FtraceEvent) -> Option<&str> {
+    match event.event.as_str() {
+        MARKER_EVENT => Some(event.args.as_str()),
+        PRINT_EVENT => event
+            .args
+            .strip_prefix(MARKER_EVENT)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .map(str::trim_start),
+        _ => None,
+    }
+}
+
+/// 마커 이벤트로 구간/카운터 생성 (events는 시간순)
+pub fn build_timeline(events: &[FtraceEvent]) -> MarkerTimeline {
+    let mut sections: Vec<MarkerSection> = Vec::new();
+    let mut counters = Vec::new();
+    let mut unmatched_ends = 0;
+    // 스레드별 열린 B 구간 스택, (pid, name, cookie)별 열린 S 구간
+    let mut stacks: HashMap<u32, Vec<usize>> = HashMap::new();
+    let mut async_open: HashMap<(u32, String, i64), usize> = HashMap::new();
+    let mut last_time = 0.0_f64;
+
+    for event in events {
+        last_time = last_time.max(event.time);
+        let Some(marker) = marker_args(event).and_then(parse_marker) else {
+            continue;
+        };
+
+        let mut open_section = |name: &str, pid: u32, parent: Option<usize>, depth: usize, is_async: bool| {
+            sections.push(MarkerSection {
+                id: sections.len(),
+                name: name.to_string(),
+                pid,
+                tid: event.pid,
+                process: event.process.clone(),
+                depth,
+                parent,
+                is_async,
+                start_time: event.time,
+                end_time: event.time,
+                duration_ms: 0.0,
+                closed: false,
+            });
+            sections.len() - 1
+        };
+
+        match marker {
+            Marker::Begin { pid, name } => {
+                let stack = stacks.entry(event.pid).or_default();
+                let id = open_section(name, pid, stack.last().copied(), stack.len(), false);
+                stack.push(id);
+            }
+            Marker::AsyncBegin { pid, name, cookie } => {
+                let id = open_section(name, pid, None, 0, true);
+                async_open.insert((pid, name.to_string(), cookie), id);
+            }
+            Marker::End => match stacks.get_mut(&event.pid).and_then(|stack| stack.pop()) {
+                Some(id) => close_section(&mut sections[id], event.time),
+                None => unmatched_ends += 1,
+            },
+            Marker::AsyncEnd { pid, name, cookie } => {
+                match async_open.remove(&(pid, name.to_string(), cookie)) {
+                    Some(id) => close_section(&mut sections[id], event.time),
+                    None => unmatched_ends += 1,
+                }
+            }
+            Marker::Counter { pid, name, value } => counters.push(CounterSample {
+                time: event.time,
+                pid,
+                name: name.to_string(),
+                value,
+            }),
+        }
+    }
+
+    // 닫히지 않은 구간은 트레이스 끝까지
+    for section in sections.iter_mut().filter(|s| !s.closed) {
+        section.end_time = last_time;
+        section.duration_ms = (last_time - section.start_time) * 1000.0;
+    }
+
+    MarkerTimeline {
+        sections,
+        counters,
+        unmatched_ends,
+    }
+}
+
+fn close_section(section: &mut MarkerSection, time: f64) {
+    section.end_time = time;
+    section.duration_ms = (time - section.start_time) * 1000.0;
+    section.closed = true;
+}
+
+// events parquet의 마커 이벤트로 타임라인 생성
+fn load_timeline(events_logname: &str) -> Result<MarkerTimeline, String> {
+    with_events(events_logname, |events| {
+        let markers: Vec<FtraceEvent> = events
+            .iter()
+            .filter(|event| marker_args(event).is_some())
+            .cloned()
+            .collect();
+        build_timeline(&markers)
+    })
+}
+
+/// marker_sections 명령 - events_logname은 이벤트 테이블(events parquet) 경로
+pub fn marker_sections(events_logname: String) -> Result<String, String> {
+    let timeline = load_timeline(&events_logname)?;
+    println!(
+        "📌 마커 구간 {}개, 카운터 {}개 (짝 없는 종료 마커 {}개)",
+        timeline.sections.len(),
+        timeline.counters.len(),
+        timeline.unmatched_ends
+    );
+    serde_json::to_string(&timeline).map_err(|e| e.to_string())
+}
+
+/// 마커 구간별 전체 통계 (latency, size, continuity)
+/// section_name이 주어지면 해당 이름의 구간만 계산
+pub async fn section_stats(
+    events_logname: String,
+    logname: String,
+    trace_type: String,
+    section_name: Option<String>,
+    thresholds: Vec<String>,
+    group: bool,
+) -> Result<String, String> {
+    let timeline = load_timeline(&events_logname)?;
+    let sections: Vec<MarkerSection> = timeline
+        .sections
+        .into_iter()
+        .filter(|s| section_name.as_ref().is_none_or(|name| &s.name == name))
+        .collect();
+    if sections.is_empty() {
+        return Err("통계를 계산할 마커 구간이 없습니다".to_string());
+    }
+
+    let mut results = Vec::with_capacity(sections.len());
+    for section in sections {
+        let time_from = Some(section.start_time);
+        let time_to = Some(section.end_time);
+        let bytes = match trace_type.as_str() {
+            "ufs" => {
+                ufs::allstats(
+                    ufs::UfsAllStatsParams {
+                        logname: logname.clone(),
+                        zoom_column: "lba".to_string(),
+                        time_from,
+                        time_to,
+                        col_from: None,
+                        col_to: None,
+                    },
+                    thresholds.clone(),
+                )
+                .await?
+            }
+            "block" => {
+                block::allstats(block::AllStatsParams {
+                    logname: logname.clone(),
+                    zoom_column: "sector".to_string(),
+                    time_from,
+                    time_to,
+                    col_from: None,
+                    col_to: None,
+                    thresholds: thresholds.clone(),
+                    group,
+                    device: None,
+                    group_by_device: false,
+                })
+                .await?
+            }
+            _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+        };
+        let stats: TraceStats = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+        results.push(SectionStats { section, stats });
+    }
+
+    serde_json::to_string(&results).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::event_table::parse_event_line;
+
+    fn events(lines: &[&str]) -> Vec<FtraceEvent> {
+        lines.iter().map(|line| parse_event_line(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_parse_marker_kinds() {
+        assert_eq!(
+            parse_marker("B|1234|AppLaunch"),
+            Some(Marker::Begin { pid: 1234, name: "AppLaunch" })
+        );
+        assert_eq!(parse_marker("E|1234"), Some(Marker::End));
+        assert_eq!(
+            parse_marker("C|1234|queued|3"),
+            Some(Marker::Counter { pid: 1234, name: "queued", value: 3.0 })
+        );
+        assert_eq!(
+            parse_marker("S|1234|io|7"),
+            Some(Marker::AsyncBegin { pid: 1234, name: "io", cookie: 7 })
+        );
+        assert_eq!(parse_marker("hello"), None);
+    }
+
+    #[test]
+    fn test_build_timeline_nested_sections() {
+        let timeline = build_timeline(&events(&[
+            "  app-100  [000] ..... 1.000: tracing_mark_write: B|100|CameraCapture",
+            "  app-100  [000] ..... 1.100: tracing_mark_write: B|100|Encode",
+            "  app-101  [001] ..... 1.150: tracing_mark_write: B|100|Worker",
+            "  app-100  [000] ..... 1.200: tracing_mark_write: E|100",
+            "  app-100  [000] ..... 1.250: tracing_mark_write: C|100|frames|2",
+            "  app-100  [000] ..... 1.500: tracing_mark_write: E|100",
+            "  app-100  [000] ..... 1.600: tracing_mark_write: E|100",
+            "  app-100  [000] ..... 2.000: print: tracing_mark_write: B|100|Tail",
+        ]));
+
+        let capture = &timeline.sections[0];
+        assert!(capture.closed);
+        assert_eq!(capture.end_time, 1.5);
+        let encode = &timeline.sections[1];
+        assert_eq!((encode.depth, encode.parent), (1, Some(0)));
+        assert!((encode.duration_ms - 100.0).abs() < 1e-9);
+
+        // 다른 스레드의 구간은 별도 스택
+        let worker = &timeline.sections[2];
+        assert_eq!((worker.depth, worker.closed, worker.end_time), (0, false, 2.0));
+
+        assert_eq!(timeline.sections[3].name, "Tail");
+        assert_eq!(timeline.counters[0].value, 2.0);
+        assert_eq!(timeline.unmatched_ends, 1);
+    }
+}
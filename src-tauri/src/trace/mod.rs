@@ -1,8 +1,29 @@
 // src-tauri/src/trace/mod.rs - Update to use dynamic patterns
 
+mod barrier;
 mod block;
+mod busy;
+mod clock_sync;
+mod crosslayer;
+mod event_table;
 mod export;
 mod filter;
+mod forensics;
+mod ftrace_events;
+mod ftrace_format;
+mod heatmap;
+mod iostat;
+mod lifecycle;
+mod markers;
+mod pattern_bundle;
+mod pattern_dryrun;
+mod pattern_generator;
+mod pattern_schema;
+mod pattern_store;
+mod provenance;
+mod reuse;
+mod streams;
+mod timestamp;
 pub mod patterns;
 mod types;
 mod ufs;
@@ -10,6 +31,8 @@ mod ufscustom;
 mod utils;
 mod constants;
 mod parser_highperf; // 고성능 파서 추가
+#[cfg(test)]
+mod test_support;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -32,41 +55,45 @@ pub(crate) static BLOCK_CACHE: Lazy<Mutex<HashMap<String, Vec<Block>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 pub(crate) static UFSCUSTOM_CACHE: Lazy<Mutex<HashMap<String, Vec<UFSCUSTOM>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
+// 이벤트 테이블 캐시 (events parquet 경로 -> 이벤트)
+pub(crate) static EVENT_CACHE: Lazy<Mutex<HashMap<String, Vec<FtraceEvent>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 // Pattern caches
 pub(crate) static UFS_PATTERNS: Lazy<RwLock<HashMap<String, Regex>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 pub(crate) static BLOCK_PATTERNS: Lazy<RwLock<HashMap<String, Regex>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
+pub(crate) static UFSCUSTOM_PATTERNS: Lazy<RwLock<HashMap<String, Regex>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
 
-// Current active patterns (name -> compiled regex)
-pub(crate) static ACTIVE_UFS_PATTERN: Lazy<RwLock<(String, Regex)>> = Lazy::new(|| {
-    RwLock::new((
-        "Default UFS Pattern".to_string(),
-        Regex::new(
-            r"^\s*(.*?)\s+\[([0-9]+)\].*?([0-9]+\.[0-9]+):\s+ufshcd_command:\s+(send_req|complete_rsp):.*?tag:\s*(\d+).*?size:\s*([-]?\d+).*?LBA:\s*(\d+).*?opcode:\s*(0x[0-9a-f]+).*?group_id:\s*0x([0-9a-f]+).*?hwq_id:\s*([-]?\d+)"
-        ).unwrap()
-    ))
+// Current active patterns per type, tried in order (name, compiled regex)
+// 첫 번째가 기본 패턴이고 나머지는 매치되지 않은 라인에 순서대로 적용되는 fallback
+pub(crate) static ACTIVE_UFS_PATTERN: Lazy<RwLock<Vec<(String, Regex)>>> = Lazy::new(|| {
+    RwLock::new(vec![(
+        patterns::DEFAULT_UFS_PATTERN_NAME.to_string(),
+        Regex::new(patterns::DEFAULT_UFS_PATTERN).unwrap(),
+    )])
 });
 
-pub(crate) static ACTIVE_BLOCK_PATTERN: Lazy<RwLock<(String, Regex)>> = Lazy::new(|| {
-    RwLock::new((
-        "Default Block Pattern".to_string(),
-        Regex::new(
-            r"^\s*(?P<process>.*?)\s+\[(?P<cpu>\d+)\]\s+(?P<flags>.+?)\s+(?P<time>[\d\.]+):\s+(?P<action>\S+):\s+(?P<devmajor>\d+),(?P<devminor>\d+)\s+(?P<io_type>[A-Z]+)(?:\s+(?P<extra>\d+))?\s+\(\)\s+(?P<sector>\d+)\s+\+\s+(?P<size>\d+)(?:\s+\S+)?\s+\[(?P<comm>.*?)\]$"
-        ).unwrap()
-    ))
+pub(crate) static ACTIVE_BLOCK_PATTERN: Lazy<RwLock<Vec<(String, Regex)>>> = Lazy::new(|| {
+    RwLock::new(vec![(
+        patterns::DEFAULT_BLOCK_PATTERN_NAME.to_string(),
+        Regex::new(patterns::DEFAULT_BLOCK_PATTERN).unwrap(),
+    )])
 });
 
-pub(crate) static ACTIVE_UFSCUSTOM_PATTERN: Lazy<RwLock<(String, Regex)>> = Lazy::new(|| {
-    RwLock::new((
-        "Default UFS Custom Pattern".to_string(),
-        Regex::new(
-            r"^(?P<opcode>0x[0-9a-f]+),(?P<lba>\d+),(?P<size>\d+),(?P<start_time>\d+(?:\.\d+)?),(?P<end_time>\d+(?:\.\d+)?)$"
-        ).unwrap()
-    ))
+pub(crate) static ACTIVE_UFSCUSTOM_PATTERN: Lazy<RwLock<Vec<(String, Regex)>>> = Lazy::new(|| {
+    RwLock::new(vec![(
+        patterns::DEFAULT_UFSCUSTOM_PATTERN_NAME.to_string(),
+        Regex::new(patterns::DEFAULT_UFSCUSTOM_PATTERN).unwrap(),
+    )])
 });
 
+// ftrace format 파일로 생성한 이벤트 파서 (이벤트 이름 -> 파서)
+pub(crate) static EVENT_PARSERS: Lazy<RwLock<HashMap<String, ftrace_format::EventParser>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 // 샘플링 관련 상수 - 기본값 설정
 pub const DEFAULT_PREVIEW_RECORDS: usize = 500_000;
 
@@ -100,7 +127,7 @@ pub async fn readtrace(logname: String, maxrecords: Option<usize>) -> Result<Tra
 pub async fn readtrace_highperf(logname: String, window: tauri::Window) -> Result<String, String> {
     // 고성능 파서 사용
     match parser_highperf::parse_log_file_highperf(&logname, Some(&window)) {
-        Ok((ufs_traces, block_traces, ufscustom_traces)) => {
+        Ok((ufs_traces, block_traces, ufscustom_traces, _)) => {
             Ok(format!(
                 "고성능 파싱 완료: UFS={}, Block={}, UFSCUSTOM={}",
                 ufs_traces.len(),
@@ -122,14 +149,32 @@ pub async fn trace_lengths(logname: String) -> Result<TraceLengths, String> {
     utils::trace_lengths(logname).await
 }
 
+// Tauri 명령 - parquet footer에 기록된 출처 정보 조회
+#[tauri::command]
+pub fn trace_info(logname: String) -> Result<String, String> {
+    provenance::trace_info(logname)
+}
+
+// block_dedup: block 재전송 / flush 시퀀스 완료 제거 여부 (기본 true)
+// ufs_streams: UFS 순차 스트림 검출 옵션 (기본 8개 스트림, 허용 오차 0)
 #[tauri::command]
-pub async fn starttrace(fname: String, logfolder: String, window: Window) -> Result<TraceParseResult, String> {
+pub async fn starttrace(
+    fname: String,
+    logfolder: String,
+    window: Window,
+    block_dedup: Option<bool>,
+    ufs_streams: Option<streams::StreamOptions>,
+) -> Result<TraceParseResult, String> {
     // 작업 시작 시 취소 신호 초기화
     {
         let mut cancel = CANCEL_SIGNAL.lock().map_err(|e| e.to_string())?;
         *cancel = false;
     }
-    utils::starttrace(fname, logfolder, window).await
+    let options = provenance::PostProcessOptions {
+        block_dedup: block_dedup.unwrap_or(true),
+        ufs_streams: ufs_streams.unwrap_or_default(),
+    };
+    utils::starttrace(fname, logfolder, window, options).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -205,6 +250,27 @@ pub async fn ufs_allstats(
     }, thresholds).await
 }
 
+// Tauri 명령 - UFS 하드웨어 큐(hwqid, MCQ)별 QD / dtoc / ctoc / ctod 통계
+#[tauri::command]
+pub async fn ufs_hwq_stats(
+    logname: String,
+    zoom_column: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    col_from: Option<f64>,
+    col_to: Option<f64>,
+    thresholds: Vec<String>,
+) -> Result<Vec<u8>, String> {
+    ufs::hwq_stats(ufs::UfsAllStatsParams {
+        logname,
+        zoom_column,
+        time_from,
+        time_to,
+        col_from,
+        col_to,
+    }, thresholds).await
+}
+
 #[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn block_latencystats(
@@ -217,6 +283,8 @@ pub async fn block_latencystats(
     col_to: Option<f64>,
     thresholds: Vec<String>,
     group: bool,
+    device: Option<String>,
+    group_by_device: Option<bool>,
 ) -> Result<Vec<u8>, String> {
     use block::LatencyStatsParams;
     
@@ -230,6 +298,8 @@ pub async fn block_latencystats(
         col_to,
         thresholds,
         group,
+        device,
+        group_by_device: group_by_device.unwrap_or(false),
     })
     .await
 }
@@ -245,6 +315,8 @@ pub async fn block_sizestats(
     col_from: Option<f64>,
     col_to: Option<f64>,
     group: bool,
+    device: Option<String>,
+    group_by_device: Option<bool>,
 ) -> Result<Vec<u8>, String> {
     use block::SizeStatsParams;
     
@@ -257,6 +329,8 @@ pub async fn block_sizestats(
         col_from,
         col_to,
         group,
+        device,
+        group_by_device: group_by_device.unwrap_or(false),
     })
     .await
 }
@@ -272,6 +346,8 @@ pub async fn block_allstats(
     col_to: Option<f64>,
     thresholds: Vec<String>,
     group: bool,
+    device: Option<String>,
+    group_by_device: Option<bool>,
 ) -> Result<Vec<u8>, String> {
     use block::AllStatsParams;
     
@@ -284,6 +360,8 @@ pub async fn block_allstats(
         col_to,
         thresholds,
         group,
+        device,
+        group_by_device: group_by_device.unwrap_or(false),
     }).await
 }
 
@@ -299,6 +377,203 @@ pub async fn ufs_continuity_stats(
     ufs::continuity_stats(logname, zoom_column, time_from, time_to, col_from, col_to).await
 }
 
+// Tauri 명령 - 4KB 단위 재사용 거리 / working set 분석 (캐시 크기별 hit 비율, read-after-write / overwrite 비율)
+// trace_type: "ufs" | "block", working set은 window_ms 구간을 step_ms(기본 window_ms) 간격으로 이동하며 계산
+#[tauri::command]
+pub async fn reuse_distance(
+    logname: String,
+    trace_type: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    window_ms: Option<f64>,
+    step_ms: Option<f64>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        reuse::reuse_distance(
+            &logname,
+            &trace_type,
+            time_from,
+            time_to,
+            window_ms.unwrap_or(1000.0),
+            step_ms,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - LBA hot / cold 히트맵 ([시간 구간][주소 구간] 행렬: read / write 횟수, 바이트, 평균 / 최대 레이턴시)
+// trace_type: "ufs" | "block" | "ufscustom", col_from / col_to는 주소 범위 (UFS lba, block sector)
+// interval_ms가 없거나 0이면 트레이스 전체를 한 구간으로 집계, device는 block 장치 필터 ("major:minor")
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn lba_heatmap(
+    logname: String,
+    trace_type: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    col_from: Option<f64>,
+    col_to: Option<f64>,
+    addr_bins: Option<usize>,
+    interval_ms: Option<f64>,
+    device: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        heatmap::lba_heatmap(
+            &logname,
+            &trace_type,
+            time_from,
+            time_to,
+            col_from,
+            col_to,
+            addr_bins.unwrap_or(256),
+            interval_ms,
+            device.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - flush / FUA / discard / UNMAP 영향 분석 (명령 레이턴시, 직후 N개 / T ms 요청의 baseline 대비 레이턴시, 해제량 타임라인)
+// trace_type: "ufs" | "block", device는 block 장치 필터 ("major:minor")
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn barrier_impact(
+    logname: String,
+    trace_type: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    next_n: Option<usize>,
+    window_ms: Option<f64>,
+    interval_ms: Option<f64>,
+    device: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        barrier::barrier_impact(
+            &logname,
+            &trace_type,
+            time_from,
+            time_to,
+            next_n.unwrap_or(16),
+            window_ms.unwrap_or(10.0),
+            interval_ms.unwrap_or(1000.0),
+            device.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 가장 느린 N개 요청과 주변 상황 (QD, in-flight 요청, 선행 flush / discard, 전원 / 유실 마커)
+// metric: "dtoc" | "ctod" | "ctoc", events_logname은 마커를 찾을 이벤트 테이블(events parquet) 경로
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn top_slow_requests(
+    logname: String,
+    trace_type: String,
+    metric: Option<String>,
+    top_n: Option<usize>,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    window_ms: Option<f64>,
+    events_logname: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        forensics::top_slow_requests(
+            &logname,
+            &trace_type,
+            metric.as_deref().unwrap_or("dtoc"),
+            top_n.unwrap_or(20),
+            time_from,
+            time_to,
+            window_ms.unwrap_or(100.0),
+            events_logname.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - Block 요청과 UFS 명령 매칭 (host 오버헤드, 요청을 발생시킨 comm)
+// tolerance_ms: 계층 간 시각 비교 허용 오차, device는 block 장치 필터 ("major:minor")
+#[tauri::command]
+pub async fn block_ufs_match(
+    logname: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    tolerance_ms: Option<f64>,
+    device: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crosslayer::cross_layer_match(
+            &logname,
+            time_from,
+            time_to,
+            tolerance_ms.unwrap_or(0.0),
+            device.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - iostat 형식 구간 리포트 (구간 / op별 IOPS, MB/s, 레이턴시, QD, 사용률, R/W 비율)
+// trace_type: "ufs" | "block", interval_ms: 구간 길이 (예: 10, 100, 1000), device는 block 장치 필터 ("major:minor")
+#[tauri::command]
+pub async fn iostat_report(
+    logname: String,
+    trace_type: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    interval_ms: f64,
+    group: Option<bool>,
+    device: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        iostat::iostat_report(
+            &logname,
+            &trace_type,
+            time_from,
+            time_to,
+            interval_ms,
+            group.unwrap_or(true),
+            device.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 장치 busy / idle 분석 (사용률, idle gap 분포, 가장 긴 busy 구간)
+// trace_type: "ufs" | "block" | "ufscustom", device는 block 장치 필터 ("major:minor")
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn busy_idle_stats(
+    logname: String,
+    trace_type: String,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    thresholds: Vec<String>,
+    top_n: Option<usize>,
+    device: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        busy::busy_idle_stats(
+            &logname,
+            &trace_type,
+            time_from,
+            time_to,
+            &thresholds,
+            top_n,
+            device.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn block_continuity_stats(
     logname: String,
@@ -307,8 +582,36 @@ pub async fn block_continuity_stats(
     time_to: Option<f64>,
     col_from: Option<f64>,
     col_to: Option<f64>,
+    device: Option<String>,
+    group_by_device: Option<bool>,
 ) -> Result<Vec<u8>, String> {
-    block::continuity_stats(logname, zoom_column, time_from, time_to, col_from, col_to).await
+    block::continuity_stats(
+        logname,
+        zoom_column,
+        time_from,
+        time_to,
+        col_from,
+        col_to,
+        device,
+        group_by_device.unwrap_or(false),
+    )
+    .await
+}
+
+// Tauri 명령 - Block 트레이스의 장치(major:minor) 목록과 요청 수
+#[tauri::command]
+pub async fn block_devices(logname: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || block::devices(&logname))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - UFS send/complete 짝이 맞지 않는 요청 리포트 (orphan, tag 재사용)
+#[tauri::command]
+pub async fn ufs_tag_anomalies(logname: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || lifecycle::tag_anomalies(logname))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
 // UFSCUSTOM 통계 명령어들
@@ -406,6 +709,7 @@ pub async fn export_to_csv(
     zoom_column: Option<String>,
     col_from: Option<f64>,
     col_to: Option<f64>,
+    wall_clock: Option<bool>,
     window: tauri::Window,
 ) -> Result<Vec<String>, String> {
     let filter = if time_from.is_some() || time_to.is_some() || col_from.is_some() || col_to.is_some() {
@@ -420,7 +724,8 @@ pub async fn export_to_csv(
         None
     };
     
-    export::export_to_csv(parquet_path, output_dir, filter, Some(window)).await
+    // wall_clock 컬럼은 요청한 경우에만 추가 (기존 CSV 컬럼 구성 유지)
+    export::export_to_csv(parquet_path, output_dir, filter, wall_clock.unwrap_or(false), Some(window)).await
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -463,6 +768,11 @@ pub fn set_active_pattern(name: String, pattern_type: String) -> Result<(), Stri
     patterns::set_active_pattern(name, pattern_type)
 }
 
+#[tauri::command]
+pub fn set_active_patterns(names: Vec<String>, pattern_type: String) -> Result<(), String> {
+    patterns::set_active_patterns(names, pattern_type)
+}
+
 #[tauri::command]
 pub fn get_patterns(pattern_type: Option<String>) -> Result<String, String> {
     patterns::get_patterns(pattern_type)
@@ -473,6 +783,19 @@ pub fn get_active_patterns() -> Result<String, String> {
     patterns::get_active_patterns()
 }
 
+#[tauri::command]
+pub fn export_patterns(
+    path: String,
+    items: Option<Vec<pattern_bundle::PatternExportItem>>,
+) -> Result<usize, String> {
+    pattern_bundle::export_patterns(path, items)
+}
+
+#[tauri::command]
+pub fn import_patterns(path: String, on_conflict: Option<String>) -> Result<String, String> {
+    pattern_bundle::import_patterns(path, on_conflict)
+}
+
 #[tauri::command]
 pub fn delete_pattern(name: String, pattern_type: String) -> Result<(), String> {
     patterns::delete_pattern(name, pattern_type)
@@ -488,6 +811,8 @@ pub async fn reparse_trace(
     logfile_path: String,
     logfolder: String,
     window: tauri::Window,
+    block_dedup: Option<bool>,
+    ufs_streams: Option<streams::StreamOptions>,
 ) -> Result<String, String> {
     // 로그 파일 존재 여부 확인
     let path = std::path::Path::new(&logfile_path);
@@ -523,7 +848,11 @@ pub async fn reparse_trace(
     }
 
     // 로그 파일 다시 파싱
-    let result = utils::starttrace(logfile_path, logfolder, window).await?;
+    let options = provenance::PostProcessOptions {
+        block_dedup: block_dedup.unwrap_or(true),
+        ufs_streams: ufs_streams.unwrap_or_default(),
+    };
+    let result = utils::starttrace(logfile_path, logfolder, window, options).await?;
 
     // 파싱 결과를 JSON으로 반환
     serde_json::to_string(&result).map_err(|e| e.to_string())
@@ -617,8 +946,145 @@ pub fn delete_folder(folder_path: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, String> {
-    patterns::test_regex_pattern(text, pattern)
+pub fn test_regex_pattern(
+    text: String,
+    pattern: String,
+    pattern_type: Option<String>,
+) -> Result<String, String> {
+    patterns::test_regex_pattern(text, pattern, pattern_type)
+}
+
+// Tauri 명령 - 후보 패턴을 실제 로그 파일 일부에 적용해 매치율/변환 실패/미리보기 확인
+#[tauri::command]
+pub async fn dry_run_pattern(
+    path: String,
+    pattern: String,
+    pattern_type: String,
+    max_bytes: Option<u64>,
+    sample: Option<bool>,
+    preview_rows: Option<usize>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        pattern_dryrun::dry_run_pattern(path, pattern, pattern_type, max_bytes, sample, preview_rows)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 샘플 라인과 필드 구간으로 named group 정규식 생성
+#[tauri::command]
+pub async fn generate_pattern(
+    pattern_type: String,
+    sample_line: String,
+    spans: Vec<pattern_generator::FieldSpan>,
+    path: Option<String>,
+    max_bytes: Option<u64>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        pattern_generator::generate_pattern(pattern_type, sample_line, spans, path, max_bytes)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_pattern_fields(pattern_type: String) -> Result<String, String> {
+    let fields = pattern_generator::pattern_fields(&pattern_type)?;
+    serde_json::to_string(&fields).map_err(|e| e.to_string())
+}
+
+// Tauri 명령 - ftrace 이벤트 format 파일(디렉터리) 로드
+#[tauri::command]
+pub fn load_event_formats(path: String) -> Result<String, String> {
+    ftrace_format::load_event_formats(&path)
+}
+
+#[tauri::command]
+pub fn get_event_parsers() -> Result<String, String> {
+    ftrace_format::get_event_parsers()
+}
+
+// Tauri 명령 - 등록된 이벤트 파서로 로그를 파싱하여 이벤트별 parquet 저장
+#[tauri::command]
+pub async fn parse_event_log(
+    fname: String,
+    logfolder: String,
+    events: Option<Vec<String>>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || ftrace_events::parse_event_log(fname, logfolder, events))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 모든 ftrace 라인을 이벤트 테이블(events parquet)로 저장
+#[tauri::command]
+pub async fn parse_generic_events(
+    fname: String,
+    logfolder: String,
+    skip_events: Option<Vec<String>>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        event_table::parse_generic_events(fname, logfolder, skip_events)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn filter_events(logname: String, filter: event_table::EventFilter) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || event_table::filter_event_table(logname, filter))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn event_summary(logname: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || event_table::event_summary(logname))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 이벤트 테이블의 tracing_mark_write 마커를 구간/카운터로 변환
+#[tauri::command]
+pub async fn marker_sections(events_logname: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || markers::marker_sections(events_logname))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+// Tauri 명령 - 마커 구간별 UFS/Block 통계
+#[tauri::command]
+pub async fn marker_section_stats(
+    events_logname: String,
+    logname: String,
+    trace_type: String,
+    section_name: Option<String>,
+    thresholds: Vec<String>,
+    group: Option<bool>,
+) -> Result<String, String> {
+    markers::section_stats(
+        events_logname,
+        logname,
+        trace_type,
+        section_name,
+        thresholds,
+        group.unwrap_or(false),
+    )
+    .await
+}
+
+// Tauri 명령 - 사용자가 입력한 (트레이스 time, 실제 시각) 쌍을 parquet footer에 저장
+// wall_clock은 RFC 3339 문자열 또는 epoch 초
+#[tauri::command]
+pub async fn set_clock_sync(logname: String, uptime: f64, wall_clock: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || clock_sync::set_clock_sync(logname, uptime, wall_clock))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_clock_sync(logname: String) -> Result<String, String> {
+    clock_sync::get_clock_sync(logname)
 }
 
 // Tauri 명령 - 진행 중인 작업 취소
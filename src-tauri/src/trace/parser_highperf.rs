@@ -1,8 +1,12 @@
 // 고성능 메모리 맵 기반 파서 구현
 // kakaromo/trace의 log_high_perf.rs와 log_common.rs를 참고하여 구현
 
-use crate::trace::{Block, UFS, UFSCUSTOM, ProgressEvent};
+use crate::trace::{unnamed_pattern, Block, UFS, UFSCUSTOM, ProgressEvent};
+use crate::trace::patterns::named_patterns;
 use crate::trace::{ACTIVE_UFS_PATTERN, ACTIVE_BLOCK_PATTERN, ACTIVE_UFSCUSTOM_PATTERN};
+use crate::trace::timestamp;
+use crate::trace::utils::parse_hex_u32;
+use crate::trace::clock_sync::{self, ClockSync};
 use memmap2::MmapOptions;
 use rayon::prelude::*;
 use regex::Regex;
@@ -38,41 +42,99 @@ fn find_line_boundaries(data: &[u8]) -> Vec<usize> {
 
 
 
+// 타입별 활성 패턴 목록 (이름, 정규식) - 순서대로 시도
+type ActivePatterns = [(Arc<str>, Regex)];
+// 파싱 결과 (UFS, Block, UFSCUSTOM, 첫 번째 시각 동기화 마커)
+pub type ParsedTraces = (Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>, Option<ClockSync>);
+
+/// 활성 패턴을 순서대로 적용하여 처음 파싱에 성공한 이벤트 반환
+/// 이벤트의 pattern 필드에는 매치된 패턴 이름을 기록
+#[inline]
+fn parse_with_fallback<T>(
+    line: &str,
+    patterns: &ActivePatterns,
+    parse: fn(&str, &Regex) -> Option<T>,
+    set_pattern: fn(&mut T, &Arc<str>),
+) -> Option<T> {
+    patterns.iter().find_map(|(name, regex)| {
+        let mut event = parse(line, regex)?;
+        set_pattern(&mut event, name);
+        Some(event)
+    })
+}
+
+/// 키워드 필터는 기본(첫 번째) 패턴 형식에만 적용
+/// 필터를 통과하지 못한 라인도 fallback 패턴(두 번째 이후)은 시도 - 형식이 다른 로그용 패턴
+#[inline]
+fn parse_prefiltered<T>(
+    pattern_type: &str,
+    line: &str,
+    patterns: &ActivePatterns,
+    parse: fn(&str, &Regex) -> Option<T>,
+    set_pattern: fn(&mut T, &Arc<str>),
+) -> Option<T> {
+    let patterns = if keyword_prefilter(pattern_type, line) {
+        patterns
+    } else {
+        patterns.get(1..).unwrap_or(&[])
+    };
+    parse_with_fallback(line, patterns, parse, set_pattern)
+}
+
+/// 정규식 적용 전 키워드 기반 빠른 타입 판별
+/// UFSCUSTOM: CSV 형식 (콤마 4개 이상), UFS: "ufshcd_command", Block: "block_" 또는 "rq_"
+#[inline]
+pub(crate) fn keyword_prefilter(pattern_type: &str, line: &str) -> bool {
+    match pattern_type {
+        "ufscustom" => line.as_bytes().iter().filter(|&&b| b == b',').count() >= 4,
+        "ufs" => line.contains("ufshcd_command"),
+        "block" => line.contains("block_") || line.contains("rq_"),
+        _ => false,
+    }
+}
+
 /// 최적화된 라인 분류 및 파싱
-/// ACTIVE_*_PATTERN을 사용하여 정규표현식 기반 파싱
+/// ACTIVE_*_PATTERN 목록을 사용하여 정규표현식 기반 파싱
 #[inline]
 fn process_line_optimized(
     line: &str,
-    ufs_regex: &Regex,
-    block_regex: &Regex,
-    ufscustom_regex: &Regex,
+    ufs_patterns: &ActivePatterns,
+    block_patterns: &ActivePatterns,
+    ufscustom_patterns: &ActivePatterns,
 ) -> (Option<UFS>, Option<Block>, Option<UFSCUSTOM>) {
     if line.is_empty() || line.len() < 10 {
         return (None, None, None);
     }
     
-    let bytes = line.as_bytes();
-    
-    // 빠른 타입 판별 후 정규표현식 적용
-    // UFSCUSTOM: CSV 형식 (콤마 4개 이상)
-    if bytes.iter().filter(|&&b| b == b',').count() >= 4 {
-        if let Some(ufscustom) = parse_ufscustom_event(line, ufscustom_regex) {
-            return (None, None, Some(ufscustom));
-        }
+    // 빠른 타입 판별 후 정규표현식 적용 (fallback 패턴은 판별 없이 시도)
+    if let Some(ufscustom) = parse_prefiltered(
+        "ufscustom",
+        line,
+        ufscustom_patterns,
+        parse_ufscustom_event,
+        |e, name| e.pattern = Arc::clone(name),
+    ) {
+        return (None, None, Some(ufscustom));
     }
     
-    // UFS: "ufshcd_command" 포함
-    if line.contains("ufshcd_command") {
-        if let Some(ufs) = parse_ufs_event(line, ufs_regex) {
-            return (Some(ufs), None, None);
-        }
+    if let Some(ufs) = parse_prefiltered(
+        "ufs",
+        line,
+        ufs_patterns,
+        parse_ufs_event,
+        |e, name| e.pattern = Arc::clone(name),
+    ) {
+        return (Some(ufs), None, None);
     }
     
-    // Block: "block_" 또는 "rq_" 포함
-    if line.contains("block_") || line.contains("rq_") {
-        if let Some(block) = parse_block_io_event(line, block_regex) {
-            return (None, Some(block), None);
-        }
+    if let Some(block) = parse_prefiltered(
+        "block",
+        line,
+        block_patterns,
+        parse_block_io_event,
+        |e, name| e.pattern = Arc::clone(name),
+    ) {
+        return (None, Some(block), None);
     }
     
     (None, None, None)
@@ -86,8 +148,9 @@ fn process_line_optimized(
 fn parse_ufs_event(line: &str, regex: &Regex) -> Option<UFS> {
     let caps = regex.captures(line)?;
     
-    let time = caps.name("time")?.as_str().parse().ok()?;
-    let process = caps.name("process")?.as_str().to_string();
+    let time = timestamp::parse_seconds(caps.name("time")?.as_str())?;
+    // process는 선택 필드 (parse_ufs_trace_with_caps와 동일)
+    let process = caps.name("process").map_or_else(String::new, |m| m.as_str().to_string());
     let cpu = caps.name("cpu")?.as_str().parse().ok()?;
     let action = caps.name("command")?.as_str().to_string();
     let tag = caps.name("tag")?.as_str().parse().ok()?;
@@ -108,13 +171,8 @@ fn parse_ufs_event(line: &str, regex: &Regex) -> Option<UFS> {
     
     let opcode = caps.name("opcode")?.as_str().to_string();
     
-    // group_id 파싱 (0x 접두사 처리)
-    let groupid_str = caps.name("group_id")?.as_str();
-    let groupid = if groupid_str.starts_with("0x") {
-        u32::from_str_radix(&groupid_str[2..], 16).ok()?
-    } else {
-        groupid_str.parse().ok()?
-    };
+    // group_id는 항상 16진수 (0x 접두사 선택)
+    let groupid = parse_hex_u32(caps.name("group_id")?.as_str())?;
     
     let hwqid = caps.name("hwq_id")?.as_str().parse().ok()?;
     
@@ -134,6 +192,14 @@ fn parse_ufs_event(line: &str, regex: &Regex) -> Option<UFS> {
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        orphan: false,
+        hwq_qd: 0,
+        hwq_ctoc: 0.0,
+        hwq_ctod: 0.0,
+        stream_id: 0,
+        stream_position: 0,
+        comm: String::new(),
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -142,10 +208,11 @@ fn parse_ufs_event(line: &str, regex: &Regex) -> Option<UFS> {
 fn parse_block_io_event(line: &str, regex: &Regex) -> Option<Block> {
     let caps = regex.captures(line)?;
     
-    let time = caps.name("time")?.as_str().parse().ok()?;
-    let process = caps.name("process")?.as_str().to_string();
+    let time = timestamp::parse_seconds(caps.name("time")?.as_str())?;
+    // process / flags / comm은 선택 필드 (parse_block_trace_with_caps와 동일)
+    let process = caps.name("process").map_or_else(String::new, |m| m.as_str().to_string());
     let cpu = caps.name("cpu")?.as_str().parse().ok()?;
-    let flags = caps.name("flags")?.as_str().to_string();
+    let flags = caps.name("flags").map_or_else(String::new, |m| m.as_str().to_string());
     let action = caps.name("action")?.as_str().to_string();
     let devmajor = caps.name("devmajor")?.as_str().parse().ok()?;
     let devminor = caps.name("devminor")?.as_str().parse().ok()?;
@@ -165,7 +232,7 @@ fn parse_block_io_event(line: &str, regex: &Regex) -> Option<Block> {
     };
     
     let size = caps.name("size")?.as_str().parse().ok()?;
-    let comm = caps.name("comm")?.as_str().to_string();
+    let comm = caps.name("comm").map_or_else(String::new, |m| m.as_str().to_string());
     
     Some(Block {
         time,
@@ -185,6 +252,7 @@ fn parse_block_io_event(line: &str, regex: &Regex) -> Option<Block> {
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -196,10 +264,10 @@ fn parse_ufscustom_event(line: &str, regex: &Regex) -> Option<UFSCUSTOM> {
     let opcode = caps.name("opcode")?.as_str().to_string();
     let lba = caps.name("lba")?.as_str().parse().ok()?;
     let size = caps.name("size")?.as_str().parse().ok()?;
-    let start_time = caps.name("start_time")?.as_str().parse().ok()?;
-    let end_time = caps.name("end_time")?.as_str().parse().ok()?;
+    let start_time = timestamp::parse_seconds(caps.name("start_time")?.as_str())?;
+    let end_time = timestamp::parse_seconds(caps.name("end_time")?.as_str())?;
     
-    let dtoc = (end_time - start_time) * 1000.0;
+    let dtoc = timestamp::ns_to_ms(end_time - start_time);
     
     Some(UFSCUSTOM {
         opcode,
@@ -213,6 +281,7 @@ fn parse_ufscustom_event(line: &str, regex: &Regex) -> Option<UFSCUSTOM> {
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -221,10 +290,10 @@ fn process_chunk(
     data: &[u8],
     start: usize,
     end: usize,
-    ufs_regex: &Regex,
-    block_regex: &Regex,
-    ufscustom_regex: &Regex,
-) -> (Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>) {
+    ufs_patterns: &ActivePatterns,
+    block_patterns: &ActivePatterns,
+    ufscustom_patterns: &ActivePatterns,
+) -> ParsedTraces {
     let chunk_data = &data[start..end];
     let boundaries = find_line_boundaries(chunk_data);
     
@@ -233,6 +302,8 @@ fn process_chunk(
     let mut ufs_traces = Vec::with_capacity(estimated_lines / 10);
     let mut block_traces = Vec::with_capacity(estimated_lines / 10);
     let mut ufscustom_traces = Vec::with_capacity(estimated_lines / 10);
+    // chunk 내 첫 번째 시각 동기화 마커
+    let mut sync_marker = None;
     
     // 각 라인 처리
     for window in boundaries.windows(2) {
@@ -245,30 +316,29 @@ fn process_chunk(
             // UTF-8 변환 (필요할 때만)
             if let Ok(line_str) = std::str::from_utf8(line) {
                 let line_str = line_str.trim();
-                let (maybe_ufs, maybe_block, maybe_ufscustom) = 
-                    process_line_optimized(line_str, ufs_regex, block_regex, ufscustom_regex);
-                
-                if let Some(ufs) = maybe_ufs {
-                    ufs_traces.push(ufs);
-                }
-                if let Some(block) = maybe_block {
-                    block_traces.push(block);
-                }
-                if let Some(ufscustom) = maybe_ufscustom {
-                    ufscustom_traces.push(ufscustom);
+                match process_line_optimized(line_str, ufs_patterns, block_patterns, ufscustom_patterns) {
+                    (Some(ufs), _, _) => ufs_traces.push(ufs),
+                    (_, Some(block), _) => block_traces.push(block),
+                    (_, _, Some(ufscustom)) => ufscustom_traces.push(ufscustom),
+                    // 패턴과 일치하지 않은 라인에서만 시각 동기화 마커 확인
+                    (None, None, None) => {
+                        if sync_marker.is_none() {
+                            sync_marker = clock_sync::clock_sync_from_unmatched(line_str);
+                        }
+                    }
                 }
             }
         }
     }
     
-    (ufs_traces, block_traces, ufscustom_traces)
+    (ufs_traces, block_traces, ufscustom_traces, sync_marker)
 }
 
 /// 메인 고성능 파싱 함수
 pub fn parse_log_file_highperf(
     filepath: &str, 
     window: Option<&tauri::Window>
-) -> io::Result<(Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>)> {
+) -> io::Result<ParsedTraces> {
     let start_time = Instant::now();
     println!("🚀 고성능 파싱 시작: {}", filepath);
     
@@ -346,18 +416,17 @@ pub fn parse_log_file_highperf(
     
     // ACTIVE 패턴 읽기
     println!("📋 ACTIVE 패턴 로드 중...");
-    let ufs_pattern = ACTIVE_UFS_PATTERN.read().unwrap();
-    let block_pattern = ACTIVE_BLOCK_PATTERN.read().unwrap();
-    let ufscustom_pattern = ACTIVE_UFSCUSTOM_PATTERN.read().unwrap();
-    
-    let ufs_regex = &ufs_pattern.1;
-    let block_regex = &block_pattern.1;
-    let ufscustom_regex = &ufscustom_pattern.1;
+    let ufs_patterns = named_patterns(&ACTIVE_UFS_PATTERN.read().unwrap());
+    let block_patterns = named_patterns(&ACTIVE_BLOCK_PATTERN.read().unwrap());
+    let ufscustom_patterns = named_patterns(&ACTIVE_UFSCUSTOM_PATTERN.read().unwrap());
     
+    fn pattern_names(patterns: &ActivePatterns) -> String {
+        patterns.iter().map(|(name, _)| &**name).collect::<Vec<_>>().join(" → ")
+    }
     println!("✅ 패턴 로드 완료:");
-    println!("  - UFS: {}", ufs_pattern.0);
-    println!("  - Block: {}", block_pattern.0);
-    println!("  - UFSCustom: {}", ufscustom_pattern.0);
+    println!("  - UFS: {}", pattern_names(&ufs_patterns));
+    println!("  - Block: {}", pattern_names(&block_patterns));
+    println!("  - UFSCustom: {}", pattern_names(&ufscustom_patterns));
     
     // 진행 상태 카운터 (Arc<Mutex>로 공유)
     let completed_chunks = Arc::new(Mutex::new(0usize));
@@ -365,11 +434,18 @@ pub fn parse_log_file_highperf(
     
     // 병렬 처리 - 인덱스와 함께 결과 저장하여 순서 보존
     let parse_start = Instant::now();
-    let results: Vec<(usize, Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>)> = chunk_boundaries
+    let results: Vec<(usize, ParsedTraces)> = chunk_boundaries
         .par_iter()
         .enumerate()
         .map(|(i, &(start, end))| {
-            let result = process_chunk(&data, start as usize, end as usize, ufs_regex, block_regex, ufscustom_regex);
+            let result = process_chunk(
+                &data,
+                start as usize,
+                end as usize,
+                &ufs_patterns,
+                &block_patterns,
+                &ufscustom_patterns,
+            );
             
             // 진행 상황 업데이트 (5% 간격)
             let mut completed = completed_chunks.lock().unwrap();
@@ -398,7 +474,7 @@ pub fn parse_log_file_highperf(
             }
             
             // 인덱스와 함께 반환하여 순서 보존
-            (i, result.0, result.1, result.2)
+            (i, result)
         })
         .collect();
     
@@ -425,20 +501,23 @@ pub fn parse_log_file_highperf(
     let mut ufs_traces = Vec::new();
     let mut block_traces = Vec::new();
     let mut ufscustom_traces = Vec::new();
+    // 가장 앞선 chunk의 시각 동기화 마커 사용
+    let mut sync_marker = None;
     
     // 용량 사전 할당
     let total_estimate = sorted_results.iter()
-        .map(|r| r.1.len() + r.2.len() + r.3.len())
+        .map(|(_, r)| r.0.len() + r.1.len() + r.2.len())
         .sum::<usize>();
     ufs_traces.reserve(total_estimate / 3);
     block_traces.reserve(total_estimate / 3);
     ufscustom_traces.reserve(total_estimate / 3);
     
     // 인덱스 순서대로 merge (순서 보존)
-    for (_idx, ufs, block, ufscustom) in sorted_results {
+    for (_idx, (ufs, block, ufscustom, chunk_sync)) in sorted_results {
         ufs_traces.extend(ufs);
         block_traces.extend(block);
         ufscustom_traces.extend(ufscustom);
+        sync_marker = sync_marker.or(chunk_sync);
     }
     
     println!("🔗 결과 merge 완료: {:.2}초", merge_start.elapsed().as_secs_f64());
@@ -468,7 +547,7 @@ pub fn parse_log_file_highperf(
     println!("  ⏱️  총 시간: {:.2}초", total_time);
     println!("  🚄 처리 속도: {:.2} MB/s", throughput);
     
-    Ok((ufs_traces, block_traces, ufscustom_traces))
+    Ok((ufs_traces, block_traces, ufscustom_traces, sync_marker))
 }
 
 #[cfg(test)]
@@ -486,7 +565,7 @@ mod tests {
     fn test_parse_ufscustom_event() {
         // ACTIVE_UFSCUSTOM_PATTERN 사용
         let pattern = ACTIVE_UFSCUSTOM_PATTERN.read().unwrap();
-        let regex = &pattern.1;
+        let regex = &pattern[0].1;
         
         let line = "0x28,1048576,8,123.456,123.789";
         let result = parse_ufscustom_event(line, regex);
@@ -495,8 +574,8 @@ mod tests {
             assert_eq!(ufscustom.opcode, "0x28");
             assert_eq!(ufscustom.lba, 1048576);
             assert_eq!(ufscustom.size, 8);
-            assert!((ufscustom.start_time - 123.456).abs() < 0.001);
-            assert!((ufscustom.end_time - 123.789).abs() < 0.001);
+            assert_eq!(ufscustom.start_time, 123_456_000_000);
+            assert_eq!(ufscustom.end_time, 123_789_000_000);
         }
     }
     
@@ -504,7 +583,7 @@ mod tests {
     fn test_parse_ufs_event() {
         // ACTIVE_UFS_PATTERN 사용
         let pattern = ACTIVE_UFS_PATTERN.read().unwrap();
-        let regex = &pattern.1;
+        let regex = &pattern[0].1;
         
         // UFS 파서 테스트 - 실제 ACTIVE_UFS_PATTERN에 맞는 형식 필요
         let line = "kworker/u16:3 [7] 123.456789: ufshcd_command: send_req: ... tag: 5 ... size: 32768 ... LBA: 1048576 ... opcode: 0x28 ... group_id: 0x01 ... hwq_id: 0";
@@ -516,7 +595,7 @@ mod tests {
     fn test_parse_block_io_event() {
         // ACTIVE_BLOCK_PATTERN 사용
         let pattern = ACTIVE_BLOCK_PATTERN.read().unwrap();
-        let regex = &pattern.1;
+        let regex = &pattern[0].1;
         
         // Block 파서 테스트 - 실제 ACTIVE_BLOCK_PATTERN에 맞는 형식 필요
         let line = "kworker/u16:0 [0] d..1. 123.456: block_rq_issue: 8,0 R 0 () 2048 + 8 [kworker/u16:0]";
@@ -528,23 +607,90 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_optional_fields_and_hex_group_id() {
+        // process 없는 UFS 패턴 - group_id는 0x 유무와 관계없이 16진수
+        let ufs_regex = Regex::new(
+            r"^\[(?P<cpu>\d+)\] (?P<time>[\d.]+): (?P<command>\S+) tag: (?P<tag>\d+) size: (?P<size>\d+) LBA: (?P<lba>\d+) opcode: (?P<opcode>0x[0-9a-f]+) group_id: (?P<group_id>\S+) hwq_id: (?P<hwq_id>\d+)$",
+        )
+        .unwrap();
+        let line = "[1] 1.5: send_req tag: 3 size: 8192 LBA: 100 opcode: 0x2a group_id: 10 hwq_id: 2";
+        let ufs = parse_ufs_event(line, &ufs_regex).unwrap();
+        assert_eq!((ufs.process.as_str(), ufs.groupid, ufs.size), ("", 16, 2));
+        let ufs = parse_ufs_event(&line.replace("group_id: 10", "group_id: 0x10"), &ufs_regex).unwrap();
+        assert_eq!(ufs.groupid, 16);
+        assert!(parse_ufs_event(&line.replace("group_id: 10", "group_id: zz"), &ufs_regex).is_none());
+
+        // process / flags / comm 없는 Block 패턴
+        let block_regex = Regex::new(
+            r"^\[(?P<cpu>\d+)\] (?P<time>[\d.]+): (?P<action>\S+): (?P<devmajor>\d+),(?P<devminor>\d+) (?P<io_type>[A-Z]+) (?P<sector>\d+) \+ (?P<size>\d+)$",
+        )
+        .unwrap();
+        let block = parse_block_io_event("[0] 2.0: block_rq_issue: 8,0 W 64 + 8", &block_regex).unwrap();
+        assert_eq!((block.process.as_str(), block.flags.as_str(), block.comm.as_str()), ("", "", ""));
+        assert_eq!((block.sector, block.size), (64, 8));
+    }
+
     #[test]
     fn test_process_line_optimized() {
         // ACTIVE 패턴들 읽기
-        let ufs_pattern = ACTIVE_UFS_PATTERN.read().unwrap();
-        let block_pattern = ACTIVE_BLOCK_PATTERN.read().unwrap();
-        let ufscustom_pattern = ACTIVE_UFSCUSTOM_PATTERN.read().unwrap();
-        
-        let ufs_regex = &ufs_pattern.1;
-        let block_regex = &block_pattern.1;
-        let ufscustom_regex = &ufscustom_pattern.1;
+        let ufs_patterns = named_patterns(&ACTIVE_UFS_PATTERN.read().unwrap());
+        let block_patterns = named_patterns(&ACTIVE_BLOCK_PATTERN.read().unwrap());
+        let ufscustom_patterns = named_patterns(&ACTIVE_UFSCUSTOM_PATTERN.read().unwrap());
         
         // UFSCUSTOM 라인 테스트
         let line = "0x28,1048576,8,123.456,123.789";
-        let (ufs, block, ufscustom) = process_line_optimized(line, ufs_regex, block_regex, ufscustom_regex);
+        let (ufs, block, ufscustom) =
+            process_line_optimized(line, &ufs_patterns, &block_patterns, &ufscustom_patterns);
         
         assert!(ufs.is_none());
         assert!(block.is_none());
         assert!(ufscustom.is_some());
     }
+    
+    #[test]
+    fn test_parse_with_fallback_records_matching_pattern() {
+        let patterns = vec![
+            (
+                Arc::from("primary"),
+                Regex::new(r"^(?P<opcode>0x[0-9a-f]+);(?P<lba>\d+);(?P<size>\d+);(?P<start_time>[\d.]+);(?P<end_time>[\d.]+)$").unwrap(),
+            ),
+            (
+                Arc::from("fallback"),
+                Regex::new(crate::trace::patterns::DEFAULT_UFSCUSTOM_PATTERN).unwrap(),
+            ),
+        ];
+        let set_pattern: fn(&mut UFSCUSTOM, &Arc<str>) = |e, name| e.pattern = Arc::clone(name);
+        
+        let first = parse_with_fallback("0x28;100;8;1.0;1.5", &patterns, parse_ufscustom_event, set_pattern);
+        assert_eq!(&*first.unwrap().pattern, "primary");
+        
+        let second = parse_with_fallback("0x2a,200,8,2.0,2.5", &patterns, parse_ufscustom_event, set_pattern);
+        assert_eq!(&*second.unwrap().pattern, "fallback");
+        
+        assert!(parse_with_fallback("garbage", &patterns, parse_ufscustom_event, set_pattern).is_none());
+    }
+
+    #[test]
+    fn test_fallback_patterns_skip_keyword_prefilter() {
+        let ufscustom_patterns = vec![
+            (
+                Arc::from("default"),
+                Regex::new(crate::trace::patterns::DEFAULT_UFSCUSTOM_PATTERN).unwrap(),
+            ),
+            (
+                Arc::from("semicolon"),
+                Regex::new(r"^(?P<opcode>0x[0-9a-f]+);(?P<lba>\d+);(?P<size>\d+);(?P<start_time>[\d.]+);(?P<end_time>[\d.]+)$").unwrap(),
+            ),
+        ];
+
+        // 콤마가 없어 키워드 필터는 통과하지 못하지만 fallback 패턴으로 파싱
+        let (ufs, block, ufscustom) = process_line_optimized("0x28;100;8;1.0;1.5", &[], &[], &ufscustom_patterns);
+        assert!(ufs.is_none() && block.is_none());
+        assert_eq!(&*ufscustom.unwrap().pattern, "semicolon");
+
+        // 기본 패턴만 있으면 키워드 필터에서 거부
+        let (_, _, ufscustom) = process_line_optimized("0x28;100;8;1.0;1.5", &[], &[], &ufscustom_patterns[..1]);
+        assert!(ufscustom.is_none());
+    }
 }
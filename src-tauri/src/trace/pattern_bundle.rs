@@ -0,0 +1,450 @@
+// src-tauri/src/trace/pattern_bundle.rs
+//
+// 팀 간 패턴 공유를 위한 번들 파일 내보내기/가져오기
+// 확장자가 .toml이면 TOML, 그 외에는 JSON으로 읽고 쓴다.
+
+use chrono::Local;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::pattern_schema;
+use super::pattern_store;
+use super::patterns::{insert_pattern, registry, PATTERN_TYPES};
+
+// 번들 포맷 버전 (포맷이 바뀌면 증가)
+pub const PATTERN_BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: String,
+    pub patterns: Vec<BundledPattern>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledPattern {
+    pub name: String,
+    pub pattern_type: String,
+    pub pattern: String,
+    // 스키마 필드 -> "required" / "optional" (정규식에 있는 named group만)
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub sample_lines: Vec<String>,
+}
+
+// 내보낼 패턴 선택 - description/sample_lines는 프론트엔드 DB에 있으므로 함께 전달받음
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatternExportItem {
+    pub name: String,
+    pub pattern_type: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub sample_lines: Vec<String>,
+}
+
+// 같은 이름, 다른 정규식인 패턴 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    // 충돌이 하나라도 있으면 아무것도 가져오지 않고 충돌 목록만 보고
+    Abort,
+    Rename,
+    Overwrite,
+    Skip,
+}
+
+impl ConflictAction {
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value {
+            None | Some("abort") => Ok(ConflictAction::Abort),
+            Some("rename") => Ok(ConflictAction::Rename),
+            Some("overwrite") => Ok(ConflictAction::Overwrite),
+            Some("skip") => Ok(ConflictAction::Skip),
+            Some(other) => Err(format!("Unsupported conflict action: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatternConflict {
+    pub name: String,
+    pub pattern_type: String,
+    pub existing_pattern: String,
+    pub incoming_pattern: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedPattern {
+    pub name: String,
+    pub original_name: String,
+    pub pattern_type: String,
+    pub pattern: String,
+    pub description: String,
+    // 프론트엔드 DB에 함께 저장할 sample line
+    pub sample_lines: Vec<String>,
+    // "added" / "renamed" / "overwritten"
+    pub action: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RejectedPattern {
+    pub name: String,
+    pub pattern_type: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct PatternImportReport {
+    pub imported: Vec<ImportedPattern>,
+    pub conflicts: Vec<PatternConflict>,
+    pub rejected: Vec<RejectedPattern>,
+    // 이름과 정규식이 모두 같아 건너뛴 패턴 수
+    pub unchanged: usize,
+    // 충돌로 인해 가져오기를 중단했는지 여부
+    pub aborted: bool,
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+// 정규식의 named group 중 스키마 필드만 required/optional로 표시
+fn field_map(pattern_type: &str, regex: &Regex) -> Result<BTreeMap<String, String>, String> {
+    let schema = pattern_schema::schema_for(pattern_type)?;
+    let names: Vec<&str> = regex.capture_names().flatten().collect();
+
+    let mut fields = BTreeMap::new();
+    for (specs, requirement) in [(schema.required, "required"), (schema.optional, "optional")] {
+        for spec in specs.iter().filter(|spec| names.contains(&spec.name)) {
+            fields.insert(spec.name.to_string(), requirement.to_string());
+        }
+    }
+    Ok(fields)
+}
+
+/// 레지스트리의 패턴을 번들로 수집 (items가 None이면 전체)
+pub fn build_bundle(items: Option<Vec<PatternExportItem>>) -> Result<PatternBundle, String> {
+    let items = match items {
+        Some(items) => items,
+        None => {
+            let mut all = Vec::new();
+            for pattern_type in PATTERN_TYPES {
+                let (patterns, _) = registry(pattern_type)?;
+                let patterns = patterns.read().map_err(|e| e.to_string())?;
+                let mut names: Vec<&String> = patterns.keys().collect();
+                names.sort();
+                all.extend(names.into_iter().map(|name| PatternExportItem {
+                    name: name.clone(),
+                    pattern_type: pattern_type.to_string(),
+                    description: String::new(),
+                    sample_lines: Vec::new(),
+                }));
+            }
+            all
+        }
+    };
+
+    let mut bundled = Vec::with_capacity(items.len());
+    for item in items {
+        let (patterns, _) = registry(&item.pattern_type)?;
+        let regex = patterns
+            .read()
+            .map_err(|e| e.to_string())?
+            .get(&item.name)
+            .cloned()
+            .ok_or_else(|| format!("Pattern '{}' not found", item.name))?;
+
+        bundled.push(BundledPattern {
+            fields: field_map(&item.pattern_type, &regex)?,
+            name: item.name,
+            pattern_type: item.pattern_type,
+            pattern: regex.as_str().to_string(),
+            description: item.description,
+            sample_lines: item.sample_lines,
+        });
+    }
+
+    Ok(PatternBundle {
+        version: PATTERN_BUNDLE_VERSION,
+        exported_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        patterns: bundled,
+    })
+}
+
+pub fn serialize_bundle(bundle: &PatternBundle, toml_format: bool) -> Result<String, String> {
+    if toml_format {
+        toml::to_string_pretty(bundle).map_err(|e| format!("TOML 변환 실패: {}", e))
+    } else {
+        serde_json::to_string_pretty(bundle).map_err(|e| e.to_string())
+    }
+}
+
+pub fn parse_bundle(content: &str, toml_format: bool) -> Result<PatternBundle, String> {
+    let bundle: PatternBundle = if toml_format {
+        toml::from_str(content).map_err(|e| format!("패턴 번들 파싱 실패: {}", e))?
+    } else {
+        serde_json::from_str(content).map_err(|e| format!("패턴 번들 파싱 실패: {}", e))?
+    };
+
+    if bundle.version > PATTERN_BUNDLE_VERSION {
+        return Err(format!(
+            "지원하지 않는 패턴 번들 버전: {} (최대 {})",
+            bundle.version, PATTERN_BUNDLE_VERSION
+        ));
+    }
+
+    Ok(bundle)
+}
+
+/// 번들의 패턴 하나를 검증
+/// 정규식 컴파일, 스키마 검사, sample line이 있으면 모두 매치되고 필수 필드가 변환되는지 확인
+pub fn validate_bundled(pattern: &BundledPattern) -> Result<Regex, String> {
+    let regex =
+        Regex::new(&pattern.pattern).map_err(|e| format!("Invalid regex pattern: {}", e))?;
+    pattern_schema::validate_pattern(&pattern.pattern_type, &regex)?;
+
+    let schema = pattern_schema::schema_for(&pattern.pattern_type)?;
+    for (index, line) in pattern.sample_lines.iter().enumerate() {
+        let caps = regex
+            .captures(line)
+            .ok_or_else(|| format!("sample line {}이 패턴과 매치되지 않습니다", index + 1))?;
+        let failed = pattern_schema::failed_required_fields(schema, &caps);
+        if !failed.is_empty() {
+            return Err(format!(
+                "sample line {}에서 필드 변환 실패: {}",
+                index + 1,
+                failed.join(", ")
+            ));
+        }
+    }
+
+    Ok(regex)
+}
+
+// 레지스트리에 없는 이름이 나올 때까지 " (n)" 접미사 증가
+fn unique_name(pattern_type: &str, name: &str, taken: &[String]) -> Result<String, String> {
+    let (patterns, _) = registry(pattern_type)?;
+    let patterns = patterns.read().map_err(|e| e.to_string())?;
+
+    let mut index = 2;
+    loop {
+        let candidate = format!("{} ({})", name, index);
+        if !patterns.contains_key(&candidate) && !taken.contains(&candidate) {
+            return Ok(candidate);
+        }
+        index += 1;
+    }
+}
+
+// 번들 안에서 같은 타입 / 같은 이름이 다시 나오면 처음 것만 남기고 나머지는 거부
+// (그대로 두면 뒤의 패턴이 앞의 패턴을 조용히 덮어씀)
+fn split_duplicates(patterns: Vec<BundledPattern>) -> (Vec<BundledPattern>, Vec<RejectedPattern>) {
+    let mut seen: Vec<(String, String)> = Vec::new();
+    let mut unique = Vec::with_capacity(patterns.len());
+    let mut rejected = Vec::new();
+
+    for pattern in patterns {
+        let key = (pattern.pattern_type.clone(), pattern.name.clone());
+        if seen.contains(&key) {
+            rejected.push(RejectedPattern {
+                name: pattern.name,
+                pattern_type: pattern.pattern_type,
+                reason: "번들 안에 같은 이름의 패턴이 두 번 이상 있습니다 (첫 번째만 가져옴)".to_string(),
+            });
+            continue;
+        }
+        seen.push(key);
+        unique.push(pattern);
+    }
+
+    (unique, rejected)
+}
+
+/// 번들을 레지스트리에 반영
+pub fn import_bundle(
+    bundle: PatternBundle,
+    on_conflict: ConflictAction,
+) -> Result<PatternImportReport, String> {
+    let mut report = PatternImportReport::default();
+    let mut accepted: Vec<(BundledPattern, Regex, bool)> = Vec::new();
+
+    let (patterns, duplicates) = split_duplicates(bundle.patterns);
+    report.rejected = duplicates;
+
+    // 1단계: 검증 및 충돌 검사 (레지스트리는 아직 변경하지 않음)
+    for pattern in patterns {
+        let regex = match validate_bundled(&pattern) {
+            Ok(regex) => regex,
+            Err(reason) => {
+                report.rejected.push(RejectedPattern {
+                    name: pattern.name,
+                    pattern_type: pattern.pattern_type,
+                    reason,
+                });
+                continue;
+            }
+        };
+
+        let (patterns, _) = registry(&pattern.pattern_type)?;
+        let existing = patterns
+            .read()
+            .map_err(|e| e.to_string())?
+            .get(&pattern.name)
+            .map(|re| re.as_str().to_string());
+
+        match existing {
+            Some(existing) if existing == pattern.pattern => report.unchanged += 1,
+            Some(existing) => {
+                report.conflicts.push(PatternConflict {
+                    name: pattern.name.clone(),
+                    pattern_type: pattern.pattern_type.clone(),
+                    existing_pattern: existing,
+                    incoming_pattern: pattern.pattern.clone(),
+                });
+                accepted.push((pattern, regex, true));
+            }
+            None => accepted.push((pattern, regex, false)),
+        }
+    }
+
+    if on_conflict == ConflictAction::Abort && !report.conflicts.is_empty() {
+        report.aborted = true;
+        return Ok(report);
+    }
+
+    // 2단계: 레지스트리에 추가
+    let mut renamed: Vec<String> = Vec::new();
+    for (pattern, regex, conflict) in accepted {
+        let (name, action) = match (conflict, on_conflict) {
+            (false, _) => (pattern.name.clone(), "added"),
+            (true, ConflictAction::Skip) => continue,
+            (true, ConflictAction::Overwrite) => (pattern.name.clone(), "overwritten"),
+            (true, _) => {
+                let name = unique_name(&pattern.pattern_type, &pattern.name, &renamed)?;
+                renamed.push(name.clone());
+                (name, "renamed")
+            }
+        };
+
+        insert_pattern(&pattern.pattern_type, name.clone(), regex)?;
+        report.imported.push(ImportedPattern {
+            name,
+            original_name: pattern.name,
+            pattern_type: pattern.pattern_type,
+            pattern: pattern.pattern,
+            description: pattern.description,
+            sample_lines: pattern.sample_lines,
+            action: action.to_string(),
+        });
+    }
+
+    if !report.imported.is_empty() {
+        pattern_store::save_or_warn();
+    }
+
+    Ok(report)
+}
+
+/// 패턴 번들 파일 쓰기
+pub fn export_patterns(path: String, items: Option<Vec<PatternExportItem>>) -> Result<usize, String> {
+    let path = Path::new(&path);
+    let bundle = build_bundle(items)?;
+    let content = serialize_bundle(&bundle, is_toml(path))?;
+
+    fs::write(path, content)
+        .map_err(|e| format!("패턴 번들 쓰기 실패 ({}): {}", path.display(), e))?;
+
+    Ok(bundle.patterns.len())
+}
+
+/// 패턴 번들 파일 읽어서 가져오기 - 결과 리포트를 JSON 문자열로 반환
+pub fn import_patterns(path: String, on_conflict: Option<String>) -> Result<String, String> {
+    let path = Path::new(&path);
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("패턴 번들 읽기 실패 ({}): {}", path.display(), e))?;
+
+    let bundle = parse_bundle(&content, is_toml(path))?;
+    let report = import_bundle(bundle, ConflictAction::parse(on_conflict.as_deref())?)?;
+
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::patterns::DEFAULT_UFSCUSTOM_PATTERN;
+
+    fn sample_bundle() -> PatternBundle {
+        PatternBundle {
+            version: PATTERN_BUNDLE_VERSION,
+            exported_at: String::new(),
+            patterns: vec![BundledPattern {
+                name: "vendor csv".to_string(),
+                pattern_type: "ufscustom".to_string(),
+                pattern: DEFAULT_UFSCUSTOM_PATTERN.to_string(),
+                fields: BTreeMap::new(),
+                description: "CSV export".to_string(),
+                sample_lines: vec!["0x28,1048576,8,123.456,123.789".to_string()],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_bundle_roundtrip_json_and_toml() {
+        let bundle = sample_bundle();
+        for toml_format in [false, true] {
+            let content = serialize_bundle(&bundle, toml_format).unwrap();
+            let parsed = parse_bundle(&content, toml_format).unwrap();
+            assert_eq!(parsed.patterns.len(), 1);
+            assert_eq!(parsed.patterns[0].pattern, DEFAULT_UFSCUSTOM_PATTERN);
+            assert_eq!(parsed.patterns[0].sample_lines, bundle.patterns[0].sample_lines);
+        }
+    }
+
+    #[test]
+    fn test_validate_bundled_checks_sample_lines() {
+        let mut pattern = sample_bundle().patterns.remove(0);
+        assert!(validate_bundled(&pattern).is_ok());
+
+        pattern.sample_lines.push("not,a,custom,line".to_string());
+        assert!(validate_bundled(&pattern).is_err());
+
+        pattern.sample_lines.clear();
+        pattern.pattern = r"^(?P<opcode>0x[0-9a-f]+)$".to_string();
+        assert!(validate_bundled(&pattern).is_err());
+    }
+
+    #[test]
+    fn test_split_duplicates_rejects_repeated_names() {
+        let first = sample_bundle().patterns.remove(0);
+        let mut repeated = first.clone();
+        repeated.pattern = r"^(?P<opcode>0x[0-9a-f]+);(?P<lba>\d+);(?P<size>\d+);(?P<start_time>[\d.]+);(?P<end_time>[\d.]+)$".to_string();
+        // 이름이 같아도 타입이 다르면 별개의 패턴
+        let mut other_type = first.clone();
+        other_type.pattern_type = "block".to_string();
+
+        let (unique, rejected) = split_duplicates(vec![first.clone(), repeated, other_type]);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].pattern, first.pattern);
+        assert_eq!(unique[1].pattern_type, "block");
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(rejected[0].name, "vendor csv");
+        assert_eq!(rejected[0].pattern_type, "ufscustom");
+    }
+
+    #[test]
+    fn test_conflict_action_parse() {
+        assert_eq!(ConflictAction::parse(None).unwrap(), ConflictAction::Abort);
+        assert_eq!(ConflictAction::parse(Some("rename")).unwrap(), ConflictAction::Rename);
+        assert!(ConflictAction::parse(Some("merge")).is_err());
+    }
+}
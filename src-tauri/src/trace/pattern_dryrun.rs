@@ -0,0 +1,399 @@
+// src-tauri/src/trace/pattern_dryrun.rs
+//
+// 활성 패턴으로 바꾸기 전에 후보 정규식을 실제 로그 파일 일부에 적용해 보는 dry-run
+// 파일 앞부분 N MB 또는 파일 전체에서 무작위로 고른 구간을 대상으로 한다.
+
+use chardetng::EncodingDetector;
+use once_cell::sync::Lazy;
+use rand::{Rng, SeedableRng};
+use regex::{Captures, Regex};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+use super::parser_highperf::keyword_prefilter;
+use super::pattern_schema;
+use super::utils::{
+    parse_block_trace_with_caps, parse_ufs_trace_with_caps, parse_ufscustom_trace_with_caps,
+};
+
+const DEFAULT_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const DEFAULT_PREVIEW_ROWS: usize = 20;
+// 무작위 샘플링 시 한 번에 읽는 구간 크기
+const SAMPLE_WINDOW_BYTES: u64 = 256 * 1024;
+// 거부 사유 버킷당 보관하는 예시 라인 수
+const MAX_EXAMPLES_PER_BUCKET: usize = 3;
+
+// ftrace 라인의 이벤트 이름 ("123.456: block_rq_issue:")
+static EVENT_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\d+\.\d+:\s+([A-Za-z0-9_]+):").unwrap());
+
+#[derive(Debug, Serialize)]
+pub struct PrefilterRejection {
+    pub key: String,
+    pub count: usize,
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatternDryRunResult {
+    pub pattern_type: String,
+    pub sampled: bool,
+    pub bytes_scanned: u64,
+    pub file_size: u64,
+    pub total_lines: usize,
+    // 키워드 prefilter를 통과한 라인 수
+    pub prefilter_accepted: usize,
+    // 정규식이 매치된 라인 수
+    pub matched: usize,
+    // parse_*_trace_with_caps까지 성공한 라인 수
+    pub parsed: usize,
+    // parsed / prefilter_accepted (%)
+    pub match_rate: f64,
+    // 필드 이름 -> 값이 없거나 변환에 실패한 라인 수
+    pub field_failures: BTreeMap<String, usize>,
+    // prefilter는 통과했지만 정규식이 거부한 라인 (이벤트 이름별, 많은 순)
+    pub prefilter_rejections: Vec<PrefilterRejection>,
+    // 파싱 결과 미리보기 (UFS / Block / UFSCUSTOM 행)
+    pub preview: Vec<serde_json::Value>,
+}
+
+// 거부된 라인을 묶는 키 - ftrace 이벤트 이름, CSV는 필드 수
+fn rejection_key(pattern_type: &str, line: &str) -> String {
+    if let Some(caps) = EVENT_NAME.captures(line) {
+        return caps[1].to_string();
+    }
+    if pattern_type == "ufscustom" {
+        return format!("{} fields", line.split(',').count());
+    }
+    "(unknown)".to_string()
+}
+
+// 바이트를 인코딩 감지 후 문자열로 변환
+fn decode(bytes: &[u8]) -> String {
+    let mut detector = EncodingDetector::new();
+    detector.feed(&bytes[..bytes.len().min(8192)], false);
+    let encoding = detector.guess(None, true);
+    let (text, _, _) = encoding.decode(bytes);
+    text.into_owned()
+}
+
+// 파일 앞부분 max_bytes 읽기 (마지막 잘린 라인 제외)
+pub(crate) fn read_head(file: &mut File, max_bytes: u64) -> Result<(Vec<String>, u64), String> {
+    let mut buffer = Vec::new();
+    file.take(max_bytes)
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+    let bytes_read = buffer.len() as u64;
+
+    let text = decode(&buffer);
+    let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+    if bytes_read == max_bytes && !text.ends_with('\n') {
+        lines.pop();
+    }
+    Ok((lines, bytes_read))
+}
+
+// start부터 최대 len 바이트 구간의 완전한 라인만 읽기
+// start 직전 바이트까지 함께 읽어, 구간이 라인 시작에 걸친 경우 첫 라인을 버리지 않음
+// 마지막 라인은 구간이 라인 중간에서 끝난 경우에만 제외 (파일 끝이면 유지)
+fn read_window(file: &mut File, start: u64, len: u64, file_size: u64) -> Result<(Vec<String>, u64), String> {
+    let read_from = start.saturating_sub(1);
+    file.seek(SeekFrom::Start(read_from)).map_err(|e| e.to_string())?;
+    let mut buffer = Vec::new();
+    file.by_ref()
+        .take(start + len - read_from)
+        .read_to_end(&mut buffer)
+        .map_err(|e| e.to_string())?;
+    let bytes_read = buffer.len() as u64 - (start - read_from);
+
+    // 앞쪽 잘린 라인 건너뛰기 (직전 바이트가 개행이면 그 개행만)
+    let mut body = &buffer[..];
+    if start > 0 {
+        body = match body.iter().position(|&b| b == b'\n') {
+            Some(pos) => &body[pos + 1..],
+            None => &[],
+        };
+    }
+    // 뒤쪽 잘린 라인 제외
+    let cut_mid_line = !body.ends_with(b"\n") && read_from + (buffer.len() as u64) < file_size;
+    if cut_mid_line {
+        body = match body.iter().rposition(|&b| b == b'\n') {
+            Some(pos) => &body[..pos + 1],
+            None => &[],
+        };
+    }
+
+    let lines = decode(body).lines().map(str::to_string).collect();
+    Ok((lines, bytes_read))
+}
+
+// 파일 전체를 window_bytes 구간 수만큼 같은 크기의 조각으로 나누고,
+// 조각마다 무작위 위치의 구간 하나를 읽음 (구간끼리 겹치지 않음)
+fn read_sample(
+    file: &mut File,
+    file_size: u64,
+    max_bytes: u64,
+    window_bytes: u64,
+) -> Result<(Vec<String>, u64), String> {
+    if file_size <= max_bytes {
+        return read_head(file, max_bytes);
+    }
+
+    let window_count = max_bytes.div_ceil(window_bytes).max(1);
+    let slice = file_size / window_count;
+    let window = window_bytes.min(slice);
+    // 고정 시드로 같은 파일에 대해 재현 가능한 결과
+    let mut rng = rand::rngs::StdRng::seed_from_u64(12345);
+
+    let mut lines = Vec::new();
+    let mut bytes_read = 0;
+    for index in 0..window_count {
+        let slice_start = index * slice;
+        let offset = slice_start + rng.random_range(0..=slice - window);
+        let (window_lines, window_read) = read_window(file, offset, window, file_size)?;
+        bytes_read += window_read;
+        lines.extend(window_lines);
+    }
+
+    Ok((lines, bytes_read))
+}
+
+// 타입별 parse_*_trace_with_caps 실행 후 미리보기용 JSON으로 변환
+fn parse_row(pattern_type: &str, caps: &Captures) -> Option<serde_json::Value> {
+    let row = match pattern_type {
+        "ufs" => parse_ufs_trace_with_caps(caps).ok().map(serde_json::to_value),
+        "block" => parse_block_trace_with_caps(caps).ok().map(serde_json::to_value),
+        "ufscustom" => parse_ufscustom_trace_with_caps(caps).ok().map(serde_json::to_value),
+        _ => None,
+    }?;
+    row.ok()
+}
+
+/// 후보 패턴을 라인 목록에 적용하여 통계 계산
+pub fn dry_run_lines(
+    pattern_type: &str,
+    regex: &Regex,
+    lines: &[String],
+    preview_rows: usize,
+) -> Result<PatternDryRunResult, String> {
+    let schema = pattern_schema::schema_for(pattern_type)?;
+
+    let mut prefilter_accepted = 0;
+    let mut matched = 0;
+    let mut parsed = 0;
+    let mut field_failures: BTreeMap<String, usize> = BTreeMap::new();
+    let mut rejections: HashMap<String, PrefilterRejection> = HashMap::new();
+    let mut preview = Vec::new();
+
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || !keyword_prefilter(pattern_type, line) {
+            continue;
+        }
+        prefilter_accepted += 1;
+
+        let Some(caps) = regex.captures(line) else {
+            let key = rejection_key(pattern_type, line);
+            let bucket = rejections.entry(key.clone()).or_insert_with(|| PrefilterRejection {
+                key,
+                count: 0,
+                examples: Vec::new(),
+            });
+            bucket.count += 1;
+            if bucket.examples.len() < MAX_EXAMPLES_PER_BUCKET {
+                bucket.examples.push(line.to_string());
+            }
+            continue;
+        };
+        matched += 1;
+
+        for field in pattern_schema::failed_required_fields(schema, &caps) {
+            *field_failures.entry(field).or_insert(0) += 1;
+        }
+
+        if let Some(row) = parse_row(pattern_type, &caps) {
+            parsed += 1;
+            if preview.len() < preview_rows {
+                preview.push(row);
+            }
+        }
+    }
+
+    let mut prefilter_rejections: Vec<PrefilterRejection> = rejections.into_values().collect();
+    prefilter_rejections.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+
+    let match_rate = if prefilter_accepted > 0 {
+        parsed as f64 / prefilter_accepted as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    Ok(PatternDryRunResult {
+        pattern_type: pattern_type.to_string(),
+        sampled: false,
+        bytes_scanned: 0,
+        file_size: 0,
+        total_lines: lines.len(),
+        prefilter_accepted,
+        matched,
+        parsed,
+        match_rate,
+        field_failures,
+        prefilter_rejections,
+        preview,
+    })
+}
+
+/// 로그 파일에 후보 패턴 dry-run
+/// max_bytes: 읽을 최대 바이트 (기본 16MB), sample: true면 파일 전체에서 무작위 구간 샘플링
+pub fn dry_run_pattern(
+    path: String,
+    pattern: String,
+    pattern_type: String,
+    max_bytes: Option<u64>,
+    sample: Option<bool>,
+    preview_rows: Option<usize>,
+) -> Result<String, String> {
+    let regex = Regex::new(&pattern).map_err(|e| format!("Invalid regex pattern: {}", e))?;
+    // 필수 named group이 없으면 parse_*_trace_with_caps가 동작하지 않으므로 먼저 확인
+    pattern_schema::validate_pattern(&pattern_type, &regex)?;
+
+    let mut file = File::open(&path).map_err(|e| format!("로그 파일 열기 실패: {}", e))?;
+    let file_size = file.metadata().map_err(|e| e.to_string())?.len();
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_BYTES).max(1);
+    let sampled = sample.unwrap_or(false);
+
+    let (lines, bytes_scanned) = if sampled {
+        read_sample(&mut file, file_size, max_bytes, SAMPLE_WINDOW_BYTES)?
+    } else {
+        read_head(&mut file, max_bytes)?
+    };
+
+    let mut result = dry_run_lines(
+        &pattern_type,
+        &regex,
+        &lines,
+        preview_rows.unwrap_or(DEFAULT_PREVIEW_ROWS),
+    )?;
+    result.sampled = sampled;
+    result.bytes_scanned = bytes_scanned;
+    result.file_size = file_size;
+
+    println!(
+        "🧪 패턴 dry-run: {} 라인 중 후보 {}개, 매치 {}개, 파싱 {}개 ({:.1}%)",
+        result.total_lines, result.prefilter_accepted, result.matched, result.parsed, result.match_rate
+    );
+
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::patterns::DEFAULT_BLOCK_PATTERN;
+
+    #[test]
+    fn test_dry_run_lines_counts_and_rejections() {
+        let regex = Regex::new(DEFAULT_BLOCK_PATTERN).unwrap();
+        let lines: Vec<String> = [
+            "kworker/u16:0-123 [000] d..1. 100.000001: block_rq_issue: 8,0 R 4096 () 2048 + 8 [kworker/u16:0]",
+            "kworker/u16:0-123 [000] d..1. 100.000101: block_rq_complete: 8,0 R () 2048 + 8 [0]",
+            "kworker/u16:0-123 [000] d..1. 100.000201: block_rq_insert: 8,0 R 4096 () 2048 + 8 [kworker/u16:0]",
+            // 매치되지만 size가 u32 범위를 넘어 변환 실패
+            "kworker/u16:0-123 [000] d..1. 100.000251: block_rq_issue: 8,0 W 4096 () 4096 + 99999999999 [kworker/u16:0]",
+            "kworker/u16:0-123 [000] d..1. 100.000301: block_bio_queue: 8,0 R 2048 + 8",
+            "kworker/u16:0-123 [000] d..1. 100.000351: block_bio_queue: 8,0 W 4096 + 8",
+            // "()" 없이 바이트 수가 붙은 block_rq_issue는 정규식이 거부 (bio 이벤트 형식만 "()" 생략 허용)
+            "kworker/u16:0-123 [000] d..1. 100.000371: block_rq_issue: 8,0 R 4096 2048 + 8 [x]",
+            "kworker/u16:0-123 [000] d..1. 100.000401: sched_switch: prev_comm=foo",
+            "",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let result = dry_run_lines("block", &regex, &lines, 2).unwrap();
+        assert_eq!(result.total_lines, 9);
+        assert_eq!(result.prefilter_accepted, 7);
+        assert_eq!(result.matched, 4);
+        assert_eq!(result.parsed, 3);
+        assert!((result.match_rate - 3.0 / 7.0 * 100.0).abs() < 1e-9);
+        assert_eq!(result.field_failures, BTreeMap::from([("size".to_string(), 1)]));
+        assert_eq!(result.preview.len(), 2);
+
+        let histogram: Vec<(&str, usize, usize)> = result
+            .prefilter_rejections
+            .iter()
+            .map(|r| (r.key.as_str(), r.count, r.examples.len()))
+            .collect();
+        assert_eq!(histogram, vec![("block_bio_queue", 2, 2), ("block_rq_issue", 1, 1)]);
+    }
+
+    // 고정 길이 라인 ("line000000\n", 11바이트) 파일
+    fn fixture_file(name: &str, lines: usize, trailing_newline: bool) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("estrace_{}_{}.txt", name, std::process::id()));
+        let mut content: String = (0..lines).map(|i| format!("line{:06}\n", i)).collect();
+        if !trailing_newline {
+            content.pop();
+        }
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_window_keeps_complete_lines() {
+        let path = fixture_file("window", 10, false);
+        let mut file = File::open(&path).unwrap();
+        let size = file.metadata().unwrap().len();
+
+        // 라인 경계에서 시작 / 끝나는 구간은 첫 줄과 마지막 줄 모두 유지
+        let (lines, _) = read_window(&mut file, 11, 22, size).unwrap();
+        assert_eq!(lines, vec!["line000001", "line000002"]);
+        // 라인 중간에서 시작 / 끝나는 구간은 잘린 줄만 제외
+        let (lines, bytes) = read_window(&mut file, 16, 30, size).unwrap();
+        assert_eq!(lines, vec!["line000002", "line000003"]);
+        assert_eq!(bytes, 30);
+        // 파일 끝 (개행 없음)에 닿는 구간은 마지막 줄 유지
+        let (lines, _) = read_window(&mut file, 88, size - 88, size).unwrap();
+        assert_eq!(lines, vec!["line000008", "line000009"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_sample_windows_do_not_overlap() {
+        let path = fixture_file("sample", 10_000, true);
+        let mut file = File::open(&path).unwrap();
+        let size = file.metadata().unwrap().len();
+
+        // 110,000바이트를 10개 조각(11,000바이트)으로 나누고 조각마다 1,100바이트 구간
+        let (lines, bytes) = read_sample(&mut file, size, 11_000, 1_100).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(bytes, 11_000);
+        let numbers: Vec<usize> = lines
+            .iter()
+            .map(|line| {
+                assert_eq!(line.len(), 10, "잘린 라인: {}", line);
+                line["line".len()..].parse().unwrap()
+            })
+            .collect();
+        // 구간이 겹치지 않으므로 중복 없이 증가, 각 조각에서 잘린 줄을 빼고 99~100줄
+        assert!(numbers.windows(2).all(|w| w[0] < w[1]));
+        for slice in 0..10 {
+            let count = numbers.iter().filter(|&&n| n / 1_000 == slice).count();
+            assert!((99..=100).contains(&count), "조각 {}: {}줄", slice, count);
+        }
+    }
+
+    #[test]
+    fn test_rejection_key() {
+        assert_eq!(
+            rejection_key("ufs", "foo [1] 12.5: ufshcd_upiu: send"),
+            "ufshcd_upiu"
+        );
+        assert_eq!(rejection_key("ufscustom", "a,b,c,d,e,f"), "6 fields");
+    }
+}
@@ -0,0 +1,309 @@
+// src-tauri/src/trace/pattern_generator.rs
+//
+// 샘플 라인 하나와 사용자가 지정한 필드 구간(span)으로 named group 정규식 생성
+// 구간 사이의 텍스트는 숫자/공백을 일반화하고 단어와 구두점은 그대로 유지한다.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+
+use super::pattern_dryrun::{self, PatternDryRunResult};
+use super::pattern_schema::{self, FieldKind, SchemaCheck, TraceSchema};
+
+// 생성 결과 검사에 사용할 로그 파일 기본 크기
+const DEFAULT_CHECK_BYTES: u64 = 4 * 1024 * 1024;
+
+// 샘플 라인에서 필드로 지정한 구간 (문자 단위 offset, end 미포함)
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldSpan {
+    pub field: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedPattern {
+    pub pattern: String,
+    pub schema: SchemaCheck,
+    // 샘플 라인에서 값이 없거나 변환에 실패한 필수 필드
+    pub sample_failed_fields: Vec<String>,
+    // 같은 파일의 다른 라인에 적용한 결과 (path가 주어진 경우)
+    pub file_check: Option<PatternDryRunResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PatternFields {
+    pub required: Vec<String>,
+    pub optional: Vec<String>,
+}
+
+/// 트레이스 타입의 필수/선택 필드 이름
+pub fn pattern_fields(pattern_type: &str) -> Result<PatternFields, String> {
+    let schema = pattern_schema::schema_for(pattern_type)?;
+    let names = |specs: &[pattern_schema::FieldSpec]| -> Vec<String> {
+        specs.iter().map(|s| s.name.to_string()).collect()
+    };
+    Ok(PatternFields {
+        required: names(schema.required),
+        optional: names(schema.optional),
+    })
+}
+
+fn field_kind(schema: &TraceSchema, field: &str) -> Option<FieldKind> {
+    schema
+        .required
+        .iter()
+        .chain(schema.optional.iter())
+        .find(|spec| spec.name == field)
+        .map(|spec| spec.kind)
+}
+
+// 필드 값에 대한 정규식 조각
+fn field_regex(kind: Option<FieldKind>, value: &str) -> &'static str {
+    match kind {
+        Some(FieldKind::Float) => r"\d+(?:\.\d+)?",
+        Some(FieldKind::UInt32) | Some(FieldKind::UInt64) | Some(FieldKind::Sector) => r"\d+",
+        Some(FieldKind::Int32) => r"-?\d+",
+        Some(FieldKind::Hex32) => r"[0-9a-fA-F]+",
+        _ if value.starts_with("0x") => r"0x[0-9a-fA-F]+",
+        _ if value.chars().any(char::is_whitespace) => r".*?",
+        _ => r"\S+",
+    }
+}
+
+// 구간 사이 텍스트 일반화: 숫자 -> \d+, 공백 -> \s+, 나머지는 escape
+fn generalize_literal(literal: &str) -> String {
+    let mut result = String::new();
+    let mut chars = literal.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            while chars.peek().is_some_and(|n| n.is_ascii_digit()) {
+                chars.next();
+            }
+            result.push_str(r"\d+");
+        } else if c.is_whitespace() {
+            while chars.peek().is_some_and(|n| n.is_whitespace()) {
+                chars.next();
+            }
+            result.push_str(r"\s+");
+        } else {
+            result.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+
+    result
+}
+
+// 첫 필드 앞의 텍스트: 마지막 구분자(공백/구두점)만 유지하고 앞부분은 .*?로 대체
+fn generalize_prefix(prefix: &str) -> String {
+    let delimiter_start = prefix
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| !c.is_alphanumeric())
+        .last()
+        .map_or(prefix.len(), |(i, _)| i);
+
+    let (head, delimiter) = prefix.split_at(delimiter_start);
+    let head = if head.is_empty() { "" } else { ".*?" };
+    format!(r"^\s*{}{}", head, generalize_literal(delimiter.trim_start()))
+}
+
+/// 샘플 라인과 필드 구간으로 정규식 문자열 생성
+pub fn build_regex(pattern_type: &str, sample_line: &str, spans: &[FieldSpan]) -> Result<String, String> {
+    if spans.is_empty() {
+        return Err("필드 구간을 하나 이상 지정해주세요".to_string());
+    }
+    let schema = pattern_schema::schema_for(pattern_type)?;
+
+    // 문자 offset -> byte offset
+    let char_count = sample_line.chars().count();
+    let byte_offset = |char_index: usize| {
+        sample_line
+            .char_indices()
+            .nth(char_index)
+            .map_or(sample_line.len(), |(i, _)| i)
+    };
+
+    let mut spans = spans.to_vec();
+    spans.sort_by_key(|span| span.start);
+
+    let mut pattern = String::new();
+    let mut cursor = 0;
+    for (index, span) in spans.iter().enumerate() {
+        if span.start >= span.end || span.end > char_count {
+            return Err(format!("'{}' 필드 구간이 잘못되었습니다", span.field));
+        }
+        if span.start < cursor {
+            return Err(format!("'{}' 필드 구간이 다른 필드와 겹칩니다", span.field));
+        }
+        if !span.field.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            || span.field.is_empty()
+        {
+            return Err(format!("잘못된 필드 이름: '{}'", span.field));
+        }
+        if spans[..index].iter().any(|other| other.field == span.field) {
+            return Err(format!("'{}' 필드가 두 번 지정되었습니다", span.field));
+        }
+
+        let kind = field_kind(schema, &span.field);
+        // 16진수 필드의 0x 접두사는 그룹 밖에서 선택적으로 매치 (값은 16진수 숫자만 캡처)
+        // 0x 없이 숫자만 지정한 경우 앞 텍스트의 0x도 접두사로 처리
+        let hex = kind == Some(FieldKind::Hex32);
+        let mut literal = &sample_line[byte_offset(cursor)..byte_offset(span.start)];
+        if hex {
+            literal = literal.strip_suffix("0x").unwrap_or(literal);
+        }
+        if index == 0 {
+            pattern.push_str(&generalize_prefix(literal));
+        } else {
+            pattern.push_str(&generalize_literal(literal));
+        }
+
+        let value = &sample_line[byte_offset(span.start)..byte_offset(span.end)];
+        let prefix = if hex { "(?:0x)?" } else { "" };
+        pattern.push_str(&format!(
+            "{}(?P<{}>{})",
+            prefix,
+            span.field,
+            field_regex(kind, value)
+        ));
+        cursor = span.end;
+    }
+
+    // 마지막 필드 뒤 텍스트가 lazy 그룹으로 끝나면 다음 구분자까지는 유지해야 값이 잘리지 않음
+    if pattern.ends_with(r".*?)") {
+        let rest = &sample_line[byte_offset(cursor)..];
+        let delimiter: String = rest.chars().take_while(|c| !c.is_alphanumeric()).collect();
+        pattern.push_str(&generalize_literal(&delimiter));
+    }
+
+    Ok(pattern)
+}
+
+/// 정규식 생성 후 스키마, 샘플 라인, (선택) 같은 파일의 다른 라인으로 검사
+pub fn generate_pattern(
+    pattern_type: String,
+    sample_line: String,
+    spans: Vec<FieldSpan>,
+    path: Option<String>,
+    max_bytes: Option<u64>,
+) -> Result<String, String> {
+    let pattern = build_regex(&pattern_type, &sample_line, &spans)?;
+    let regex = Regex::new(&pattern).map_err(|e| format!("생성된 정규식 컴파일 실패: {}", e))?;
+
+    let schema = pattern_schema::schema_for(&pattern_type)?;
+    let schema_check = pattern_schema::check_capture_names(schema, &regex);
+
+    let caps = regex
+        .captures(&sample_line)
+        .ok_or("생성된 정규식이 샘플 라인과 매치되지 않습니다")?;
+    let sample_failed_fields = pattern_schema::failed_required_fields(schema, &caps);
+
+    // 필수 필드가 모두 있을 때만 파일 검사 (parse_*_trace_with_caps 사용)
+    let file_check = match path {
+        Some(path) if schema_check.is_valid() => {
+            let mut file = File::open(&path).map_err(|e| format!("로그 파일 열기 실패: {}", e))?;
+            let (lines, _) =
+                pattern_dryrun::read_head(&mut file, max_bytes.unwrap_or(DEFAULT_CHECK_BYTES))?;
+            Some(pattern_dryrun::dry_run_lines(&pattern_type, &regex, &lines, 5)?)
+        }
+        _ => None,
+    };
+
+    let result = GeneratedPattern {
+        pattern,
+        schema: schema_check,
+        sample_failed_fields,
+        file_check,
+    };
+    serde_json::to_string(&result).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(line: &str, field: &str, text: &str) -> FieldSpan {
+        let byte_start = line.rfind(text).unwrap();
+        let start = line[..byte_start].chars().count();
+        FieldSpan {
+            field: field.to_string(),
+            start,
+            end: start + text.chars().count(),
+        }
+    }
+
+    #[test]
+    fn test_build_regex_matches_other_lines() {
+        let line = "0x28,1048576,8,123.456,123.789";
+        let spans = vec![
+            span(line, "opcode", "0x28"),
+            span(line, "lba", "1048576"),
+            FieldSpan { field: "size".to_string(), start: 13, end: 14 },
+            span(line, "start_time", "123.456"),
+            span(line, "end_time", "123.789"),
+        ];
+
+        let pattern = build_regex("ufscustom", line, &spans).unwrap();
+        let regex = Regex::new(&pattern).unwrap();
+        let caps = regex.captures("0x2a,77,16,200.5,200.75").unwrap();
+        assert_eq!(&caps["opcode"], "0x2a");
+        assert_eq!(&caps["lba"], "77");
+        assert_eq!(&caps["size"], "16");
+        assert_eq!(&caps["end_time"], "200.75");
+    }
+
+    #[test]
+    fn test_build_regex_generalizes_prefix_and_numbers() {
+        let line = "kworker/u16:3-811 [007] d..1. 123.456789: ufshcd_command: send_req: tag: 5";
+        let spans = vec![
+            span(line, "cpu", "007"),
+            span(line, "time", "123.456789"),
+            span(line, "command", "send_req"),
+            span(line, "tag", "5"),
+        ];
+
+        let pattern = build_regex("ufs", line, &spans).unwrap();
+        let regex = Regex::new(&pattern).unwrap();
+        let other = "  binder:1234_5-99 [2] d..2. 9.5: ufshcd_command: complete_rsp: tag: 31";
+        let caps = regex.captures(other).unwrap();
+        assert_eq!(&caps["cpu"], "2");
+        assert_eq!(&caps["command"], "complete_rsp");
+        assert_eq!(&caps["tag"], "31");
+    }
+
+    #[test]
+    fn test_build_regex_hex_group_id_prefix() {
+        let line = "kworker/u16:3-811 [007] d..1. 123.456789: ufshcd_command: send_req: 1d84000.ufshc: tag: 5 size: 4096 LBA: 1048576 opcode: 0x28 group_id: 0x0b hwq_id: 3";
+        let other = "kworker/u16:3-811 [001] d..1. 130.5: ufshcd_command: complete_rsp: 1d84000.ufshc: tag: 17 size: 8192 LBA: 2048 opcode: 0x2a group_id: 0x1f hwq_id: 2";
+
+        // 0x를 포함해 지정하든 숫자만 지정하든 group_id에는 16진수 숫자만 캡처
+        for group_id in ["0x0b", "0b"] {
+            let spans = vec![
+                span(line, "time", "123.456789"),
+                span(line, "command", "send_req"),
+                span(line, "opcode", "0x28"),
+                span(line, "group_id", group_id),
+                span(line, "hwq_id", "3"),
+            ];
+            let pattern = build_regex("ufs", line, &spans).unwrap();
+            assert!(pattern.contains(r"group_id:\s+(?:0x)?(?P<group_id>[0-9a-fA-F]+)"));
+            let regex = Regex::new(&pattern).unwrap();
+            assert_eq!(&regex.captures(line).unwrap()["group_id"], "0b");
+            let caps = regex.captures(other).unwrap();
+            assert_eq!(&caps["group_id"], "1f");
+            assert_eq!(&caps["hwq_id"], "2");
+            assert!(pattern_schema::convert_ok(FieldKind::Hex32, &caps["group_id"]));
+        }
+    }
+
+    #[test]
+    fn test_build_regex_rejects_overlapping_spans() {
+        let spans = vec![
+            FieldSpan { field: "lba".to_string(), start: 0, end: 4 },
+            FieldSpan { field: "size".to_string(), start: 2, end: 6 },
+        ];
+        assert!(build_regex("ufscustom", "123456789", &spans).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+// src-tauri/src/trace/pattern_schema.rs
+//
+// 트레이스 타입별로 정규식이 제공해야 하는 named capture 목록과
+// 각 필드의 변환 규칙 (parse_*_trace_with_caps와 동일한 규칙) 정의
+
+use regex::{Captures, Regex};
+use serde::Serialize;
+
+use crate::trace::utils::parse_hex_u32;
+
+// 필드 값 변환 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Float,
+    UInt32,
+    UInt64,
+    Int32,
+    // 16진수, 0x 접두사 선택 (group_id)
+    Hex32,
+    // u64, 단 최대값(18446744073709551615)은 0으로 처리되는 sector
+    Sector,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    pub kind: FieldKind,
+}
+
+#[derive(Debug)]
+pub struct TraceSchema {
+    pub pattern_type: &'static str,
+    pub required: &'static [FieldSpec],
+    pub optional: &'static [FieldSpec],
+}
+
+const fn field(name: &'static str, kind: FieldKind) -> FieldSpec {
+    FieldSpec { name, kind }
+}
+
+pub static UFS_SCHEMA: TraceSchema = TraceSchema {
+    pattern_type: "ufs",
+    required: &[
+        field("time", FieldKind::Float),
+        field("cpu", FieldKind::UInt32),
+        field("command", FieldKind::Text),
+        field("tag", FieldKind::UInt32),
+        field("size", FieldKind::Int32),
+        field("lba", FieldKind::UInt64),
+        field("opcode", FieldKind::Text),
+        field("group_id", FieldKind::Hex32),
+        field("hwq_id", FieldKind::UInt32),
+    ],
+    optional: &[field("process", FieldKind::Text)],
+};
+
+pub static BLOCK_SCHEMA: TraceSchema = TraceSchema {
+    pattern_type: "block",
+    required: &[
+        field("time", FieldKind::Float),
+        field("cpu", FieldKind::UInt32),
+        field("action", FieldKind::Text),
+        field("devmajor", FieldKind::UInt32),
+        field("devminor", FieldKind::UInt32),
+        field("io_type", FieldKind::Text),
+        field("sector", FieldKind::Sector),
+        field("size", FieldKind::UInt32),
+    ],
+    optional: &[
+        field("process", FieldKind::Text),
+        field("flags", FieldKind::Text),
+        field("extra", FieldKind::UInt32),
+        field("comm", FieldKind::Text),
+    ],
+};
+
+pub static UFSCUSTOM_SCHEMA: TraceSchema = TraceSchema {
+    pattern_type: "ufscustom",
+    required: &[
+        field("opcode", FieldKind::Text),
+        field("lba", FieldKind::UInt64),
+        field("size", FieldKind::UInt32),
+        field("start_time", FieldKind::Float),
+        field("end_time", FieldKind::Float),
+    ],
+    optional: &[],
+};
+
+pub fn schema_for(pattern_type: &str) -> Result<&'static TraceSchema, String> {
+    match pattern_type {
+        "ufs" => Ok(&UFS_SCHEMA),
+        "block" => Ok(&BLOCK_SCHEMA),
+        "ufscustom" => Ok(&UFSCUSTOM_SCHEMA),
+        _ => Err(format!("Unsupported pattern type: {}", pattern_type)),
+    }
+}
+
+// 정규식의 capture 이름 검사 결과
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct SchemaCheck {
+    pub missing_required: Vec<String>,
+    pub missing_optional: Vec<String>,
+}
+
+impl SchemaCheck {
+    pub fn is_valid(&self) -> bool {
+        self.missing_required.is_empty()
+    }
+}
+
+/// 정규식에 스키마의 named capture가 모두 있는지 검사
+pub fn check_capture_names(schema: &TraceSchema, regex: &Regex) -> SchemaCheck {
+    let names: Vec<&str> = regex.capture_names().flatten().collect();
+    let missing = |specs: &[FieldSpec]| -> Vec<String> {
+        specs
+            .iter()
+            .filter(|spec| !names.contains(&spec.name))
+            .map(|spec| spec.name.to_string())
+            .collect()
+    };
+
+    SchemaCheck {
+        missing_required: missing(schema.required),
+        missing_optional: missing(schema.optional),
+    }
+}
+
+/// 패턴 등록/활성화 전 검증 - 필수 capture가 없으면 Err
+pub fn validate_pattern(pattern_type: &str, regex: &Regex) -> Result<SchemaCheck, String> {
+    let schema = schema_for(pattern_type)?;
+    let check = check_capture_names(schema, regex);
+
+    if !check.is_valid() {
+        return Err(format!(
+            "{} 패턴에 필수 named group이 없습니다: {}",
+            pattern_type,
+            check.missing_required.join(", ")
+        ));
+    }
+
+    if !check.missing_optional.is_empty() {
+        println!(
+            "⚠️  {} 패턴에 선택 named group 없음 (기본값 사용): {}",
+            pattern_type,
+            check.missing_optional.join(", ")
+        );
+    }
+
+    Ok(check)
+}
+
+/// 필드 값이 parse_*_trace_with_caps와 같은 규칙으로 변환되는지 확인
+pub fn convert_ok(kind: FieldKind, value: &str) -> bool {
+    match kind {
+        FieldKind::Text => true,
+        FieldKind::Float => value.parse::<f64>().is_ok(),
+        FieldKind::UInt32 => value.parse::<u32>().is_ok(),
+        FieldKind::UInt64 => value.parse::<u64>().is_ok(),
+        FieldKind::Int32 => value.parse::<i32>().is_ok(),
+        FieldKind::Hex32 => parse_hex_u32(value).is_some(),
+        FieldKind::Sector => value == "18446744073709551615" || value.parse::<u64>().is_ok(),
+    }
+}
+
+/// 매칭된 라인에서 필수 필드 중 값이 없거나 변환에 실패한 필드 목록
+pub fn failed_required_fields(schema: &TraceSchema, caps: &Captures) -> Vec<String> {
+    schema
+        .required
+        .iter()
+        .filter(|spec| match caps.name(spec.name) {
+            Some(m) => !convert_ok(spec.kind, m.as_str()),
+            None => true,
+        })
+        .map(|spec| spec.name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::patterns::{DEFAULT_BLOCK_PATTERN, DEFAULT_UFSCUSTOM_PATTERN, DEFAULT_UFS_PATTERN};
+
+    #[test]
+    fn test_default_patterns_satisfy_schema() {
+        for (pattern_type, pattern) in [
+            ("ufs", DEFAULT_UFS_PATTERN),
+            ("block", DEFAULT_BLOCK_PATTERN),
+            ("ufscustom", DEFAULT_UFSCUSTOM_PATTERN),
+        ] {
+            let regex = Regex::new(pattern).unwrap();
+            assert!(validate_pattern(pattern_type, &regex).is_ok(), "{}", pattern_type);
+        }
+    }
+
+    #[test]
+    fn test_positional_pattern_is_rejected() {
+        let regex = Regex::new(r"^(\d+\.\d+): ufshcd_command: (send_req|complete_rsp)").unwrap();
+        let check = check_capture_names(&UFS_SCHEMA, &regex);
+        assert!(!check.is_valid());
+        assert!(check.missing_required.contains(&"time".to_string()));
+    }
+
+    #[test]
+    fn test_failed_required_fields() {
+        let regex = Regex::new(
+            r"^(?P<opcode>0x[0-9a-f]+),(?P<lba>\S+),(?P<size>\d+),(?P<start_time>\S+),(?P<end_time>\S+)$",
+        )
+        .unwrap();
+        let caps = regex.captures("0x28,abc,8,1.0,x").unwrap();
+        assert_eq!(
+            failed_required_fields(&UFSCUSTOM_SCHEMA, &caps),
+            vec!["lba".to_string(), "end_time".to_string()]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// src-tauri/src/trace/pattern_store.rs
+//
+// 패턴 레지스트리(UFS_PATTERNS, BLOCK_PATTERNS, UFSCUSTOM_PATTERNS, ACTIVE_*)를 앱 설정 폴더의
+// JSON 파일로 저장/복원한다. 앱 시작 시 load, 패턴 변경 시 save.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::patterns::{registry, PATTERN_TYPES};
+
+// 저장 파일 포맷 버전 (포맷이 바뀌면 증가)
+// v2: 타입별 활성 패턴이 순서 있는 목록으로 변경
+// v3: 기본 패턴도 저장 파일 기준으로 관리 (삭제한 기본 패턴을 다시 추가하지 않음)
+pub const PATTERN_STORE_VERSION: u32 = 3;
+
+// 이 버전부터 저장 파일에 기본 패턴 seed 결과가 반영되어 있음
+const DEFAULTS_SEEDED_VERSION: u32 = 3;
+
+const APP_IDENTIFIER: &str = "com.estrace.app";
+const PATTERN_STORE_FILE: &str = "patterns.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPattern {
+    pub name: String,
+    pub pattern_type: String,
+    pub pattern: String,
+}
+
+// 타입별 활성 패턴 - v1 파일은 이름 하나, v2부터는 시도 순서대로 나열된 목록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ActivePatterns {
+    Single(String),
+    Ordered(Vec<String>),
+}
+
+impl ActivePatterns {
+    pub fn into_names(self) -> Vec<String> {
+        match self {
+            ActivePatterns::Single(name) => vec![name],
+            ActivePatterns::Ordered(names) => names,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternStore {
+    pub version: u32,
+    pub patterns: Vec<StoredPattern>,
+    // pattern_type -> active pattern names
+    pub active: BTreeMap<String, ActivePatterns>,
+}
+
+/// 패턴 저장 파일 경로 (<config_dir>/com.estrace.app/patterns.json)
+pub fn store_path() -> Result<PathBuf, String> {
+    let config_dir =
+        dirs::config_dir().ok_or_else(|| "설정 폴더를 찾을 수 없습니다".to_string())?;
+    Ok(config_dir.join(APP_IDENTIFIER).join(PATTERN_STORE_FILE))
+}
+
+/// 저장 파일 읽기
+/// 파일이 없으면 Ok(None), 읽기/파싱 실패 시 Err
+pub fn load() -> Result<Option<PatternStore>, String> {
+    let path = store_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("패턴 파일 읽기 실패 ({}): {}", path.display(), e))?;
+    parse_store(&content).map(Some)
+}
+
+/// 읽을 수 없는 저장 파일을 patterns.json.bak으로 옮김
+/// 기본 패턴으로 시작한 뒤 저장할 때 기존 파일을 덮어쓰지 않도록 함
+pub fn backup_store() -> Result<PathBuf, String> {
+    backup_file(&store_path()?)
+}
+
+fn backup_file(path: &Path) -> Result<PathBuf, String> {
+    let backup = path.with_extension("json.bak");
+    fs::rename(path, &backup)
+        .map_err(|e| format!("패턴 파일 백업 실패 ({}): {}", path.display(), e))?;
+    Ok(backup)
+}
+
+pub fn parse_store(content: &str) -> Result<PatternStore, String> {
+    let store: PatternStore =
+        serde_json::from_str(content).map_err(|e| format!("패턴 파일 파싱 실패: {}", e))?;
+
+    if store.version > PATTERN_STORE_VERSION {
+        return Err(format!(
+            "지원하지 않는 패턴 파일 버전: {} (최대 {})",
+            store.version, PATTERN_STORE_VERSION
+        ));
+    }
+
+    Ok(store)
+}
+
+/// 기본 패턴을 레지스트리에 넣어야 하는지 여부
+/// 저장 파일이 없거나 v3 이전 파일(기본 패턴을 매번 다시 넣던 포맷)이면 seed
+pub fn needs_default_seed(store: Option<&PatternStore>) -> bool {
+    store.is_none_or(|store| store.version < DEFAULTS_SEEDED_VERSION)
+}
+
+/// 현재 메모리의 레지스트리 상태를 PatternStore로 수집
+pub fn snapshot() -> Result<PatternStore, String> {
+    let mut patterns = Vec::new();
+    let mut active = BTreeMap::new();
+
+    for pattern_type in PATTERN_TYPES {
+        let (type_patterns, type_active) = registry(pattern_type)?;
+
+        let type_patterns = type_patterns.read().map_err(|e| e.to_string())?;
+        for (name, regex) in type_patterns.iter() {
+            patterns.push(StoredPattern {
+                name: name.clone(),
+                pattern_type: pattern_type.to_string(),
+                pattern: regex.as_str().to_string(),
+            });
+        }
+
+        let type_active = type_active.read().map_err(|e| e.to_string())?;
+        let names = type_active.iter().map(|(name, _)| name.clone()).collect();
+        active.insert(pattern_type.to_string(), ActivePatterns::Ordered(names));
+    }
+
+    // HashMap 순서에 따라 파일 내용이 바뀌지 않도록 정렬
+    patterns.sort_by(|a, b| {
+        (a.pattern_type.as_str(), a.name.as_str()).cmp(&(b.pattern_type.as_str(), b.name.as_str()))
+    });
+
+    Ok(PatternStore {
+        version: PATTERN_STORE_VERSION,
+        patterns,
+        active,
+    })
+}
+
+/// 현재 레지스트리를 파일로 저장
+/// 임시 파일에 먼저 쓰고 rename하여 저장 도중 종료되어도 기존 파일이 깨지지 않도록 함
+pub fn save() -> Result<(), String> {
+    let store = snapshot()?;
+    let path = store_path()?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("설정 폴더 생성 실패: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(&store).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, content).map_err(|e| format!("패턴 파일 쓰기 실패: {}", e))?;
+    fs::rename(&tmp_path, &path).map_err(|e| format!("패턴 파일 교체 실패: {}", e))?;
+
+    Ok(())
+}
+
+/// 패턴 변경 후 호출 - 저장 실패는 메모리 상태에 영향을 주지 않으므로 경고만 출력
+pub fn save_or_warn() {
+    if let Err(e) = save() {
+        println!("⚠️  패턴 저장 실패: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_store_roundtrip() {
+        let mut active = BTreeMap::new();
+        active.insert(
+            "ufs".to_string(),
+            ActivePatterns::Ordered(vec!["custom".to_string(), "fallback".to_string()]),
+        );
+        let store = PatternStore {
+            version: PATTERN_STORE_VERSION,
+            patterns: vec![StoredPattern {
+                name: "custom".to_string(),
+                pattern_type: "ufs".to_string(),
+                pattern: r"^(?P<time>\d+\.\d+)".to_string(),
+            }],
+            active,
+        };
+
+        let content = serde_json::to_string(&store).unwrap();
+        let parsed = parse_store(&content).unwrap();
+        assert_eq!(parsed.patterns.len(), 1);
+        assert_eq!(parsed.patterns[0].pattern, r"^(?P<time>\d+\.\d+)");
+        let names = parsed.active.get("ufs").cloned().unwrap().into_names();
+        assert_eq!(names, vec!["custom".to_string(), "fallback".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_store_reads_v1_single_active() {
+        let parsed = parse_store(
+            r#"{"version": 1, "patterns": [], "active": {"block": "Default Block Pattern"}}"#,
+        )
+        .unwrap();
+        let names = parsed.active.get("block").cloned().unwrap().into_names();
+        assert_eq!(names, vec!["Default Block Pattern".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_store_rejects_corrupt_content() {
+        assert!(parse_store("{ not json").is_err());
+        assert!(parse_store(r#"{"version": 999, "patterns": [], "active": {}}"#).is_err());
+    }
+
+    #[test]
+    fn test_backup_file_moves_corrupt_store() {
+        let dir = std::env::temp_dir().join(format!("estrace_pattern_store_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(PATTERN_STORE_FILE);
+        fs::write(&path, "{ not json").unwrap();
+        assert!(parse_store(&fs::read_to_string(&path).unwrap()).is_err());
+
+        let backup = backup_file(&path).unwrap();
+        assert_eq!(backup, dir.join("patterns.json.bak"));
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "{ not json");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_needs_default_seed() {
+        assert!(needs_default_seed(None));
+        let v2 = parse_store(r#"{"version": 2, "patterns": [], "active": {}}"#).unwrap();
+        assert!(needs_default_seed(Some(&v2)));
+        // v3 파일에 기본 패턴이 없으면 사용자가 삭제한 것이므로 다시 넣지 않음
+        let v3 = parse_store(r#"{"version": 3, "patterns": [], "active": {}}"#).unwrap();
+        assert!(!needs_default_seed(Some(&v3)));
+    }
+}
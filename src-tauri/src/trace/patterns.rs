@@ -3,8 +3,14 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
-use super::{ACTIVE_BLOCK_PATTERN, ACTIVE_UFS_PATTERN, BLOCK_PATTERNS, UFS_PATTERNS};
+use super::pattern_schema::{self, SchemaCheck};
+use super::pattern_store::{self, PatternStore};
+use super::{
+    ACTIVE_BLOCK_PATTERN, ACTIVE_UFSCUSTOM_PATTERN, ACTIVE_UFS_PATTERN, BLOCK_PATTERNS,
+    UFSCUSTOM_PATTERNS, UFS_PATTERNS,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Pattern {
@@ -12,6 +18,8 @@ pub struct Pattern {
     pub pattern_type: String,
     pub pattern: String,
     pub is_active: bool,
+    // 활성 패턴 목록 내 순서 (0 = 기본 패턴, 1.. = fallback), 비활성이면 None
+    pub active_order: Option<usize>,
 }
 
 // 정규식 테스트를 위한 구조체들
@@ -20,6 +28,12 @@ pub struct RegexTestResult {
     pub success: bool,
     pub error: Option<String>,
     pub matches: Option<Vec<RegexMatch>>,
+    // pattern_type이 주어진 경우 스키마 검사 결과
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaCheck>,
+    // 패턴에 매치되지 않은 라인 번호 (1부터, pattern_type이 주어진 경우)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unmatched_lines: Option<Vec<usize>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,6 +41,46 @@ pub struct RegexMatch {
     pub full_match: String,
     pub captures: Vec<String>,
     pub groups: Option<HashMap<String, String>>,
+    // 테스트 텍스트 내 라인 번호 (1부터)
+    pub line: usize,
+    // 값이 없거나 변환에 실패한 필수 필드 (pattern_type이 주어진 경우)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_fields: Option<Vec<String>>,
+}
+
+// 지원하는 패턴 타입 목록
+pub const PATTERN_TYPES: [&str; 3] = ["ufs", "block", "ufscustom"];
+
+// 기본 패턴 이름/정규식
+pub const DEFAULT_UFS_PATTERN_NAME: &str = "Default UFS Pattern";
+pub const DEFAULT_BLOCK_PATTERN_NAME: &str = "Default Block Pattern";
+pub const DEFAULT_UFSCUSTOM_PATTERN_NAME: &str = "Default UFS Custom Pattern";
+
+pub const DEFAULT_UFS_PATTERN: &str = r"^\s*(?P<process>.*?)\s+\[(?P<cpu>[0-9]+)\].*?(?P<time>[0-9]+\.[0-9]+):\s+ufshcd_command:\s+(?P<command>send_req|complete_rsp):.*?tag:\s*(?P<tag>\d+).*?size:\s*(?P<size>[-]?\d+).*?LBA:\s*(?P<lba>\d+).*?opcode:\s*(?P<opcode>0x[0-9a-f]+).*?group_id:\s*0x(?P<group_id>[0-9a-f]+).*?hwq_id:\s*(?P<hwq_id>[-]?\d+)";
+pub const DEFAULT_BLOCK_PATTERN: &str = r"^\s*(?P<process>.*?)\s+\[(?P<cpu>\d+)\]\s+(?P<flags>.+?)\s+(?P<time>[\d\.]+):\s+(?P<action>\S+):\s+(?P<devmajor>\d+),(?P<devminor>\d+)\s+(?P<io_type>[A-Z]+)(?:(?:\s+(?P<extra>\d+))?\s+\(\))?\s+(?P<sector>\d+)\s+\+\s+(?P<size>\d+)(?:\s+\S+)?\s+\[(?P<comm>.*?)\]$";
+pub const DEFAULT_UFSCUSTOM_PATTERN: &str = r"^(?P<opcode>0x[0-9a-f]+),(?P<lba>\d+),(?P<size>\d+),(?P<start_time>\d+(?:\.\d+)?),(?P<end_time>\d+(?:\.\d+)?)$";
+
+type PatternRegistry = (
+    &'static RwLock<HashMap<String, Regex>>,
+    &'static RwLock<Vec<(String, Regex)>>,
+);
+
+// 패턴 타입에 해당하는 (패턴 맵, 순서 있는 활성 패턴 목록) 반환
+pub(crate) fn registry(pattern_type: &str) -> Result<PatternRegistry, String> {
+    match pattern_type {
+        "ufs" => Ok((&*UFS_PATTERNS, &*ACTIVE_UFS_PATTERN)),
+        "block" => Ok((&*BLOCK_PATTERNS, &*ACTIVE_BLOCK_PATTERN)),
+        "ufscustom" => Ok((&*UFSCUSTOM_PATTERNS, &*ACTIVE_UFSCUSTOM_PATTERN)),
+        _ => Err(format!("Unsupported pattern type: {}", pattern_type)),
+    }
+}
+
+// 파싱에 사용할 활성 패턴 목록 - 이벤트에는 이름을 복사하지 않고 Arc<str>를 공유
+pub(crate) fn named_patterns(active: &[(String, Regex)]) -> Vec<(Arc<str>, Regex)> {
+    active
+        .iter()
+        .map(|(name, regex)| (Arc::from(name.as_str()), regex.clone()))
+        .collect()
 }
 
 /// Add a new pattern to the appropriate cache
@@ -37,43 +91,109 @@ pub fn add_pattern(name: String, pattern_type: String, pattern: String) -> Resul
         Err(e) => return Err(format!("Invalid regex pattern: {}", e)),
     };
 
-    match pattern_type.as_str() {
-        "ufs" => {
-            let mut patterns = UFS_PATTERNS.write().map_err(|e| e.to_string())?;
-            patterns.insert(name, compiled_pattern);
-        }
-        "block" => {
-            let mut patterns = BLOCK_PATTERNS.write().map_err(|e| e.to_string())?;
-            patterns.insert(name, compiled_pattern);
+    // 파서가 필요로 하는 named group이 모두 있는지 확인
+    pattern_schema::validate_pattern(&pattern_type, &compiled_pattern)?;
+
+    insert_pattern(&pattern_type, name, compiled_pattern)?;
+
+    pattern_store::save_or_warn();
+    Ok(())
+}
+
+// 검증된 패턴을 레지스트리에 추가 (같은 이름이 있으면 교체)
+// 활성 목록에 있는 패턴이면 활성 목록의 정규식도 함께 갱신
+pub(crate) fn insert_pattern(pattern_type: &str, name: String, regex: Regex) -> Result<(), String> {
+    let (patterns, active) = registry(pattern_type)?;
+    let mut patterns = patterns.write().map_err(|e| e.to_string())?;
+
+    let mut active = active.write().map_err(|e| e.to_string())?;
+    for (active_name, active_regex) in active.iter_mut() {
+        if active_name == &name {
+            *active_regex = regex.clone();
         }
-        _ => return Err(format!("Unsupported pattern type: {}", pattern_type)),
     }
 
+    patterns.insert(name, regex);
     Ok(())
 }
 
+// 활성화할 패턴을 레지스트리에서 찾아 스키마 검사 후 반환
+fn lookup_active_candidate(
+    patterns: &HashMap<String, Regex>,
+    pattern_type: &str,
+    name: &str,
+) -> Result<Regex, String> {
+    let pattern = patterns
+        .get(name)
+        .ok_or_else(|| format!("Pattern '{}' not found", name))?;
+
+    // 스키마 검사 이전에 저장된 패턴이 활성화되지 않도록 다시 확인
+    pattern_schema::validate_pattern(pattern_type, pattern)?;
+    Ok(pattern.clone())
+}
+
 /// Set a pattern as active for a specific type
+/// 기본(첫 번째) 패턴만 교체하고 fallback 순서는 유지
 pub fn set_active_pattern(name: String, pattern_type: String) -> Result<(), String> {
-    match pattern_type.as_str() {
-        "ufs" => {
-            let patterns = UFS_PATTERNS.read().map_err(|e| e.to_string())?;
-            let pattern = patterns
-                .get(&name)
-                .ok_or_else(|| format!("Pattern '{}' not found", name))?;
-
-            let mut active = ACTIVE_UFS_PATTERN.write().map_err(|e| e.to_string())?;
-            *active = (name, pattern.clone());
+    let (patterns, active) = registry(&pattern_type)?;
+    {
+        let patterns = patterns.read().map_err(|e| e.to_string())?;
+        let regex = lookup_active_candidate(&patterns, &pattern_type, &name)?;
+
+        let mut active = active.write().map_err(|e| e.to_string())?;
+        active.retain(|(active_name, _)| active_name != &name);
+        if active.is_empty() {
+            active.push((name, regex));
+        } else {
+            active[0] = (name, regex);
         }
-        "block" => {
-            let patterns = BLOCK_PATTERNS.read().map_err(|e| e.to_string())?;
-            let pattern = patterns
-                .get(&name)
-                .ok_or_else(|| format!("Pattern '{}' not found", name))?;
-
-            let mut active = ACTIVE_BLOCK_PATTERN.write().map_err(|e| e.to_string())?;
-            *active = (name, pattern.clone());
+    }
+
+    pattern_store::save_or_warn();
+    Ok(())
+}
+
+/// Set the ordered list of active patterns for a specific type
+/// 라인마다 names 순서대로 시도하여 처음 매치된 패턴으로 파싱
+pub fn set_active_patterns(names: Vec<String>, pattern_type: String) -> Result<(), String> {
+    if names.is_empty() {
+        return Err("At least one active pattern is required".to_string());
+    }
+
+    let (patterns, active) = registry(&pattern_type)?;
+    {
+        let patterns = patterns.read().map_err(|e| e.to_string())?;
+        let mut ordered: Vec<(String, Regex)> = Vec::with_capacity(names.len());
+        for name in names {
+            if ordered.iter().any(|(existing, _)| existing == &name) {
+                return Err(format!("Pattern '{}' is listed more than once", name));
+            }
+            let regex = lookup_active_candidate(&patterns, &pattern_type, &name)?;
+            ordered.push((name, regex));
         }
-        _ => return Err(format!("Unsupported pattern type: {}", pattern_type)),
+
+        *active.write().map_err(|e| e.to_string())? = ordered;
+    }
+
+    pattern_store::save_or_warn();
+    Ok(())
+}
+
+// 한 타입의 패턴 목록 수집
+fn collect_patterns(pattern_type: &str, result: &mut Vec<Pattern>) -> Result<(), String> {
+    let (patterns, active) = registry(pattern_type)?;
+    let patterns = patterns.read().map_err(|e| e.to_string())?;
+    let active = active.read().map_err(|e| e.to_string())?;
+
+    for (name, regex) in patterns.iter() {
+        let active_order = active.iter().position(|(active_name, _)| active_name == name);
+        result.push(Pattern {
+            name: name.clone(),
+            pattern_type: pattern_type.to_string(),
+            pattern: regex.to_string(),
+            is_active: active_order.is_some(),
+            active_order,
+        });
     }
 
     Ok(())
@@ -84,97 +204,35 @@ pub fn get_patterns(pattern_type: Option<String>) -> Result<String, String> {
     let mut result = Vec::new();
 
     if let Some(ref pattern_type) = pattern_type {
-        match pattern_type.as_str() {
-            "ufs" => {
-                let patterns = UFS_PATTERNS.read().map_err(|e| e.to_string())?;
-                let active = ACTIVE_UFS_PATTERN.read().map_err(|e| e.to_string())?;
-
-                for (name, _) in patterns.iter() {
-                    result.push(Pattern {
-                        name: name.clone(),
-                        pattern_type: "ufs".to_string(),
-                        pattern: patterns.get(name).unwrap().to_string(),
-                        is_active: name == &active.0,
-                    });
-                }
-            }
-            "block" => {
-                let patterns = BLOCK_PATTERNS.read().map_err(|e| e.to_string())?;
-                let active = ACTIVE_BLOCK_PATTERN.read().map_err(|e| e.to_string())?;
-
-                for (name, _) in patterns.iter() {
-                    result.push(Pattern {
-                        name: name.clone(),
-                        pattern_type: "block".to_string(),
-                        pattern: patterns.get(name).unwrap().to_string(),
-                        is_active: name == &active.0,
-                    });
-                }
-            }
-            _ => return Err(format!("Unsupported pattern type: {}", pattern_type)),
-        }
+        collect_patterns(pattern_type, &mut result)?;
     } else {
-        // Both ufs and block patterns
-        {
-            let patterns = UFS_PATTERNS.read().map_err(|e| e.to_string())?;
-            let active = ACTIVE_UFS_PATTERN.read().map_err(|e| e.to_string())?;
-
-            for (name, _) in patterns.iter() {
-                result.push(Pattern {
-                    name: name.clone(),
-                    pattern_type: "ufs".to_string(),
-                    pattern: patterns.get(name).unwrap().to_string(),
-                    is_active: name == &active.0,
-                });
-            }
-        }
-
-        {
-            let patterns = BLOCK_PATTERNS.read().map_err(|e| e.to_string())?;
-            let active = ACTIVE_BLOCK_PATTERN.read().map_err(|e| e.to_string())?;
-
-            for (name, _) in patterns.iter() {
-                result.push(Pattern {
-                    name: name.clone(),
-                    pattern_type: "block".to_string(),
-                    pattern: patterns.get(name).unwrap().to_string(),
-                    is_active: name == &active.0,
-                });
-            }
+        for pattern_type in PATTERN_TYPES {
+            collect_patterns(pattern_type, &mut result)?;
         }
     }
 
     serde_json::to_string(&result).map_err(|e| e.to_string())
 }
 
-/// Get active patterns
+/// Get active patterns (type -> patterns in the order they are tried)
 pub fn get_active_patterns() -> Result<String, String> {
     let mut active_patterns = HashMap::new();
 
-    {
-        let active = ACTIVE_UFS_PATTERN.read().map_err(|e| e.to_string())?;
-        active_patterns.insert(
-            "ufs".to_string(),
-            Pattern {
-                name: active.0.clone(),
-                pattern_type: "ufs".to_string(),
-                pattern: active.1.to_string(),
-                is_active: true,
-            },
-        );
-    }
-
-    {
-        let active = ACTIVE_BLOCK_PATTERN.read().map_err(|e| e.to_string())?;
-        active_patterns.insert(
-            "block".to_string(),
-            Pattern {
-                name: active.0.clone(),
-                pattern_type: "block".to_string(),
-                pattern: active.1.to_string(),
+    for pattern_type in PATTERN_TYPES {
+        let (_, active) = registry(pattern_type)?;
+        let active = active.read().map_err(|e| e.to_string())?;
+        let ordered: Vec<Pattern> = active
+            .iter()
+            .enumerate()
+            .map(|(order, (name, regex))| Pattern {
+                name: name.clone(),
+                pattern_type: pattern_type.to_string(),
+                pattern: regex.to_string(),
                 is_active: true,
-            },
-        );
+                active_order: Some(order),
+            })
+            .collect();
+        active_patterns.insert(pattern_type.to_string(), ordered);
     }
 
     serde_json::to_string(&active_patterns).map_err(|e| e.to_string())
@@ -182,60 +240,123 @@ pub fn get_active_patterns() -> Result<String, String> {
 
 /// Delete a pattern
 pub fn delete_pattern(name: String, pattern_type: String) -> Result<(), String> {
-    match pattern_type.as_str() {
-        "ufs" => {
-            let mut patterns = UFS_PATTERNS.write().map_err(|e| e.to_string())?;
-            let active = ACTIVE_UFS_PATTERN.read().map_err(|e| e.to_string())?;
-
-            // Check if the pattern is active
-            if active.0 == name {
-                return Err("Cannot delete an active pattern".to_string());
-            }
+    let (patterns, active) = registry(&pattern_type)?;
+    {
+        let mut patterns = patterns.write().map_err(|e| e.to_string())?;
+        let active = active.read().map_err(|e| e.to_string())?;
 
-            if patterns.remove(&name).is_none() {
-                return Err(format!("Pattern '{}' not found", name));
-            }
+        // Check if the pattern is active (기본 패턴 또는 fallback)
+        if active.iter().any(|(active_name, _)| active_name == &name) {
+            return Err("Cannot delete an active pattern".to_string());
         }
-        "block" => {
-            let mut patterns = BLOCK_PATTERNS.write().map_err(|e| e.to_string())?;
-            let active = ACTIVE_BLOCK_PATTERN.read().map_err(|e| e.to_string())?;
 
-            // Check if the pattern is active
-            if active.0 == name {
-                return Err("Cannot delete an active pattern".to_string());
-            }
-
-            if patterns.remove(&name).is_none() {
-                return Err(format!("Pattern '{}' not found", name));
-            }
+        if patterns.remove(&name).is_none() {
+            return Err(format!("Pattern '{}' not found", name));
         }
-        _ => return Err(format!("Unsupported pattern type: {}", pattern_type)),
     }
 
+    pattern_store::save_or_warn();
     Ok(())
 }
 
 // Initialize the default patterns
 pub fn initialize_patterns() {
-    // Add default UFS pattern
-    let default_ufs_pattern = Regex::new(
-        r"^\s*(.*?)\s+\[([0-9]+)\].*?([0-9]+\.[0-9]+):\s+ufshcd_command:\s+(send_req|complete_rsp):.*?tag:\s*(\d+).*?size:\s*([-]?\d+).*?LBA:\s*(\d+).*?opcode:\s*(0x[0-9a-f]+).*?group_id:\s*0x([0-9a-f]+).*?hwq_id:\s*(\d+)"
-    ).unwrap();
+    // 저장된 패턴 복원 - 파일이 손상된 경우 백업 후 기본 패턴으로 시작
+    let store = match pattern_store::load() {
+        Ok(store) => store,
+        Err(e) => {
+            println!("⚠️  저장된 패턴 로드 실패, 기본 패턴 사용: {}", e);
+            // 다음 저장에서 덮어쓰지 않도록 기존 파일을 백업
+            match pattern_store::backup_store() {
+                Ok(backup) => println!("   기존 패턴 파일 백업: {}", backup.display()),
+                Err(e) => println!("⚠️  {}", e),
+            }
+            None
+        }
+    };
 
-    let mut ufs_patterns = UFS_PATTERNS.write().unwrap();
-    ufs_patterns.insert("Default UFS Pattern".to_string(), default_ufs_pattern);
+    // 기본 패턴은 처음 한 번만 추가 - 이후에는 저장 파일에 남아 있는 것만 복원
+    if pattern_store::needs_default_seed(store.as_ref()) {
+        let defaults = [
+            ("ufs", DEFAULT_UFS_PATTERN_NAME, DEFAULT_UFS_PATTERN),
+            ("block", DEFAULT_BLOCK_PATTERN_NAME, DEFAULT_BLOCK_PATTERN),
+            ("ufscustom", DEFAULT_UFSCUSTOM_PATTERN_NAME, DEFAULT_UFSCUSTOM_PATTERN),
+        ];
+
+        for (pattern_type, name, pattern) in defaults {
+            let (patterns, _) = registry(pattern_type).unwrap();
+            patterns
+                .write()
+                .unwrap()
+                .insert(name.to_string(), Regex::new(pattern).unwrap());
+        }
+    }
 
-    // Add default Block pattern
-    let default_block_pattern = Regex::new(
-        r"^\s*(?P<process>.*?)\s+\[(?P<cpu>\d+)\]\s+(?P<flags>.+?)\s+(?P<time>[\d\.]+):\s+(?P<action>\S+):\s+(?P<devmajor>\d+),(?P<devminor>\d+)\s+(?P<io_type>[A-Z]+)(?:\s+(?P<extra>\d+))?\s+\(\)\s+(?P<sector>\d+)\s+\+\s+(?P<size>\d+)(?:\s+\S+)?\s+\[(?P<comm>.*?)\]$"
-    ).unwrap();
+    if let Some(store) = store {
+        if let Err(e) = apply_store(store) {
+            println!("⚠️  저장된 패턴 적용 실패, 기본 패턴 사용: {}", e);
+        }
+    }
+}
+
+// 저장 파일의 패턴을 레지스트리에 반영 (컴파일 실패한 패턴은 건너뜀)
+fn apply_store(store: PatternStore) -> Result<(), String> {
+    let mut loaded = 0;
+
+    for stored in store.patterns {
+        let regex = match Regex::new(&stored.pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                println!("⚠️  저장된 패턴 '{}' 컴파일 실패: {}", stored.name, e);
+                continue;
+            }
+        };
 
-    let mut block_patterns = BLOCK_PATTERNS.write().unwrap();
-    block_patterns.insert("Default Block Pattern".to_string(), default_block_pattern);
+        let (patterns, _) = match registry(&stored.pattern_type) {
+            Ok(r) => r,
+            Err(_) => {
+                println!("⚠️  알 수 없는 패턴 타입 건너뜀: {}", stored.pattern_type);
+                continue;
+            }
+        };
+        if let Err(e) = pattern_schema::validate_pattern(&stored.pattern_type, &regex) {
+            println!("⚠️  저장된 패턴 '{}' 건너뜀: {}", stored.name, e);
+            continue;
+        }
+        patterns
+            .write()
+            .map_err(|e| e.to_string())?
+            .insert(stored.name, regex);
+        loaded += 1;
+    }
+
+    // 활성 패턴은 레지스트리에 있는 것만 순서대로 복원
+    for (pattern_type, names) in store.active {
+        let Ok((patterns, active)) = registry(&pattern_type) else {
+            continue;
+        };
+        let patterns = patterns.read().map_err(|e| e.to_string())?;
+        let ordered: Vec<(String, Regex)> = names
+            .into_names()
+            .into_iter()
+            .filter_map(|name| patterns.get(&name).cloned().map(|regex| (name, regex)))
+            .collect();
+        if !ordered.is_empty() {
+            *active.write().map_err(|e| e.to_string())? = ordered;
+        }
+    }
+
+    println!("✅ 저장된 패턴 {}개 로드 완료", loaded);
+    Ok(())
 }
 
 /// Test a regex pattern against a text
-pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, String> {
+/// pattern_type이 주어지면 스키마 검사와 라인별 필수 필드 누락/변환 실패를 함께 보고
+pub fn test_regex_pattern(
+    text: String,
+    pattern: String,
+    pattern_type: Option<String>,
+) -> Result<String, String> {
     // 정규식 컴파일 시도
     let regex = match Regex::new(&pattern) {
         Ok(re) => re,
@@ -244,19 +365,27 @@ pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, Strin
                 success: false,
                 error: Some(format!("Invalid regex pattern: {}", e)),
                 matches: None,
+                schema: None,
+                unmatched_lines: None,
             };
             return serde_json::to_string(&result).map_err(|e| e.to_string());
         }
     };
 
+    let schema = match pattern_type {
+        Some(ref pattern_type) => Some(pattern_schema::schema_for(pattern_type)?),
+        None => None,
+    };
+
     // 텍스트 분할 (각 라인별로)
     let lines: Vec<&str> = text.lines().collect();
 
     // 매치 결과 수집
     let mut matches = Vec::new();
+    let mut unmatched_lines = Vec::new();
 
     // 각 라인에 대해 매치 시도
-    for line in lines {
+    for (index, line) in lines.into_iter().enumerate() {
         // 빈 라인은 건너뛰기
         if line.trim().is_empty() {
             continue;
@@ -268,6 +397,9 @@ pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, Strin
                 full_match: caps.get(0).map_or("", |m| m.as_str()).to_string(),
                 captures: Vec::new(),
                 groups: None,
+                line: index + 1,
+                failed_fields: schema
+                    .map(|schema| pattern_schema::failed_required_fields(schema, &caps)),
             };
 
             // 캡처 그룹 추출
@@ -292,6 +424,8 @@ pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, Strin
             }
 
             matches.push(match_result);
+        } else {
+            unmatched_lines.push(index + 1);
         }
     }
 
@@ -304,6 +438,8 @@ pub fn test_regex_pattern(text: String, pattern: String) -> Result<String, Strin
         } else {
             Some(matches)
         },
+        schema: schema.map(|schema| pattern_schema::check_capture_names(schema, &regex)),
+        unmatched_lines: schema.map(|_| unmatched_lines),
     };
 
     serde_json::to_string(&result).map_err(|e| e.to_string())
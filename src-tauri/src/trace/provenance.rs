@@ -0,0 +1,407 @@
+// src-tauri/src/trace/provenance.rs
+//
+// Parquet footer(key-value metadata)에 기록하는 파싱 출처 정보
+// 공유 폴더 등에서 찾은 parquet 파일이 어떤 로그/패턴/옵션으로 만들어졌는지 추적하기 위함
+
+use chrono::Local;
+use parquet::file::metadata::KeyValue;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::thread::JoinHandle;
+
+use crate::trace::block::BlockDedupReport;
+use crate::trace::clock_sync::ClockSync;
+use crate::trace::streams::StreamOptions;
+use crate::trace::PatternMatchCount;
+
+pub const PROVENANCE_VERSION: &str = "1";
+const KEY_PREFIX: &str = "estrace.";
+// arrow 스키마는 크기가 크고 출처 정보가 아니므로 trace_info 결과에서 제외
+const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SourceLog {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenancePattern {
+    pub pattern_type: String,
+    pub name: String,
+    pub regex: String,
+    // 활성 목록에서의 순서 (0 = primary)
+    pub order: usize,
+}
+
+// 후처리 옵션 - starttrace에서 지정하고 parquet에 기록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostProcessOptions {
+    // block 재전송(requeue 이벤트 없는 반복 issue) / flush 시퀀스 완료 제거 여부
+    pub block_dedup: bool,
+    // UFS 순차 스트림 검출 옵션 (이전 버전 parquet에는 없음)
+    #[serde(default)]
+    pub ufs_streams: StreamOptions,
+}
+
+impl Default for PostProcessOptions {
+    fn default() -> Self {
+        PostProcessOptions {
+            block_dedup: true,
+            ufs_streams: StreamOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ParseReport {
+    // 고성능 파서는 전체/미인식 라인 수를 집계하지 않음
+    pub total_lines: Option<usize>,
+    pub missing_lines: Option<usize>,
+    pub ufs: usize,
+    pub block: usize,
+    pub ufscustom: usize,
+    pub pattern_matches: Vec<PatternMatchCount>,
+    // block 요청 추적 / 중복 제거 결과 (이전 버전 parquet에는 없음)
+    #[serde(default)]
+    pub block_dedup: Option<BlockDedupReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceProvenance {
+    pub source: SourceLog,
+    pub patterns: Vec<ProvenancePattern>,
+    // "legacy", "highperf" 또는 이벤트 테이블의 "generic"
+    pub parser_backend: String,
+    pub app_version: String,
+    pub post_processing: PostProcessOptions,
+    pub parse_report: ParseReport,
+    pub created_at: String,
+    // 트레이스 time -> 실제 시각 변환 정보 (마커 또는 사용자 입력, 없으면 None)
+    #[serde(default)]
+    pub clock_sync: Option<ClockSync>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TraceFileInfo {
+    pub path: String,
+    pub trace_type: Option<String>,
+    pub num_rows: i64,
+    pub num_row_groups: usize,
+    pub columns: Vec<String>,
+    pub provenance: Option<TraceProvenance>,
+    // estrace 외 다른 도구가 기록한 key-value
+    pub other_metadata: BTreeMap<String, String>,
+}
+
+/// 로그 파일 SHA-256 (1MB 단위 스트리밍)
+pub fn hash_file(path: &str) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("로그 파일 열기 실패: {}", e))?;
+    let mut reader = BufReader::with_capacity(1024 * 1024, file);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = reader.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| e.to_string())
+}
+
+/// 타입별 활성 패턴 목록을 출처 정보로 변환
+pub fn active_patterns(
+    ufs: &[(String, Regex)],
+    block: &[(String, Regex)],
+    ufscustom: &[(String, Regex)],
+) -> Vec<ProvenancePattern> {
+    [("ufs", ufs), ("block", block), ("ufscustom", ufscustom)]
+        .into_iter()
+        .flat_map(|(pattern_type, patterns)| {
+            patterns
+                .iter()
+                .enumerate()
+                .map(move |(order, (name, regex))| ProvenancePattern {
+                    pattern_type: pattern_type.to_string(),
+                    name: name.clone(),
+                    regex: regex.as_str().to_string(),
+                    order,
+                })
+        })
+        .collect()
+}
+
+/// 해시 스레드 결과 수집 - 실패해도 파싱 결과 저장은 계속 진행
+pub fn join_hash(handle: JoinHandle<Result<String, String>>) -> String {
+    match handle.join() {
+        Ok(Ok(hash)) => hash,
+        Ok(Err(e)) => {
+            println!("⚠️  로그 파일 해시 계산 실패: {}", e);
+            String::new()
+        }
+        Err(_) => {
+            println!("⚠️  로그 파일 해시 계산 스레드 비정상 종료");
+            String::new()
+        }
+    }
+}
+
+impl TraceProvenance {
+    pub fn new(
+        source: SourceLog,
+        patterns: Vec<ProvenancePattern>,
+        parser_backend: &str,
+        parse_report: ParseReport,
+    ) -> Self {
+        TraceProvenance {
+            source,
+            patterns,
+            parser_backend: parser_backend.to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            post_processing: PostProcessOptions::default(),
+            parse_report,
+            created_at: Local::now().to_rfc3339(),
+            clock_sync: None,
+        }
+    }
+
+    pub fn with_clock_sync(mut self, clock_sync: Option<ClockSync>) -> Self {
+        self.clock_sync = clock_sync;
+        self
+    }
+
+    pub fn with_post_processing(mut self, options: PostProcessOptions) -> Self {
+        self.post_processing = options;
+        self
+    }
+
+    /// trace_type parquet 파일에 기록할 key-value 목록 (해당 타입의 패턴만 포함)
+    pub fn key_value_metadata(&self, trace_type: &str) -> Result<Vec<KeyValue>, String> {
+        let patterns: Vec<&ProvenancePattern> = self
+            .patterns
+            .iter()
+            .filter(|p| p.pattern_type == trace_type)
+            .collect();
+
+        let mut entries = vec![
+            ("provenance_version", PROVENANCE_VERSION.to_string()),
+            ("trace_type", trace_type.to_string()),
+            ("source.path", self.source.path.clone()),
+            ("source.size", self.source.size.to_string()),
+            ("source.sha256", self.source.sha256.clone()),
+            ("patterns", to_json(&patterns)?),
+            ("parser_backend", self.parser_backend.clone()),
+            ("app_version", self.app_version.clone()),
+            ("post_processing", to_json(&self.post_processing)?),
+            ("parse_report", to_json(&self.parse_report)?),
+            ("created_at", self.created_at.clone()),
+        ];
+        if let Some(clock_sync) = &self.clock_sync {
+            entries.push(("clock_sync", to_json(clock_sync)?));
+        }
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, value)| KeyValue::new(format!("{}{}", KEY_PREFIX, key), value))
+            .collect())
+    }
+
+    /// key-value 목록에서 출처 정보 복원 (estrace가 기록하지 않은 파일이면 None)
+    pub fn from_key_values(
+        metadata: &HashMap<String, String>,
+    ) -> Result<Option<(String, TraceProvenance)>, String> {
+        let get = |key: &str| metadata.get(&format!("{}{}", KEY_PREFIX, key));
+        if get("provenance_version").is_none() {
+            return Ok(None);
+        }
+
+        let text = |key: &str| get(key).cloned().unwrap_or_default();
+        fn parse_json<T: serde::de::DeserializeOwned + Default>(
+            key: &str,
+            value: Option<&String>,
+        ) -> Result<T, String> {
+            match value {
+                Some(value) => serde_json::from_str(value)
+                    .map_err(|e| format!("{}{} 메타데이터 파싱 실패: {}", KEY_PREFIX, key, e)),
+                None => Ok(T::default()),
+            }
+        }
+
+        let provenance = TraceProvenance {
+            source: SourceLog {
+                path: text("source.path"),
+                size: text("source.size").parse().unwrap_or(0),
+                sha256: text("source.sha256"),
+            },
+            patterns: parse_json("patterns", get("patterns"))?,
+            parser_backend: text("parser_backend"),
+            app_version: text("app_version"),
+            post_processing: parse_json("post_processing", get("post_processing"))?,
+            parse_report: parse_json("parse_report", get("parse_report"))?,
+            created_at: text("created_at"),
+            clock_sync: get("clock_sync")
+                .map(|value| serde_json::from_str(value))
+                .transpose()
+                .map_err(|e| format!("{}clock_sync 메타데이터 파싱 실패: {}", KEY_PREFIX, e))?,
+        };
+
+        Ok(Some((text("trace_type"), provenance)))
+    }
+}
+
+/// parquet footer에서 행 수, 컬럼, 출처 정보 읽기
+pub fn read_trace_file_info(path: &str) -> Result<TraceFileInfo, String> {
+    let file = File::open(path).map_err(|e| format!("parquet 파일 열기 실패: {}", e))?;
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let metadata = reader.metadata();
+    let file_metadata = metadata.file_metadata();
+
+    let key_values: HashMap<String, String> = file_metadata
+        .key_value_metadata()
+        .map(|kvs| {
+            kvs.iter()
+                .filter_map(|kv| kv.value.clone().map(|value| (kv.key.clone(), value)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (trace_type, provenance) = match TraceProvenance::from_key_values(&key_values)? {
+        Some((trace_type, provenance)) => (Some(trace_type), Some(provenance)),
+        None => (None, None),
+    };
+
+    let other_metadata = key_values
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with(KEY_PREFIX) && key != ARROW_SCHEMA_KEY)
+        .collect();
+
+    Ok(TraceFileInfo {
+        path: path.to_string(),
+        trace_type,
+        num_rows: file_metadata.num_rows(),
+        num_row_groups: metadata.num_row_groups(),
+        columns: file_metadata
+            .schema_descr()
+            .columns()
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect(),
+        provenance,
+        other_metadata,
+    })
+}
+
+/// trace_info 명령 - logname은 쉼표로 구분된 parquet 경로 목록
+pub fn trace_info(logname: String) -> Result<String, String> {
+    let infos = logname
+        .split(',')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(read_trace_file_info)
+        .collect::<Result<Vec<_>, String>>()?;
+
+    serde_json::to_string(&infos).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_provenance() -> TraceProvenance {
+        TraceProvenance::new(
+            SourceLog {
+                path: "/logs/trace.txt".to_string(),
+                size: 1234,
+                sha256: "ab".repeat(32),
+            },
+            vec![
+                ProvenancePattern {
+                    pattern_type: "ufs".to_string(),
+                    name: "Default UFS".to_string(),
+                    regex: r"(?P<time>\d+\.\d+)".to_string(),
+                    order: 0,
+                },
+                ProvenancePattern {
+                    pattern_type: "block".to_string(),
+                    name: "Default Block".to_string(),
+                    regex: r"(?P<sector>\d+)".to_string(),
+                    order: 0,
+                },
+            ],
+            "legacy",
+            ParseReport {
+                total_lines: Some(10),
+                missing_lines: Some(2),
+                ufs: 5,
+                block: 3,
+                ufscustom: 0,
+                pattern_matches: vec![PatternMatchCount {
+                    pattern_type: "ufs".to_string(),
+                    name: "Default UFS".to_string(),
+                    order: 0,
+                    count: 5,
+                }],
+                block_dedup: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_key_value_round_trip() {
+        let provenance = sample_provenance();
+        let metadata: HashMap<String, String> = provenance
+            .key_value_metadata("ufs")
+            .unwrap()
+            .into_iter()
+            .map(|kv| (kv.key, kv.value.unwrap()))
+            .collect();
+        assert!(metadata.keys().all(|key| key.starts_with(KEY_PREFIX)));
+
+        let (trace_type, restored) = TraceProvenance::from_key_values(&metadata).unwrap().unwrap();
+        assert_eq!(trace_type, "ufs");
+        assert_eq!(restored.source.sha256, provenance.source.sha256);
+        assert_eq!(restored.source.size, 1234);
+        // 해당 타입의 패턴만 기록
+        assert_eq!(restored.patterns.len(), 1);
+        assert_eq!(restored.patterns[0].name, "Default UFS");
+        assert_eq!(restored.parse_report.missing_lines, Some(2));
+        assert_eq!(restored.parse_report.pattern_matches[0].count, 5);
+        assert!(restored.post_processing.block_dedup);
+    }
+
+    #[test]
+    fn test_from_key_values_without_provenance() {
+        let mut metadata = HashMap::new();
+        metadata.insert(ARROW_SCHEMA_KEY.to_string(), "...".to_string());
+        assert!(TraceProvenance::from_key_values(&metadata).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_hash_file() {
+        let path = std::env::temp_dir().join(format!("estrace_hash_{}.txt", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let hash = hash_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
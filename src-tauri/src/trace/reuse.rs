@@ -0,0 +1,454 @@
+// src-tauri/src/trace/reuse.rs
+//
+// 4KB 단위 재사용 거리(reuse distance)와 working set 분석 (캐시 / HPB / WriteBooster 크기 산정용)
+// 요청을 4KB 블록 접근으로 펼친 뒤
+// - 재사용 거리: 같은 블록을 다시 접근하기까지 접근한 서로 다른 블록 수 (LRU stack distance)
+//   거리 d인 접근은 (d + 1)블록 이상의 LRU 캐시에서 hit이므로 2의 거듭제곱 캐시 크기별로 집계한다.
+// - working set: window_ms 구간(step_ms 간격으로 이동)에서 접근한 서로 다른 블록 수
+// - read-after-write: 이전에 write된 블록을 read한 비율, overwrite: 이전에 write된 블록을 다시 write한 비율
+// 접근 시각은 UFS send_req / block_rq_issue 기준이고, read / write 이외의 요청은 제외한다.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::trace::block::parse_rwbs;
+use crate::trace::filter::{filter_block_data, filter_ufs_data};
+use crate::trace::iostat::rw_kind;
+use crate::trace::timestamp;
+
+const BLOCK_BYTES: u64 = 4096;
+// 분석할 최대 4KB 블록 접근 수 (넘으면 앞부분만 분석)
+const MAX_ACCESSES: usize = 20_000_000;
+// 재사용 거리 구간 최대 캐시 크기 (2^24 블록 = 64GB)
+const MAX_DISTANCE_BUCKET: u32 = 24;
+// working set 최대 구간 수
+const MAX_WINDOWS: usize = 100_000;
+
+// 4KB 블록 접근 하나 (device는 block 장치 구분, UFS는 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockAccess {
+    pub time: i64,
+    pub device: u32,
+    pub block: u64,
+    pub is_read: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct ReuseHistogram {
+    pub accesses: usize,
+    // 처음 접근한 블록 (재사용 아님)
+    pub cold: usize,
+    pub reuses: usize,
+    // 캐시 크기별 재사용 횟수 ("01_≤ 4KB"는 거리 0)
+    pub buckets: BTreeMap<String, usize>,
+    // 해당 크기 LRU 캐시의 누적 hit 비율 (전체 접근 대비)
+    pub hit_ratio: BTreeMap<String, f64>,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct WorkingSetWindow {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub blocks: usize,
+    pub read_blocks: usize,
+    pub write_blocks: usize,
+    pub bytes: u64,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ReuseReport {
+    pub trace_type: String,
+    pub block_bytes: u64,
+    pub total_accesses: usize,
+    // MAX_ACCESSES에서 잘렸는지 여부
+    pub truncated: bool,
+    pub unique_blocks: usize,
+    pub unique_read_blocks: usize,
+    pub unique_write_blocks: usize,
+    pub footprint_bytes: u64,
+    pub read: ReuseHistogram,
+    pub write: ReuseHistogram,
+    pub read_after_write: usize,
+    pub read_after_write_rate: f64, // %
+    pub overwrites: usize,
+    pub overwrite_rate: f64, // %
+    pub window_ms: f64,
+    pub step_ms: f64,
+    pub max_working_set_bytes: u64,
+    pub avg_working_set_bytes: f64,
+    pub working_set: Vec<WorkingSetWindow>,
+}
+
+// 구간 합 계산용 Fenwick tree (접근 위치별로 블록의 마지막 접근 여부 표시)
+struct Fenwick(Vec<i32>);
+
+impl Fenwick {
+    fn new(n: usize) -> Self {
+        Fenwick(vec![0; n + 1])
+    }
+
+    fn add(&mut self, pos: usize, delta: i32) {
+        let mut i = pos + 1;
+        while i < self.0.len() {
+            self.0[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    // [0, pos) 합
+    fn prefix(&self, pos: usize) -> i64 {
+        let mut i = pos;
+        let mut sum = 0i64;
+        while i > 0 {
+            sum += self.0[i] as i64;
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+fn size_label(bytes: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1 << 30, "GB"), (1 << 20, "MB"), (1 << 10, "KB")];
+    UNITS
+        .iter()
+        .find(|(unit, _)| bytes >= *unit)
+        .map(|(unit, name)| format!("{}{}", bytes / unit, name))
+        .unwrap_or_else(|| format!("{}B", bytes))
+}
+
+// 거리 d를 담을 수 있는 최소 캐시 크기 구간 (2^i 블록)
+fn bucket_index(distance: u64) -> u32 {
+    if distance == 0 {
+        0
+    } else {
+        64 - distance.leading_zeros()
+    }
+}
+
+fn bucket_key(index: u32) -> String {
+    if index > MAX_DISTANCE_BUCKET {
+        format!("99_> {}", size_label((1u64 << MAX_DISTANCE_BUCKET) * BLOCK_BYTES))
+    } else {
+        format!("{:02}_≤ {}", index + 1, size_label((1u64 << index) * BLOCK_BYTES))
+    }
+}
+
+fn empty_histogram() -> ReuseHistogram {
+    let keys = (0..=MAX_DISTANCE_BUCKET + 1).map(bucket_key);
+    ReuseHistogram {
+        buckets: keys.clone().map(|k| (k, 0)).collect(),
+        hit_ratio: keys.map(|k| (k, 0.0)).collect(),
+        ..Default::default()
+    }
+}
+
+fn finish_histogram(histogram: &mut ReuseHistogram) {
+    if histogram.accesses == 0 {
+        return;
+    }
+    let mut cumulative = 0;
+    for (key, count) in &histogram.buckets {
+        cumulative += count;
+        histogram
+            .hit_ratio
+            .insert(key.clone(), cumulative as f64 / histogram.accesses as f64);
+    }
+}
+
+fn rate(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64 * 100.0
+    }
+}
+
+/// 재사용 거리, read-after-write / overwrite 집계 (accesses는 시간순)
+fn reuse_distances(accesses: &[BlockAccess], report: &mut ReuseReport) {
+    let mut read = empty_histogram();
+    let mut write = empty_histogram();
+    // 블록 -> (마지막 접근 위치, write된 적이 있는지, read된 적이 있는지)
+    let mut last: HashMap<(u32, u64), (usize, bool, bool)> = HashMap::new();
+    let mut marks = Fenwick::new(accesses.len());
+
+    for (pos, access) in accesses.iter().enumerate() {
+        let histogram = if access.is_read { &mut read } else { &mut write };
+        histogram.accesses += 1;
+        let entry = last.entry((access.device, access.block)).or_insert((pos, false, false));
+        if entry.0 == pos {
+            histogram.cold += 1;
+        } else {
+            let distance = (marks.prefix(pos) - marks.prefix(entry.0 + 1)) as u64;
+            marks.add(entry.0, -1);
+            histogram.reuses += 1;
+            *histogram
+                .buckets
+                .entry(bucket_key(bucket_index(distance).min(MAX_DISTANCE_BUCKET + 1)))
+                .or_insert(0) += 1;
+        }
+        if access.is_read && entry.1 {
+            report.read_after_write += 1;
+        }
+        if !access.is_read && entry.1 {
+            report.overwrites += 1;
+        }
+        entry.0 = pos;
+        if access.is_read {
+            entry.2 = true;
+        } else {
+            entry.1 = true;
+        }
+        marks.add(pos, 1);
+    }
+
+    finish_histogram(&mut read);
+    finish_histogram(&mut write);
+    report.unique_blocks = last.len();
+    report.unique_read_blocks = last.values().filter(|v| v.2).count();
+    report.unique_write_blocks = last.values().filter(|v| v.1).count();
+    report.footprint_bytes = last.len() as u64 * BLOCK_BYTES;
+    report.read_after_write_rate = rate(report.read_after_write, read.accesses);
+    report.overwrite_rate = rate(report.overwrites, write.accesses);
+    report.read = read;
+    report.write = write;
+}
+
+/// window 길이 / step 간격으로 이동하며 구간별 서로 다른 블록 수 (accesses는 시간순)
+fn working_set(accesses: &[BlockAccess], range: (i64, i64), window: i64, step: i64) -> Result<Vec<WorkingSetWindow>, String> {
+    if window <= 0 || step <= 0 {
+        return Err("window_ms / step_ms는 0보다 커야 합니다".to_string());
+    }
+    let (start, end) = range;
+    let count = ((end - start).max(0) / step + 1) as usize;
+    if count > MAX_WINDOWS {
+        return Err(format!(
+            "구간이 너무 많습니다 ({}개, 최대 {}개) - step_ms를 늘려 주세요",
+            count, MAX_WINDOWS
+        ));
+    }
+
+    // 블록 -> (구간 안 read 수, write 수)
+    let mut in_window: HashMap<(u32, u64), (u32, u32)> = HashMap::new();
+    let (mut read_blocks, mut write_blocks) = (0usize, 0usize);
+    let (mut lo, mut hi) = (0, 0);
+    let mut windows = Vec::with_capacity(count);
+    for k in 0..count as i64 {
+        let from = start + k * step;
+        let to = from + window;
+        while hi < accesses.len() && accesses[hi].time < to {
+            let a = &accesses[hi];
+            let entry = in_window.entry((a.device, a.block)).or_insert((0, 0));
+            if a.is_read {
+                read_blocks += (entry.0 == 0) as usize;
+                entry.0 += 1;
+            } else {
+                write_blocks += (entry.1 == 0) as usize;
+                entry.1 += 1;
+            }
+            hi += 1;
+        }
+        while lo < hi && accesses[lo].time < from {
+            let a = &accesses[lo];
+            let key = (a.device, a.block);
+            if let Some(entry) = in_window.get_mut(&key) {
+                if a.is_read {
+                    entry.0 -= 1;
+                    read_blocks -= (entry.0 == 0) as usize;
+                } else {
+                    entry.1 -= 1;
+                    write_blocks -= (entry.1 == 0) as usize;
+                }
+                if *entry == (0, 0) {
+                    in_window.remove(&key);
+                }
+            }
+            lo += 1;
+        }
+        windows.push(WorkingSetWindow {
+            start_time: timestamp::ns_to_secs(from),
+            end_time: timestamp::ns_to_secs(to),
+            blocks: in_window.len(),
+            read_blocks,
+            write_blocks,
+            bytes: in_window.len() as u64 * BLOCK_BYTES,
+        });
+    }
+    Ok(windows)
+}
+
+/// 블록 접근 목록으로 리포트 생성 (accesses는 시간순, MAX_ACCESSES 이하)
+pub fn analyze(
+    trace_type: &str,
+    accesses: &[BlockAccess],
+    range: (i64, i64),
+    window: i64,
+    step: i64,
+) -> Result<ReuseReport, String> {
+    let mut report = ReuseReport {
+        trace_type: trace_type.to_string(),
+        block_bytes: BLOCK_BYTES,
+        total_accesses: accesses.len(),
+        window_ms: timestamp::ns_to_ms(window),
+        step_ms: timestamp::ns_to_ms(step),
+        ..Default::default()
+    };
+    reuse_distances(accesses, &mut report);
+    report.working_set = working_set(accesses, range, window, step)?;
+    report.max_working_set_bytes = report.working_set.iter().map(|w| w.bytes).max().unwrap_or(0);
+    if !report.working_set.is_empty() {
+        report.avg_working_set_bytes = report.working_set.iter().map(|w| w.bytes as f64).sum::<f64>()
+            / report.working_set.len() as f64;
+    }
+    Ok(report)
+}
+
+// 요청 [start, end) (4KB 블록)을 블록 접근으로 펼침 - MAX_ACCESSES를 넘으면 false
+fn push_blocks(accesses: &mut Vec<BlockAccess>, time: i64, device: u32, start: u64, end: u64, is_read: bool) -> bool {
+    for block in start..end {
+        if accesses.len() >= MAX_ACCESSES {
+            return false;
+        }
+        accesses.push(BlockAccess { time, device, block, is_read });
+    }
+    true
+}
+
+fn load_accesses(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+) -> Result<(Vec<BlockAccess>, bool), String> {
+    let mut accesses = Vec::new();
+    let mut complete = true;
+    match trace_type {
+        "ufs" => {
+            for u in filter_ufs_data(logname, time_from, time_to, "lba", None, None, None)? {
+                if u.action != "send_req" {
+                    continue;
+                }
+                if let Some(is_read) = rw_kind("ufs", &u.opcode) {
+                    complete = push_blocks(&mut accesses, u.time, 0, u.lba, u.lba + u.size as u64, is_read);
+                }
+                if !complete {
+                    break;
+                }
+            }
+        }
+        "block" => {
+            for b in filter_block_data(logname, time_from, time_to, "sector", None, None, None)? {
+                if b.action != "block_rq_issue" {
+                    continue;
+                }
+                let is_read = match parse_rwbs(&b.io_type).op {
+                    "read" => true,
+                    "write" => false,
+                    _ => continue,
+                };
+                // sector(512B) -> 4KB 블록, 장치별로 주소 공간 분리
+                let device = (b.devmajor << 20) | b.devminor;
+                let end = (b.sector + b.size as u64).div_ceil(8);
+                complete = push_blocks(&mut accesses, b.time, device, b.sector / 8, end, is_read);
+                if !complete {
+                    break;
+                }
+            }
+        }
+        _ => return Err(format!("지원하지 않는 트레이스 타입: {}", trace_type)),
+    }
+    accesses.sort_by_key(|a| a.time);
+    Ok((accesses, !complete))
+}
+
+/// reuse_distance 명령 - trace_type: "ufs" | "block"
+pub fn reuse_distance(
+    logname: &str,
+    trace_type: &str,
+    time_from: Option<f64>,
+    time_to: Option<f64>,
+    window_ms: f64,
+    step_ms: Option<f64>,
+) -> Result<String, String> {
+    let (accesses, truncated) = load_accesses(logname, trace_type, time_from, time_to)?;
+    if accesses.is_empty() {
+        return Err(format!("분석할 {} read / write 요청이 없습니다", trace_type));
+    }
+    let range = (
+        time_from.map(timestamp::secs_to_ns).unwrap_or(accesses[0].time),
+        time_to
+            .map(timestamp::secs_to_ns)
+            .unwrap_or(accesses[accesses.len() - 1].time),
+    );
+    let to_ns = |ms: f64| (ms * timestamp::NS_PER_MS as f64).round() as i64;
+    let mut report = analyze(
+        trace_type,
+        &accesses,
+        range,
+        to_ns(window_ms),
+        to_ns(step_ms.unwrap_or(window_ms)),
+    )?;
+    report.truncated = truncated;
+
+    println!(
+        "♻️  {} 재사용 거리: 4KB 접근 {}개, 고유 블록 {}개, RAW {:.1}%, overwrite {:.1}%{}",
+        trace_type,
+        report.total_accesses,
+        report.unique_blocks,
+        report.read_after_write_rate,
+        report.overwrite_rate,
+        if truncated { " (접근 수 제한으로 일부만 분석)" } else { "" }
+    );
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(time_ms: i64, block: u64, is_read: bool) -> BlockAccess {
+        BlockAccess { time: time_ms * timestamp::NS_PER_MS, device: 0, block, is_read }
+    }
+
+    #[test]
+    fn test_reuse_distance_and_raw() {
+        // W1 W2 R1 R3 R2 W1
+        let accesses = vec![
+            access(0, 1, false),
+            access(1, 2, false),
+            access(2, 1, true),  // 거리 1 (2), RAW
+            access(3, 3, true),  // cold
+            access(4, 2, true),  // 거리 2 (1, 3), RAW
+            access(5, 1, false), // 거리 2 (3, 2), overwrite
+        ];
+        let ms = timestamp::NS_PER_MS;
+        let report = analyze("ufs", &accesses, (0, 5 * ms), 10 * ms, 10 * ms).unwrap();
+        assert_eq!((report.read.accesses, report.read.cold, report.read.reuses), (3, 1, 2));
+        assert_eq!(report.read.buckets["02_≤ 8KB"], 1);
+        assert_eq!(report.read.buckets["03_≤ 16KB"], 1);
+        assert_eq!(report.write.buckets["03_≤ 16KB"], 1);
+        assert_eq!(report.read.hit_ratio["02_≤ 8KB"], 1.0 / 3.0);
+        assert_eq!(report.read.hit_ratio["03_≤ 16KB"], 2.0 / 3.0);
+        assert_eq!((report.read_after_write, report.overwrites), (2, 1));
+        assert_eq!((report.unique_blocks, report.unique_read_blocks, report.unique_write_blocks), (3, 3, 2));
+        assert_eq!(report.working_set.len(), 1);
+        assert_eq!(report.working_set[0].bytes, 3 * 4096);
+        assert_eq!(bucket_key(MAX_DISTANCE_BUCKET + 1), "99_> 64GB");
+    }
+
+    #[test]
+    fn test_sliding_working_set() {
+        let accesses = vec![
+            access(0, 1, true),
+            access(5, 2, true),
+            access(12, 2, false),
+            access(18, 3, false),
+        ];
+        let ms = timestamp::NS_PER_MS;
+        let windows = working_set(&accesses, (0, 20 * ms), 10 * ms, 5 * ms).unwrap();
+        let blocks: Vec<usize> = windows.iter().map(|w| w.blocks).collect();
+        // [0,10) {1,2}, [5,15) {2}, [10,20) {2,3}, [15,25) {3}, [20,30) {}
+        assert_eq!(blocks, vec![2, 1, 2, 1, 0]);
+        assert_eq!((windows[1].read_blocks, windows[1].write_blocks), (1, 1));
+        assert!(working_set(&accesses, (0, 20 * ms), 0, 5 * ms).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+// src-tauri/src/trace/streams.rs
+//
+// 다중 순차 스트림 검출
+// 직전 요청 하나와만 비교하면 두 앱의 순차 I/O가 섞였을 때 모두 랜덤으로 보인다.
+// 최근 사용된 최대 N개의 스트림을 유지하고, 새 요청이 어떤 스트림의 끝 주소 근처
+// (gap / overlap 허용 범위)에서 시작하면 그 스트림에 이어 붙인다.
+// 스트림은 opcode와 분리 기준(process / hwqid)이 같은 요청끼리만 이어진다.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+// 통계 결과에 포함할 최대 스트림 수 (바이트 순, 개수 집계는 전체 기준)
+const MAX_REPORTED_STREAMS: usize = 1_000;
+
+// 스트림 길이(요청 수) 구간
+const RUN_LENGTH_BUCKETS: [(usize, &str); 7] = [
+    (1, "1"),
+    (3, "2-3"),
+    (7, "4-7"),
+    (15, "8-15"),
+    (63, "16-63"),
+    (255, "64-255"),
+    (usize::MAX, "256+"),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamSplit {
+    #[default]
+    None,
+    Process,
+    Hwq,
+}
+
+// 스트림 검출 옵션 (PostProcessOptions에 포함되어 parquet에 기록)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct StreamOptions {
+    // 동시에 추적할 최대 스트림 수 (1이면 직전 요청과만 비교하는 기존 방식)
+    pub max_streams: usize,
+    // 스트림 끝 주소보다 뒤에서 시작해도 이어지는 것으로 보는 거리 (lba 단위)
+    pub gap_tolerance: u64,
+    // 스트림 끝 주소보다 앞에서 시작(겹침)해도 이어지는 것으로 보는 거리 (lba 단위)
+    pub overlap_tolerance: u64,
+    pub split_by: StreamSplit,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            max_streams: 8,
+            gap_tolerance: 0,
+            overlap_tolerance: 0,
+            split_by: StreamSplit::None,
+        }
+    }
+}
+
+// 요청이 배정된 스트림 (position 0 = 스트림의 첫 요청)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamAssignment {
+    pub stream_id: u32,
+    pub position: u32,
+}
+
+impl StreamAssignment {
+    pub fn continuous(&self) -> bool {
+        self.position > 0
+    }
+}
+
+#[derive(Debug)]
+struct ActiveStream<K> {
+    key: K,
+    id: u32,
+    next: u64,
+    position: u32,
+    last_used: u64,
+}
+
+/// 최근 사용 순(LRU)으로 최대 max_streams개의 스트림을 추적
+/// key는 스트림이 이어질 수 있는 조건 (opcode, 분리 기준 값 등)
+#[derive(Debug)]
+pub struct StreamDetector<K> {
+    options: StreamOptions,
+    streams: Vec<ActiveStream<K>>,
+    next_id: u32,
+    clock: u64,
+}
+
+impl<K: PartialEq> StreamDetector<K> {
+    pub fn new(options: StreamOptions) -> Self {
+        StreamDetector {
+            options,
+            streams: Vec::new(),
+            next_id: 1,
+            clock: 0,
+        }
+    }
+
+    /// 요청 [start, start + len) 배정 - 허용 범위 안에서 가장 가까운 스트림에 이어 붙이고
+    /// 없으면 가장 오래 사용되지 않은 스트림을 밀어내고 새 스트림 시작
+    pub fn observe(&mut self, key: K, start: u64, len: u64) -> StreamAssignment {
+        self.clock += 1;
+        let gap = self.options.gap_tolerance;
+        let overlap = self.options.overlap_tolerance;
+        let end = start.saturating_add(len);
+
+        let matched = self
+            .streams
+            .iter_mut()
+            .filter(|s| {
+                s.key == key
+                    && start <= s.next.saturating_add(gap)
+                    && start.saturating_add(overlap) >= s.next
+            })
+            .min_by_key(|s| s.next.abs_diff(start));
+
+        if let Some(stream) = matched {
+            stream.position += 1;
+            stream.next = stream.next.max(end);
+            stream.last_used = self.clock;
+            return StreamAssignment { stream_id: stream.id, position: stream.position };
+        }
+
+        let stream = ActiveStream { key, id: self.next_id, next: end, position: 0, last_used: self.clock };
+        self.next_id += 1;
+        let assignment = StreamAssignment { stream_id: stream.id, position: 0 };
+
+        if self.streams.len() < self.options.max_streams.max(1) {
+            self.streams.push(stream);
+        } else if let Some(lru) = self.streams.iter_mut().min_by_key(|s| s.last_used) {
+            *lru = stream;
+        }
+        assignment
+    }
+
+    /// 지금까지 만들어진 스트림 수
+    pub fn stream_count(&self) -> u32 {
+        self.next_id - 1
+    }
+}
+
+// 스트림 하나의 요약 (통계 구간 안의 요청 기준)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StreamRun {
+    pub stream_id: u32,
+    pub opcode: String,
+    pub start_lba: u64,
+    pub end_lba: u64,
+    // run length (스트림에 속한 요청 수)
+    pub requests: usize,
+    pub bytes: u64,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StreamStats {
+    pub stream_count: usize,
+    // 요청이 2개 이상인 스트림 수
+    pub sequential_streams: usize,
+    pub mean_run_length: f64,
+    pub max_run_length: usize,
+    pub mean_stream_bytes: f64,
+    // run length 구간별 스트림 수
+    pub run_length_counts: BTreeMap<String, usize>,
+    // 스트림 목록이 MAX_REPORTED_STREAMS에서 잘렸는지 여부
+    pub truncated: bool,
+    pub streams: Vec<StreamRun>,
+}
+
+// 스트림 통계 입력 요청 한 건
+pub struct StreamRequest<'a> {
+    pub stream_id: u32,
+    pub opcode: &'a str,
+    pub lba: u64,
+    pub len: u64,
+    pub bytes: u64,
+    pub time: f64,
+}
+
+/// 스트림별 run length / 바이트 집계 (stream_id 0은 스트림 정보가 없는 요청)
+pub fn stream_stats<'a>(requests: impl IntoIterator<Item = StreamRequest<'a>>) -> StreamStats {
+    let mut runs: HashMap<u32, StreamRun> = HashMap::new();
+    for req in requests {
+        if req.stream_id == 0 {
+            continue;
+        }
+        let run = runs.entry(req.stream_id).or_insert_with(|| StreamRun {
+            stream_id: req.stream_id,
+            opcode: req.opcode.to_string(),
+            start_lba: req.lba,
+            end_lba: req.lba,
+            requests: 0,
+            bytes: 0,
+            start_time: req.time,
+            end_time: req.time,
+        });
+        run.start_lba = run.start_lba.min(req.lba);
+        run.end_lba = run.end_lba.max(req.lba.saturating_add(req.len));
+        run.requests += 1;
+        run.bytes += req.bytes;
+        run.end_time = run.end_time.max(req.time);
+    }
+
+    let mut stats = StreamStats {
+        run_length_counts: RUN_LENGTH_BUCKETS
+            .iter()
+            .map(|(_, label)| (label.to_string(), 0))
+            .collect(),
+        ..Default::default()
+    };
+    if runs.is_empty() {
+        return stats;
+    }
+
+    let mut streams: Vec<StreamRun> = runs.into_values().collect();
+    let total_requests: usize = streams.iter().map(|s| s.requests).sum();
+    let total_bytes: u64 = streams.iter().map(|s| s.bytes).sum();
+    for stream in &streams {
+        if let Some((_, label)) = RUN_LENGTH_BUCKETS.iter().find(|(max, _)| stream.requests <= *max) {
+            *stats.run_length_counts.entry(label.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    stats.stream_count = streams.len();
+    stats.sequential_streams = streams.iter().filter(|s| s.requests > 1).count();
+    stats.mean_run_length = total_requests as f64 / streams.len() as f64;
+    stats.max_run_length = streams.iter().map(|s| s.requests).max().unwrap_or(0);
+    stats.mean_stream_bytes = total_bytes as f64 / streams.len() as f64;
+
+    streams.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.stream_id.cmp(&b.stream_id)));
+    stats.truncated = streams.len() > MAX_REPORTED_STREAMS;
+    streams.truncate(MAX_REPORTED_STREAMS);
+    stats.streams = streams;
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleaved_streams_are_detected() {
+        let mut detector = StreamDetector::new(StreamOptions::default());
+        // 두 앱의 순차 read가 번갈아 들어옴
+        let a0 = detector.observe("0x28", 1000, 8);
+        let b0 = detector.observe("0x28", 5000, 8);
+        let a1 = detector.observe("0x28", 1008, 8);
+        let b1 = detector.observe("0x28", 5008, 8);
+        assert_eq!((a0.position, b0.position), (0, 0));
+        assert_eq!((a1.stream_id, a1.position), (a0.stream_id, 1));
+        assert_eq!((b1.stream_id, b1.position), (b0.stream_id, 1));
+        // opcode가 다르면 같은 주소여도 다른 스트림
+        assert!(!detector.observe("0x2a", 1016, 8).continuous());
+
+        // 스트림 1개면 직전 요청과만 비교하는 기존 동작
+        let mut single = StreamDetector::new(StreamOptions { max_streams: 1, ..Default::default() });
+        single.observe("0x28", 1000, 8);
+        single.observe("0x28", 5000, 8);
+        assert!(!single.observe("0x28", 1008, 8).continuous());
+        assert_eq!(single.stream_count(), 3);
+    }
+
+    #[test]
+    fn test_gap_and_overlap_tolerance() {
+        let options = StreamOptions { gap_tolerance: 4, overlap_tolerance: 2, ..Default::default() };
+        let mut detector = StreamDetector::new(options);
+        detector.observe("0x2a", 100, 8);
+        assert!(detector.observe("0x2a", 112, 8).continuous()); // 4 건너뜀
+        assert!(detector.observe("0x2a", 118, 8).continuous()); // 2 겹침
+        assert!(!detector.observe("0x2a", 131, 8).continuous()); // 5 건너뜀
+
+        let stats = stream_stats([1u32, 1, 1, 2].iter().enumerate().map(|(i, &id)| StreamRequest {
+            stream_id: id,
+            opcode: "0x2a",
+            lba: 100 + i as u64 * 8,
+            len: 8,
+            bytes: 8 * 4096,
+            time: i as f64,
+        }));
+        assert_eq!((stats.stream_count, stats.sequential_streams, stats.max_run_length), (2, 1, 3));
+        assert_eq!(stats.mean_run_length, 2.0);
+        assert_eq!(stats.run_length_counts["2-3"], 1);
+        assert_eq!(stats.streams[0].stream_id, 1);
+        assert_eq!((stats.streams[0].start_lba, stats.streams[0].end_lba), (100, 124));
+    }
+}
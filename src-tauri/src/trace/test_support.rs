@@ -0,0 +1,60 @@
+// src-tauri/src/trace/test_support.rs
+//
+// 테스트용 행 생성 - 각 테스트는 필요한 필드만 덮어써서 사용한다.
+//   UFS { hwqid: 1, ..ufs(100, "send_req", 3) }
+//   Block { io_type: "WS".to_string(), ..block(100, "block_rq_issue", 64) }
+
+use crate::trace::{unnamed_pattern, Block, UFS};
+
+/// UFS 행 - opcode 0x28(read), hwqid 0, lba = tag * 8, 4KB
+pub fn ufs(time_us: i64, action: &str, tag: u32) -> UFS {
+    UFS {
+        time: time_us * 1_000,
+        process: String::new(),
+        cpu: 0,
+        action: action.to_string(),
+        tag,
+        opcode: "0x28".to_string(),
+        lba: tag as u64 * 8,
+        size: 1,
+        groupid: 0,
+        hwqid: 0,
+        qd: 0,
+        dtoc: 0.0,
+        ctoc: 0.0,
+        ctod: 0.0,
+        continuous: false,
+        orphan: false,
+        hwq_qd: 0,
+        hwq_ctoc: 0.0,
+        hwq_ctod: 0.0,
+        stream_id: 0,
+        stream_position: 0,
+        comm: String::new(),
+        pattern: unnamed_pattern(),
+    }
+}
+
+/// Block 행 - 장치 8:0, read(R), 8 sector (4KB)
+pub fn block(time_us: i64, action: &str, sector: u64) -> Block {
+    Block {
+        time: time_us * 1_000,
+        process: String::new(),
+        cpu: 0,
+        flags: String::new(),
+        action: action.to_string(),
+        devmajor: 8,
+        devminor: 0,
+        io_type: "R".to_string(),
+        extra: 0,
+        sector,
+        size: 8,
+        comm: String::new(),
+        qd: 0,
+        dtoc: 0.0,
+        ctoc: 0.0,
+        ctod: 0.0,
+        continuous: false,
+        pattern: unnamed_pattern(),
+    }
+}
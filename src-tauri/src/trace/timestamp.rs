@@ -0,0 +1,163 @@
+// src-tauri/src/trace/timestamp.rs
+//
+// 트레이스 시각은 i64 나노초로 보관한다.
+// 로그의 "12345.678901" 문자열을 f64를 거치지 않고 나노초로 파싱하고,
+// 레이턴시는 나노초 정수 차이로 계산한 뒤 마지막에 ms로 변환한다.
+// (업타임이 며칠 이상이면 f64 초로는 sub-microsecond 정밀도가 유지되지 않음)
+// 이전 버전 parquet의 Float64 초 컬럼도 읽을 수 있도록 시간 컬럼 변환을 제공한다.
+
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, TimestampNanosecondArray};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const NS_PER_SEC: i64 = 1_000_000_000;
+pub const NS_PER_MS: i64 = 1_000_000;
+
+// parquet/IPC에서 나노초로 저장하는 시간 컬럼
+pub const TIME_COLUMNS: [&str; 3] = ["time", "start_time", "end_time"];
+
+/// "123.456789" 형식의 초 문자열을 나노초로 변환 (소수점 10자리 이하는 버림)
+/// 지수 표기 등 정수/소수 형식이 아니면 f64로 파싱 후 변환
+pub fn parse_seconds(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (int_part, frac_part) = text.split_once('.').unwrap_or((text, ""));
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (int_part.is_empty() && frac_part.is_empty()) || !is_digits(int_part) || !is_digits(frac_part) {
+        return text
+            .parse::<f64>()
+            .ok()
+            .filter(|secs| secs.is_finite())
+            .map(secs_to_ns);
+    }
+
+    let secs: i64 = if int_part.is_empty() { 0 } else { int_part.parse().ok()? };
+    let digits = &frac_part[..frac_part.len().min(9)];
+    let frac: i64 = if digits.is_empty() {
+        0
+    } else {
+        digits.parse::<i64>().ok()? * 10_i64.pow(9 - digits.len() as u32)
+    };
+    secs.checked_mul(NS_PER_SEC)?.checked_add(frac)
+}
+
+/// 초(f64) -> 나노초 (UI에서 넘어온 시간 범위, 이전 parquet 값 변환용)
+pub fn secs_to_ns(secs: f64) -> i64 {
+    (secs * NS_PER_SEC as f64).round() as i64
+}
+
+/// 나노초 -> 초(f64) (화면 표시, 통계 출력용)
+pub fn ns_to_secs(ns: i64) -> f64 {
+    ns as f64 / NS_PER_SEC as f64
+}
+
+/// 나노초 정수 차이 -> ms (레이턴시 컬럼 단위)
+pub fn ns_to_ms(ns: i64) -> f64 {
+    ns as f64 / NS_PER_MS as f64
+}
+
+/// parquet 저장용 시간 필드 (Int64 나노초, unit 메타데이터 포함)
+pub fn time_field(name: &str) -> Field {
+    Field::new(name, DataType::Int64, false)
+        .with_metadata(HashMap::from([("unit".to_string(), "ns".to_string())]))
+}
+
+/// 시간 컬럼을 나노초 값으로 읽기 (호환 reader)
+/// - Int64 / Timestamp(ns): 현재 형식
+/// - Float64: 이전 버전 parquet (초 단위)
+pub fn time_column_ns(array: &dyn Array, name: &str) -> Result<Vec<i64>, String> {
+    match array.data_type() {
+        DataType::Int64 => {
+            let values = array
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(|| format!("Failed to downcast '{}'", name))?;
+            Ok(values.iter().map(|v| v.unwrap_or(0)).collect())
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            let values = array
+                .as_any()
+                .downcast_ref::<TimestampNanosecondArray>()
+                .ok_or_else(|| format!("Failed to downcast '{}'", name))?;
+            Ok(values.iter().map(|v| v.unwrap_or(0)).collect())
+        }
+        DataType::Float64 => {
+            let values = array
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .ok_or_else(|| format!("Failed to downcast '{}'", name))?;
+            Ok(values.iter().map(|v| secs_to_ns(v.unwrap_or(0.0))).collect())
+        }
+        other => Err(format!("'{}' 컬럼 타입을 시간으로 읽을 수 없습니다: {:?}", name, other)),
+    }
+}
+
+/// 화면 전송(IPC)용 batch - 나노초 시간 컬럼을 Float64 초로 변환
+/// 프론트엔드 차트와 필터는 초 단위를 사용한다.
+pub fn seconds_view(batch: &RecordBatch) -> Result<RecordBatch, String> {
+    let schema = batch.schema();
+    let mut fields: Vec<Field> = Vec::with_capacity(schema.fields().len());
+    let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns());
+
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        if TIME_COLUMNS.contains(&field.name().as_str()) && field.data_type() == &DataType::Int64 {
+            let seconds: Float64Array = time_column_ns(column.as_ref(), field.name())?
+                .into_iter()
+                .map(ns_to_secs)
+                .collect::<Vec<_>>()
+                .into();
+            fields.push(Field::new(field.name(), DataType::Float64, field.is_nullable()));
+            columns.push(Arc::new(seconds));
+        } else {
+            fields.push(field.as_ref().clone());
+            columns.push(column.clone());
+        }
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_seconds_keeps_nanosecond_precision() {
+        // 약 11.5일 업타임: f64 초로는 마지막 자리가 보존되지 않는 값
+        assert_eq!(parse_seconds("987654.123456789"), Some(987_654_123_456_789));
+        assert_eq!(parse_seconds("987654.123456"), Some(987_654_123_456_000));
+        assert_eq!(parse_seconds("12"), Some(12_000_000_000));
+        assert_eq!(parse_seconds(".5"), Some(500_000_000));
+        // 10자리 이상 소수부는 버림
+        assert_eq!(parse_seconds("1.0000000019"), Some(1_000_000_001));
+        assert_eq!(parse_seconds("1e-3"), Some(1_000_000));
+        assert_eq!(parse_seconds("abc"), None);
+
+        let send = parse_seconds("987654.123456789").unwrap();
+        let complete = parse_seconds("987654.123457790").unwrap();
+        assert_eq!(ns_to_ms(complete - send), 0.001001);
+    }
+
+    #[test]
+    fn test_time_column_compat_and_seconds_view() {
+        let old = Float64Array::from(vec![1.5, 2.000001]);
+        assert_eq!(time_column_ns(&old, "time").unwrap(), vec![1_500_000_000, 2_000_001_000]);
+
+        let schema = Schema::new(vec![time_field("time"), Field::new("lba", DataType::Int64, false)]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1_500_000_000, 2_000_000_000])),
+                Arc::new(Int64Array::from(vec![10, 20])),
+            ],
+        )
+        .unwrap();
+
+        let view = seconds_view(&batch).unwrap();
+        let time = view.column(0).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(time.value(0), 1.5);
+        // 시간 컬럼이 아닌 Int64는 그대로
+        assert_eq!(view.schema().field(1).data_type(), &DataType::Int64);
+    }
+}
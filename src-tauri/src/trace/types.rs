@@ -1,11 +1,17 @@
+use once_cell::sync::Lazy;
 use serde::{Serialize, Deserialize};
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::trace::block::BlockDedupReport;
+use crate::trace::clock_sync::WallClockRange;
+use crate::trace::streams::StreamStats;
 
 // UFS는 Universal Flash Storage의 약어이므로 UFs로 변경하지 않고 원래 이름 유지
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Serialize, Debug, Clone)]
 pub struct UFS {
-    pub time: f64,
+    pub time: i64, // 나노초 (timestamp.rs)
     pub process: String,
     pub cpu: u32,
     pub action: String,
@@ -20,6 +26,14 @@ pub struct UFS {
     pub ctoc: f64, // Complete to Complete latency
     pub ctod: f64, // Complete to Device latency
     pub continuous: bool,
+    pub orphan: bool,    // 짝이 없는 send/complete (lifecycle.rs), 레이턴시 통계에서 제외
+    pub hwq_qd: u32,     // hwqid별 Queue Depth (MCQ)
+    pub hwq_ctoc: f64,   // 같은 hwqid 안에서의 Complete to Complete latency
+    pub hwq_ctod: f64,   // 같은 hwqid 안에서의 Complete to Device latency
+    pub stream_id: u32,       // 순차 스트림 번호 (send_req만, 0 = 없음)
+    pub stream_position: u32, // 스트림 안에서의 순서 (0 = 스트림 시작)
+    pub comm: String,         // 이 명령으로 처리된 block 요청의 comm (crosslayer.rs, 매칭되지 않으면 빈 문자열)
+    pub pattern: Arc<str>, // 이 이벤트를 파싱한 패턴 이름
 }
 
 // UFS는 Universal Flash Storage의 약어이므로 UFs로 변경하지 않고 원래 이름 유지
@@ -29,8 +43,8 @@ pub struct UFSCUSTOM {
     pub opcode: String,
     pub lba: u64,
     pub size: u32,
-    pub start_time: f64,
-    pub end_time: f64,
+    pub start_time: i64, // 나노초
+    pub end_time: i64,   // 나노초
     pub dtoc: f64,
     // 새로 추가할 필드들
     pub start_qd: u32,     // Queue Depth at request start
@@ -38,11 +52,12 @@ pub struct UFSCUSTOM {
     pub ctoc: f64,         // Complete to Complete latency (ms)
     pub ctod: f64,         // Complete to Dispatch latency (ms)
     pub continuous: bool,  // 연속적인 요청 여부
+    pub pattern: Arc<str>, // 이 이벤트를 파싱한 패턴 이름
 }
 
 #[derive(Serialize, Debug, Clone)]
 pub struct Block {
-    pub time: f64,
+    pub time: i64, // 나노초
     pub process: String,
     pub cpu: u32,
     pub flags: String,
@@ -59,6 +74,15 @@ pub struct Block {
     pub ctoc: f64, // Complete to Complete latency
     pub ctod: f64, // Complete to Device latency
     pub continuous: bool,
+    pub pattern: Arc<str>, // 이 이벤트를 파싱한 패턴 이름
+}
+
+// 패턴 이름은 같은 패턴으로 파싱한 이벤트끼리 Arc<str>를 공유 (행마다 문자열을 할당하지 않음)
+// 이름이 아직 없는 이벤트 (파싱 직후, pattern 컬럼이 없는 이전 버전 parquet)용 빈 이름
+static UNNAMED_PATTERN: Lazy<Arc<str>> = Lazy::new(|| Arc::from(""));
+
+pub fn unnamed_pattern() -> Arc<str> {
+    Arc::clone(&UNNAMED_PATTERN)
 }
 
 // 필요하지만 현재는 사용되지 않는 variant가 있는 경우
@@ -94,6 +118,31 @@ pub struct TraceParseResult {
     pub ufs_parquet_filename: String,
     pub block_parquet_filename: String,
     pub ufscustom_parquet_filename: String,
+    pub pattern_matches: Vec<PatternMatchCount>,
+    // block 요청 추적 / 중복 제거 결과 (block 이벤트가 없으면 None)
+    pub block_dedup: Option<BlockDedupReport>,
+}
+
+// 파싱 리포트 - 활성 패턴별 매치(파싱 성공) 라인 수
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatternMatchCount {
+    pub pattern_type: String,
+    pub name: String,
+    pub order: usize,
+    pub count: usize,
+}
+
+// 이벤트 테이블의 ftrace 라인 하나 - fields는 args의 key=value / key: value 쌍
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FtraceEvent {
+    pub time: f64,
+    pub cpu: u32,
+    pub process: String,
+    pub pid: u32,
+    pub flags: String,
+    pub event: String,
+    pub args: String,
+    pub fields: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -123,6 +172,9 @@ pub struct SizeStats {
 pub struct ContinuityStats {
     pub op_stats: BTreeMap<String, ContinuityCount>,
     pub total: TotalContinuity,
+    // 순차 스트림별 run length / 바이트 (UFS만, streams.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streams: Option<StreamStats>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -152,4 +204,7 @@ pub struct TraceStats {
     pub ctoc_stat: LatencyStats,
     pub size_counts: SizeStats,
     pub continuity: ContinuityStats,
+    // 분석 구간의 실제 시각 (시각 동기화 정보가 있는 트레이스만)
+    #[serde(default)]
+    pub wall_clock: Option<WallClockRange>,
 }
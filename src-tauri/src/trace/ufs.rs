@@ -3,20 +3,27 @@ use std::fs::{create_dir_all, File};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, BooleanArray, Float64Array, StringArray, UInt32Array, UInt64Array};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, UInt32Array, UInt64Array};
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
-use arrow::temporal_conversions::MILLISECONDS;
 use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
 use tauri::Emitter;
 
+use crate::trace::clock_sync::{self, WallClockRange};
+use crate::trace::timestamp;
 use crate::trace::filter::{filter_ufs_data};
+use crate::trace::lifecycle::RequestTracker;
+use crate::trace::provenance::TraceProvenance;
+use crate::trace::streams::{self, StreamDetector, StreamOptions, StreamRequest, StreamSplit, StreamStats};
 use crate::trace::utils::{
-    calculate_statistics, create_range_key, initialize_ranges, parse_time_to_ms,
+    calculate_statistics, create_range_key, initialize_ranges, parse_time_to_ms, pattern_array,
+    pattern_field,
 };
 use crate::trace::{
-    ContinuityCount, ContinuityStats, LatencyStat, LatencyStats, LatencyValue, SizeStats,
-    TotalContinuity, TraceStats, UFS,
+    ContinuityCount, ContinuityStats, LatencyStat, LatencyStats, LatencySummary, LatencyValue,
+    SizeStats, TotalContinuity, TraceStats, UFS,
 };
 
 // UFS 레이턴시 통계 분석을 위한 매개변수 구조체
@@ -55,8 +62,96 @@ pub struct UfsAllStatsParams {
     pub col_to: Option<f64>,
 }
 
+// 하드웨어 큐(hwqid)별 통계
+#[derive(Serialize, Debug, Clone)]
+pub struct HwqStats {
+    pub hwqid: u32,
+    pub send_count: usize,
+    pub complete_count: usize,
+    pub request_ratio: f64, // 전체 send_req 중 이 큐의 비율 (%)
+    pub total_bytes: u64,
+    pub qd: LatencySummary,   // complete_rsp 시점의 hwq_qd
+    pub dtoc: LatencySummary,
+    pub ctoc: LatencySummary, // hwq_ctoc
+    pub ctod: LatencySummary, // hwq_ctod
+    pub dtoc_counts: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct HwqStatsResult {
+    pub queues: Vec<HwqStats>,
+    // 큐별 send_req 수의 최대 / 평균 (1.0이면 균등 분배)
+    pub imbalance: f64,
+    pub wall_clock: Option<WallClockRange>,
+}
+
+// hwqid별 통계 계산용 임시 값
+#[derive(Default)]
+struct HwqAccumulator {
+    send_count: usize,
+    complete_count: usize,
+    total_bytes: u64,
+    qd: Vec<f64>,
+    dtoc: Vec<f64>,
+    ctoc: Vec<f64>,
+    ctod: Vec<f64>,
+}
+
+// 큐 하나의 QD / ctoc / ctod 계산 상태
+// 전체(global) 큐와 hwqid별(MCQ) 큐에 같은 규칙을 적용
+#[derive(Debug, Default)]
+struct QueueState {
+    qd: u32,
+    last_complete_time: Option<i64>,
+    last_complete_qd0_time: Option<i64>,
+    first_c: bool,
+    first_complete_time: i64,
+}
+
+impl QueueState {
+    // send_req: QD 증가, 큐가 비어 있다가 시작된 요청이면 ctod(ms) 반환
+    fn send(&mut self, time: i64) -> f64 {
+        self.qd += 1;
+        let mut ctod = 0.0;
+        if self.qd == 1 {
+            if let Some(t) = self.last_complete_qd0_time {
+                ctod = timestamp::ns_to_ms(time - t);
+            }
+            self.first_c = true;
+            self.first_complete_time = time;
+        }
+        ctod
+    }
+
+    // complete_rsp: ctoc(ms) 반환, 짝이 없는 complete(matched = false)는 QD를 줄이지 않음
+    fn complete(&mut self, time: i64, matched: bool) -> f64 {
+        if matched {
+            self.qd = self.qd.saturating_sub(1);
+        }
+        let ctoc = if self.first_c {
+            self.first_c = false;
+            timestamp::ns_to_ms(time - self.first_complete_time)
+        } else if let Some(t) = self.last_complete_time {
+            timestamp::ns_to_ms(time - t)
+        } else {
+            0.0
+        };
+        if self.qd == 0 {
+            self.last_complete_qd0_time = Some(time);
+        }
+        self.last_complete_time = Some(time);
+        ctoc
+    }
+
+    // 완료가 유실된 요청 (tag 재사용) - QD에서 제외
+    fn drop_request(&mut self) {
+        self.qd = self.qd.saturating_sub(1);
+    }
+}
+
 // UFS 레이턴시 후처리 함수
-pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>) -> Vec<UFS> {
+// 연속성은 다중 스트림 검출(streams.rs)로 판정하고 stream_id / stream_position을 기록
+pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>, stream_options: &StreamOptions) -> Vec<UFS> {
     // 이벤트가 없으면 빈 벡터 반환
     if ufs_list.is_empty() {
         return ufs_list;
@@ -82,9 +177,7 @@ pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>) -> Vec<UFS> {
     
     let sort_elapsed = if needs_sort {
         println!("      ⚠️  정렬되지 않은 데이터 감지, 정렬 중...");
-        ufs_list.sort_unstable_by(|a, b| {
-            a.time.partial_cmp(&b.time).unwrap_or(std::cmp::Ordering::Equal)
-        });
+        ufs_list.sort_unstable_by_key(|u| u.time);
         let elapsed = sort_start.elapsed().as_secs_f64();
         println!("      ✅ 정렬 완료: {:.2}초", elapsed);
         elapsed
@@ -94,18 +187,19 @@ pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>) -> Vec<UFS> {
         elapsed
     };
 
-    // 메모리 효율성을 위한 용량 최적화 (더 정확한 추정)
-    let estimated_capacity = (ufs_list.len() / 4).max(1024);
-    let mut req_times: HashMap<(u32, String), f64> = HashMap::with_capacity(estimated_capacity);
-    
-    let mut current_qd: u32 = 0;
-    let mut last_complete_time: Option<f64> = None;
-    let mut last_complete_qd0_time: Option<f64> = None;
-    let mut first_c: bool = false;
-    let mut first_complete_time: f64 = 0.0;
-
-    // 이전 send_req의 정보를 저장할 변수들
-    let mut prev_send_req: Option<(u64, u32, String)> = None; // (lba, size, opcode)
+    // send_req/complete_rsp 짝짓기 및 orphan/tag 재사용 검출
+    let mut tracker = RequestTracker::new();
+    // 전체 큐와 hwqid별 큐 (MCQ)
+    let mut queue = QueueState::default();
+    let mut hwq_queues: HashMap<u32, QueueState> = HashMap::new();
+
+    // 순차 스트림 검출 - (opcode, 분리 기준 값)이 같은 요청끼리만 이어짐
+    let mut streams: StreamDetector<(String, String)> = StreamDetector::new(stream_options.clone());
+    let split_key = |ufs: &UFS| match stream_options.split_by {
+        StreamSplit::None => String::new(),
+        StreamSplit::Process => ufs.process.clone(),
+        StreamSplit::Hwq => ufs.hwqid.to_string(),
+    };
 
     // 프로그레스 카운터 최적화
     let total_events = ufs_list.len();
@@ -130,53 +224,53 @@ pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>) -> Vec<UFS> {
         let action_bytes = ufs.action.as_bytes();
         
         if action_bytes == b"send_req" {
-            // 연속성 체크: 이전 send_req가 있는 경우
-            if let Some((prev_lba, prev_size, ref prev_opcode)) = prev_send_req {
-                let prev_end_addr = prev_lba + prev_size as u64;
-                // 현재 요청의 시작 주소가 이전 요청의 끝 주소와 같고, opcode가 같은 경우
-                ufs.continuous = ufs.lba == prev_end_addr && ufs.opcode == *prev_opcode;
-            } else {
-                ufs.continuous = false;
+            // 연속성 체크: 추적 중인 스트림의 끝 주소 근처에서 시작하면 이어짐
+            let stream = streams.observe((ufs.opcode.clone(), split_key(ufs)), ufs.lba, ufs.size as u64);
+            ufs.continuous = stream.continuous();
+            ufs.stream_id = stream.stream_id;
+            ufs.stream_position = stream.position;
+
+            // 완료되지 않은 같은 tag가 있으면 이전 요청은 QD에서 빠짐 (tag 재사용)
+            if let Some(lost) = tracker.send(idx, ufs) {
+                queue.drop_request();
+                hwq_queues.entry(lost.hwqid).or_default().drop_request();
             }
+            ufs.ctod = queue.send(ufs.time);
 
-            // 현재 send_req 정보 저장 (clone 최소화)
-            prev_send_req = Some((ufs.lba, ufs.size, ufs.opcode.clone()));
-
-            req_times.insert((ufs.tag, ufs.opcode.clone()), ufs.time);
-            current_qd += 1;
-            if current_qd == 1 {
-                if let Some(t) = last_complete_qd0_time {
-                    ufs.ctod = (ufs.time - t) * MILLISECONDS as f64;
-                }
-                first_c = true;
-                first_complete_time = ufs.time;
-            }
+            let hwq = hwq_queues.entry(ufs.hwqid).or_default();
+            ufs.hwq_ctod = hwq.send(ufs.time);
+            ufs.hwq_qd = hwq.qd;
         } else if action_bytes == b"complete_rsp" {
             // complete_rsp는 continuous 체크하지 않음
             ufs.continuous = false;
 
-            current_qd = current_qd.saturating_sub(1);
-            if let Some(send_time) = req_times.remove(&(ufs.tag, ufs.opcode.clone())) {
-                ufs.dtoc = (ufs.time - send_time) * MILLISECONDS as f64;
-            }
-            
-            // 조건 분기 최적화
-            if first_c {
-                ufs.ctoc = (ufs.time - first_complete_time) * MILLISECONDS as f64;
-                first_c = false;
-            } else if let Some(t) = last_complete_time {
-                ufs.ctoc = (ufs.time - t) * MILLISECONDS as f64;
-            }
-            
-            if current_qd == 0 {
-                last_complete_qd0_time = Some(ufs.time);
+            // 짝이 없는 complete는 QD를 줄이지 않음
+            let send = tracker.complete(idx, ufs);
+            if let Some(send) = &send {
+                ufs.dtoc = timestamp::ns_to_ms(ufs.time - send.time);
             }
-            last_complete_time = Some(ufs.time);
+            ufs.ctoc = queue.complete(ufs.time, send.is_some());
+
+            let hwq = hwq_queues.entry(ufs.hwqid).or_default();
+            ufs.hwq_ctoc = hwq.complete(ufs.time, send.is_some());
+            ufs.hwq_qd = hwq.qd;
         } else {
             ufs.continuous = false;
+            ufs.hwq_qd = hwq_queues.get(&ufs.hwqid).map_or(0, |q| q.qd);
         }
-        ufs.qd = current_qd;
+        ufs.qd = queue.qd;
+    }
+
+    let (report, orphan_rows) = tracker.finish(&ufs_list);
+    for idx in orphan_rows {
+        ufs_list[idx].orphan = true;
     }
+    if report.anomaly_count() > 0 {
+        println!("      🏷️  orphan send: {} | orphan complete: {} | tag 재사용: {} | opcode 불일치: {} (레이턴시 통계에서 제외)",
+                 report.orphan_sends, report.orphan_completes, report.tag_reuses, report.opcode_mismatches);
+    }
+
+    println!("      🔀 순차 스트림 {}개 검출 (최대 동시 추적 {}개)", streams.stream_count(), stream_options.max_streams);
 
     let processing_elapsed = processing_start.elapsed().as_secs_f64();
     let processing_rate = ufs_list.len() as f64 / processing_elapsed;
@@ -204,7 +298,7 @@ pub fn ufs_bottom_half_latency_process(mut ufs_list: Vec<UFS>) -> Vec<UFS> {
 // Vec<UFS>를 Arrow RecordBatch로 변환하는 함수
 pub fn ufs_to_record_batch(ufs_list: &[UFS]) -> Result<RecordBatch, String> {
     // 각 필드별로 Arrow 배열 생성
-    let time_array = Float64Array::from(ufs_list.iter().map(|u| u.time).collect::<Vec<f64>>());
+    let time_array = Int64Array::from(ufs_list.iter().map(|u| u.time).collect::<Vec<i64>>());
     let process_array = StringArray::from(
         ufs_list
             .iter()
@@ -235,10 +329,19 @@ pub fn ufs_to_record_batch(ufs_list: &[UFS]) -> Result<RecordBatch, String> {
     let ctod_array = Float64Array::from(ufs_list.iter().map(|u| u.ctod).collect::<Vec<f64>>());
     let continues_array =
         BooleanArray::from(ufs_list.iter().map(|u| u.continuous).collect::<Vec<bool>>());
+    let orphan_array = BooleanArray::from(ufs_list.iter().map(|u| u.orphan).collect::<Vec<bool>>());
+    let hwq_qd_array = UInt32Array::from(ufs_list.iter().map(|u| u.hwq_qd).collect::<Vec<u32>>());
+    let hwq_ctoc_array = Float64Array::from(ufs_list.iter().map(|u| u.hwq_ctoc).collect::<Vec<f64>>());
+    let hwq_ctod_array = Float64Array::from(ufs_list.iter().map(|u| u.hwq_ctod).collect::<Vec<f64>>());
+    let stream_id_array = UInt32Array::from(ufs_list.iter().map(|u| u.stream_id).collect::<Vec<u32>>());
+    let stream_position_array =
+        UInt32Array::from(ufs_list.iter().map(|u| u.stream_position).collect::<Vec<u32>>());
+    let comm_array = StringArray::from(ufs_list.iter().map(|u| u.comm.as_str()).collect::<Vec<&str>>());
+    let pattern_array = pattern_array(ufs_list.iter().map(|u| &*u.pattern));
 
     // 스키마 정의
     let schema = Arc::new(Schema::new(vec![
-        Field::new("time", DataType::Float64, false),
+        timestamp::time_field("time"),
         Field::new("process", DataType::Utf8, false),
         Field::new("cpu", DataType::UInt32, false),
         Field::new("action", DataType::Utf8, false),
@@ -253,6 +356,14 @@ pub fn ufs_to_record_batch(ufs_list: &[UFS]) -> Result<RecordBatch, String> {
         Field::new("ctoc", DataType::Float64, false),
         Field::new("ctod", DataType::Float64, false),
         Field::new("continuous", DataType::Boolean, false),
+        Field::new("orphan", DataType::Boolean, false),
+        Field::new("hwq_qd", DataType::UInt32, false),
+        Field::new("hwq_ctoc", DataType::Float64, false),
+        Field::new("hwq_ctod", DataType::Float64, false),
+        Field::new("stream_id", DataType::UInt32, false),
+        Field::new("stream_position", DataType::UInt32, false),
+        Field::new("comm", DataType::Utf8, false),
+        pattern_field(),
     ]));
 
     // RecordBatch 생성
@@ -274,6 +385,14 @@ pub fn ufs_to_record_batch(ufs_list: &[UFS]) -> Result<RecordBatch, String> {
             Arc::new(ctoc_array) as ArrayRef,
             Arc::new(ctod_array) as ArrayRef,
             Arc::new(continues_array) as ArrayRef,
+            Arc::new(orphan_array) as ArrayRef,
+            Arc::new(hwq_qd_array) as ArrayRef,
+            Arc::new(hwq_ctoc_array) as ArrayRef,
+            Arc::new(hwq_ctod_array) as ArrayRef,
+            Arc::new(stream_id_array) as ArrayRef,
+            Arc::new(stream_position_array) as ArrayRef,
+            Arc::new(comm_array) as ArrayRef,
+            Arc::new(pattern_array) as ArrayRef,
         ],
     )
     .map_err(|e| e.to_string())
@@ -286,6 +405,7 @@ pub fn save_ufs_to_parquet(
     fname: String,
     timestamp: &str,
     window: Option<&tauri::Window>,
+    provenance: Option<&TraceProvenance>,
 ) -> Result<String, String> {
     // logfolder 내에 stem 폴더 생성
     let stem = PathBuf::from(&fname)
@@ -324,7 +444,12 @@ pub fn save_ufs_to_parquet(
     let first_batch = ufs_to_record_batch(first_chunk)?;
     let schema = first_batch.schema();
     let file = File::create(&path).map_err(|e| e.to_string())?;
-    let mut writer = ArrowWriter::try_new(file, schema.clone(), None).map_err(|e| e.to_string())?;
+    // 출처 정보는 footer key-value metadata로 기록
+    let props = provenance
+        .map(|provenance| provenance.key_value_metadata("ufs"))
+        .transpose()?
+        .map(|kvs| WriterProperties::builder().set_key_value_metadata(Some(kvs)).build());
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), props).map_err(|e| e.to_string())?;
     
         // 첫 번째 Chunk 쓰기
     writer.write(&first_batch).map_err(|e| e.to_string())?;
@@ -393,11 +518,12 @@ pub async fn latencystats(params: UfsLatencyStatsParams) -> Result<Vec<u8>, Stri
 
     // LatencyStat 생성 - column에 따라 데이터 매핑
     let latency_stats = match params.column.as_str() {
+        // 짝이 없는 요청(orphan)은 레이턴시가 의미 없으므로 제외
         "dtoc" | "ctoc" => filtered_ufs
             .iter()
-            .filter(|ufs| ufs.action == "complete_rsp")
+            .filter(|ufs| ufs.action == "complete_rsp" && !ufs.orphan)
             .map(|ufs| LatencyStat {
-                time: ufs.time,
+                time: timestamp::ns_to_secs(ufs.time),
                 opcode: ufs.opcode.clone(),
                 value: if params.column == "dtoc" {
                     LatencyValue::F64(ufs.dtoc)
@@ -408,9 +534,9 @@ pub async fn latencystats(params: UfsLatencyStatsParams) -> Result<Vec<u8>, Stri
             .collect::<Vec<_>>(),
         "ctod" => filtered_ufs
             .iter()
-            .filter(|ufs| ufs.action == "send_req")
+            .filter(|ufs| ufs.action == "send_req" && !ufs.orphan)
             .map(|ufs| LatencyStat {
-                time: ufs.time,
+                time: timestamp::ns_to_secs(ufs.time),
                 opcode: ufs.opcode.clone(),
                 value: LatencyValue::F64(ufs.ctod),
             })
@@ -520,6 +646,18 @@ pub async fn sizestats(params: UfsSizeStatsParams) -> Result<Vec<u8>, String> {
     serde_json::to_vec(&result).map_err(|e| e.to_string())
 }
 
+// send_req의 순차 스트림별 run length / 바이트
+fn send_req_stream_stats<'a>(send_reqs: impl Iterator<Item = &'a UFS>) -> StreamStats {
+    streams::stream_stats(send_reqs.map(|ufs| StreamRequest {
+        stream_id: ufs.stream_id,
+        opcode: &ufs.opcode,
+        lba: ufs.lba,
+        len: ufs.size as u64,
+        bytes: ufs.size as u64 * 4096,
+        time: timestamp::ns_to_secs(ufs.time),
+    }))
+}
+
 // UFS 연속성 통계 함수
 pub async fn continuity_stats(
     logname: String,
@@ -612,6 +750,7 @@ pub async fn continuity_stats(
             continuous_bytes,
             bytes_ratio,
         },
+        streams: Some(send_req_stream_stats(send_reqs.iter().copied())),
     };
 
     serde_json::to_vec(&result).map_err(|e| e.to_string())
@@ -673,7 +812,8 @@ pub async fn allstats(params: UfsAllStatsParams, thresholds: Vec<String>) -> Res
 
     // 전체 통계 한번에 계산
     for ufs in &filtered_ufs {
-        if ufs.action == "complete_rsp" {
+        // 짝이 없는 요청(orphan)은 레이턴시 통계에서 제외
+        if ufs.action == "complete_rsp" && !ufs.orphan {
             // DTOC 레이턴시 통계
             let range_key = create_range_key(ufs.dtoc, &threshold_values, &thresholds);
             if let Some(counts) = dtoc_counts.get_mut(&ufs.opcode) {
@@ -698,13 +838,15 @@ pub async fn allstats(params: UfsAllStatsParams, thresholds: Vec<String>) -> Res
 
         if ufs.action == "send_req" {
             // CTOD 레이턴시 통계
-            let range_key = create_range_key(ufs.ctod, &threshold_values, &thresholds);
-            if let Some(counts) = ctod_counts.get_mut(&ufs.opcode) {
-                if let Some(count) = counts.get_mut(&range_key) {
-                    *count += 1;
+            if !ufs.orphan {
+                let range_key = create_range_key(ufs.ctod, &threshold_values, &thresholds);
+                if let Some(counts) = ctod_counts.get_mut(&ufs.opcode) {
+                    if let Some(count) = counts.get_mut(&range_key) {
+                        *count += 1;
+                    }
                 }
+                ctod_groups.entry(ufs.opcode.clone()).or_default().push(ufs.ctod);
             }
-            ctod_groups.entry(ufs.opcode.clone()).or_default().push(ufs.ctod);
 
             // 연속성 통계 (send_req에서만 연속성이 의미가 있음)
             if ufs.opcode == "0x28" || ufs.opcode == "0x2a" || ufs.opcode == "0x42" {
@@ -827,8 +969,136 @@ pub async fn allstats(params: UfsAllStatsParams, thresholds: Vec<String>) -> Res
                 continuous_bytes,
                 bytes_ratio,
             },
+            streams: Some(send_req_stream_stats(filtered_ufs.iter().filter(|ufs| {
+                ufs.action == "send_req"
+                    && (ufs.opcode == "0x28" || ufs.opcode == "0x2a" || ufs.opcode == "0x42")
+            }))),
         },
+        wall_clock: clock_sync::wall_clock_range(
+            &params.logname,
+            params.time_from,
+            params.time_to,
+            filtered_ufs.iter().map(|ufs| timestamp::ns_to_secs(ufs.time)),
+        ),
     };
 
     serde_json::to_vec(&result).map_err(|e| e.to_string())
 }
+
+// UFS 하드웨어 큐(MCQ)별 통계 - 큐 불균형, 큐별 완료 간격 확인용
+pub async fn hwq_stats(params: UfsAllStatsParams, thresholds: Vec<String>) -> Result<Vec<u8>, String> {
+    let mut threshold_values: Vec<f64> = Vec::new();
+    for t in &thresholds {
+        threshold_values.push(parse_time_to_ms(t)?);
+    }
+
+    let filtered_ufs =
+        filter_ufs_data(&params.logname, params.time_from, params.time_to, &params.zoom_column, params.col_from, params.col_to, None)?;
+
+    let mut queues: BTreeMap<u32, HwqAccumulator> = BTreeMap::new();
+    for ufs in &filtered_ufs {
+        let acc = queues.entry(ufs.hwqid).or_default();
+        match ufs.action.as_str() {
+            "send_req" => {
+                acc.send_count += 1;
+                acc.total_bytes += ufs.size as u64 * 4096; // size는 4KB 단위
+                if !ufs.orphan {
+                    acc.ctod.push(ufs.hwq_ctod);
+                }
+            }
+            "complete_rsp" => {
+                acc.complete_count += 1;
+                // 짝이 없는 요청(orphan)은 레이턴시 통계에서 제외
+                if !ufs.orphan {
+                    acc.qd.push(ufs.hwq_qd as f64);
+                    acc.dtoc.push(ufs.dtoc);
+                    acc.ctoc.push(ufs.hwq_ctoc);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let total_sends: usize = queues.values().map(|q| q.send_count).sum();
+    let max_sends = queues.values().map(|q| q.send_count).max().unwrap_or(0);
+    let imbalance = if total_sends > 0 {
+        max_sends as f64 / (total_sends as f64 / queues.len() as f64)
+    } else {
+        0.0
+    };
+
+    let queues: Vec<HwqStats> = queues
+        .into_iter()
+        .map(|(hwqid, mut acc)| {
+            let mut dtoc_counts = initialize_ranges(&thresholds);
+            for &dtoc in &acc.dtoc {
+                let range_key = create_range_key(dtoc, &threshold_values, &thresholds);
+                if let Some(count) = dtoc_counts.get_mut(&range_key) {
+                    *count += 1;
+                }
+            }
+            HwqStats {
+                hwqid,
+                send_count: acc.send_count,
+                complete_count: acc.complete_count,
+                request_ratio: if total_sends > 0 {
+                    acc.send_count as f64 / total_sends as f64 * 100.0
+                } else {
+                    0.0
+                },
+                total_bytes: acc.total_bytes,
+                qd: calculate_statistics(&mut acc.qd),
+                dtoc: calculate_statistics(&mut acc.dtoc),
+                ctoc: calculate_statistics(&mut acc.ctoc),
+                ctod: calculate_statistics(&mut acc.ctod),
+                dtoc_counts,
+            }
+        })
+        .collect();
+
+    println!("🧮 UFS hwq 통계: {}개 큐, 불균형 {:.2}", queues.len(), imbalance);
+
+    let result = HwqStatsResult {
+        queues,
+        imbalance,
+        wall_clock: clock_sync::wall_clock_range(
+            &params.logname,
+            params.time_from,
+            params.time_to,
+            filtered_ufs.iter().map(|ufs| timestamp::ns_to_secs(ufs.time)),
+        ),
+    };
+
+    serde_json::to_vec(&result).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::test_support::ufs;
+
+    #[test]
+    fn test_per_hwq_queue_depth_and_latency() {
+        let on_hwq1 = |ufs| UFS { hwqid: 1, ..ufs };
+        let rows = ufs_bottom_half_latency_process(vec![
+            ufs(100, "send_req", 1),
+            on_hwq1(ufs(200, "send_req", 2)),
+            ufs(300, "send_req", 3),
+            ufs(400, "complete_rsp", 1),
+            on_hwq1(ufs(500, "complete_rsp", 2)),
+            ufs(900, "complete_rsp", 3),
+            on_hwq1(ufs(1000, "send_req", 4)),
+        ], &StreamOptions::default());
+
+        let qd: Vec<(u32, u32)> = rows.iter().map(|u| (u.qd, u.hwq_qd)).collect();
+        assert_eq!(qd, vec![(1, 1), (2, 1), (3, 2), (2, 1), (1, 0), (0, 0), (1, 1)]);
+
+        // 전체 ctoc는 직전 완료 기준, hwq_ctoc는 같은 큐의 직전 완료 기준
+        assert_eq!(rows[5].ctoc, 0.4);
+        assert_eq!(rows[5].hwq_ctoc, 0.5);
+        // hwq 1은 0.5ms에 비었다가 1.0ms에 다시 시작
+        assert_eq!(rows[6].hwq_ctod, 0.5);
+        assert_eq!(rows[6].ctod, 0.1);
+        assert_eq!(rows[5].dtoc, 0.6);
+    }
+}
@@ -3,24 +3,27 @@ use std::fs::{create_dir_all, File};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, BooleanArray, Float64Array, StringArray, UInt32Array, UInt64Array};
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, UInt32Array, UInt64Array};
 use arrow::datatypes::{DataType, Field, Schema};
 use arrow::record_batch::RecordBatch;
 use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
 use tauri::Emitter;
 
 
+use crate::trace::clock_sync;
+use crate::trace::timestamp;
 use crate::trace::filter::{filter_ufscustom_data};
+use crate::trace::provenance::TraceProvenance;
 use crate::trace::utils::{
-    calculate_statistics, create_range_key, initialize_ranges, parse_time_to_ms,
+    calculate_statistics, create_range_key, initialize_ranges, parse_time_to_ms, pattern_array,
+    pattern_field,
 };
 use crate::trace::{
     ContinuityCount, ContinuityStats, LatencyStat, LatencyStats, LatencyValue, SizeStats,
     TotalContinuity, TraceStats, UFSCUSTOM,
 };
 
-const MILLISECONDS_CONST: u32 = 1000;
-
 // UFSCUSTOM 레이턴시 후처리 함수
 pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>) -> Vec<UFSCUSTOM> {
     // 이벤트가 없으면 빈 벡터 반환
@@ -48,9 +51,7 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
     
     let sort_elapsed = if needs_sort {
         println!("      ⚠️  정렬되지 않은 데이터 감지, 정렬 중...");
-        ufscustom_list.sort_unstable_by(|a, b| {
-            a.start_time.partial_cmp(&b.start_time).unwrap_or(std::cmp::Ordering::Equal)
-        });
+        ufscustom_list.sort_unstable_by_key(|u| u.start_time);
         let elapsed = sort_start.elapsed().as_secs_f64();
         println!("      ✅ 정렬 완료: {:.2}초", elapsed);
         elapsed
@@ -63,7 +64,7 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
     // 이벤트 기반 QD 계산을 위한 구조체
     #[derive(Debug, Clone, Copy)]
     struct Event {
-        time: f64,
+        time: i64,
         event_type: EventType,
         request_idx: usize,
     }
@@ -92,9 +93,7 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
     }
 
     // 시간순으로 이벤트 정렬 (unstable sort)
-    events.sort_unstable_by(|a, b| {
-        a.time.partial_cmp(&b.time).unwrap_or(std::cmp::Ordering::Equal)
-    });
+    events.sort_unstable_by_key(|e| e.time);
 
     // 이벤트 처리하여 각 요청의 start_qd, end_qd 계산
     let mut current_qd = 0u32;
@@ -126,8 +125,8 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
 
     // CTOC, CTOD, continuous 계산
     let mut prev_request: Option<(u64, u32, String)> = None;
-    let mut last_complete_time: Option<f64> = None;
-    let mut last_qd_zero_complete_time: Option<f64> = None; // QD가 0이 될 때의 완료 시간
+    let mut last_complete_time: Option<i64> = None;
+    let mut last_qd_zero_complete_time: Option<i64> = None; // QD가 0이 될 때의 완료 시간
     
     let total_items = ufscustom_list.len();
     let report_threshold = total_items / 20; // 5% 간격
@@ -158,7 +157,7 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
         // CTOC 계산 (Complete to Complete) - 이전 완료에서 현재 완료까지
         ufscustom.ctoc = if let Some(prev_complete) = last_complete_time {
             let time_diff = ufscustom.end_time - prev_complete;
-            if time_diff >= 0.0 { time_diff * MILLISECONDS_CONST as f64 } else { 0.0 }
+            if time_diff >= 0 { timestamp::ns_to_ms(time_diff) } else { 0.0 }
         } else {
             0.0 // 첫 번째 요청
         };
@@ -169,13 +168,13 @@ pub fn ufscustom_bottom_half_latency_process(mut ufscustom_list: Vec<UFSCUSTOM>)
         ufscustom.ctod = if ufscustom.start_qd == 1 {
             if let Some(prev_qd_zero_complete) = last_qd_zero_complete_time {
                 let time_diff = ufscustom.start_time - prev_qd_zero_complete;
-                if time_diff >= 0.0 { time_diff * MILLISECONDS_CONST as f64 } else { 0.0 }
+                if time_diff >= 0 { timestamp::ns_to_ms(time_diff) } else { 0.0 }
             } else {
                 0.0 // 첫 번째 idle 시작 요청
             }
         } else if let Some(prev_complete) = last_complete_time {
             let time_diff = ufscustom.start_time - prev_complete;
-            if time_diff >= 0.0 { time_diff * MILLISECONDS_CONST as f64 } else { 0.0 }
+            if time_diff >= 0 { timestamp::ns_to_ms(time_diff) } else { 0.0 }
         } else {
             0.0 // 첫 번째 요청
         };
@@ -224,28 +223,30 @@ pub fn ufscustom_to_record_batch(ufscustom_list: &[UFSCUSTOM]) -> Result<RecordB
             Field::new("opcode", DataType::Utf8, false),
             Field::new("lba", DataType::UInt64, false),
             Field::new("size", DataType::UInt32, false),
-            Field::new("start_time", DataType::Float64, false),
-            Field::new("end_time", DataType::Float64, false),            
+            timestamp::time_field("start_time"),
+            timestamp::time_field("end_time"),
             Field::new("start_qd", DataType::UInt32, false),
             Field::new("end_qd", DataType::UInt32, false),
             Field::new("dtoc", DataType::Float64, false),
             Field::new("ctoc", DataType::Float64, false),
             Field::new("ctod", DataType::Float64, false),
             Field::new("continuous", DataType::Boolean, false),
+            pattern_field(),
         ]));
         
         let arrays: Vec<ArrayRef> = vec![
             Arc::new(StringArray::from(Vec::<String>::new())),  // opcode
             Arc::new(UInt64Array::from(Vec::<u64>::new())),     // lba
             Arc::new(UInt32Array::from(Vec::<u32>::new())),     // size
-            Arc::new(Float64Array::from(Vec::<f64>::new())),    // start_time
-            Arc::new(Float64Array::from(Vec::<f64>::new())),    // end_time
+            Arc::new(Int64Array::from(Vec::<i64>::new())),      // start_time
+            Arc::new(Int64Array::from(Vec::<i64>::new())),      // end_time
             Arc::new(UInt32Array::from(Vec::<u32>::new())),     // start_qd
             Arc::new(UInt32Array::from(Vec::<u32>::new())),     // end_qd
             Arc::new(Float64Array::from(Vec::<f64>::new())),    // dtoc
             Arc::new(Float64Array::from(Vec::<f64>::new())),    // ctoc
             Arc::new(Float64Array::from(Vec::<f64>::new())),    // ctod
             Arc::new(BooleanArray::from(Vec::<bool>::new())),   // continuous
+            Arc::new(pattern_array(std::iter::empty())),        // pattern
         ];
         
         return RecordBatch::try_new(schema, arrays).map_err(|e| e.to_string());
@@ -285,14 +286,15 @@ pub fn ufscustom_to_record_batch(ufscustom_list: &[UFSCUSTOM]) -> Result<RecordB
         Field::new("opcode", DataType::Utf8, false),
         Field::new("lba", DataType::UInt64, false),
         Field::new("size", DataType::UInt32, false),
-        Field::new("start_time", DataType::Float64, false),
-        Field::new("end_time", DataType::Float64, false),        
+        timestamp::time_field("start_time"),
+        timestamp::time_field("end_time"),
         Field::new("start_qd", DataType::UInt32, false),
         Field::new("end_qd", DataType::UInt32, false),
         Field::new("dtoc", DataType::Float64, false),
         Field::new("ctoc", DataType::Float64, false),
         Field::new("ctod", DataType::Float64, false),
         Field::new("continuous", DataType::Boolean, false),
+        pattern_field(),
     ]));
 
     // ArrayRef 벡터 생성
@@ -300,14 +302,15 @@ pub fn ufscustom_to_record_batch(ufscustom_list: &[UFSCUSTOM]) -> Result<RecordB
         Arc::new(StringArray::from(opcode_vec)),
         Arc::new(UInt64Array::from(lba_vec)),
         Arc::new(UInt32Array::from(size_vec)),
-        Arc::new(Float64Array::from(start_time_vec)),
-        Arc::new(Float64Array::from(end_time_vec)),        
+        Arc::new(Int64Array::from(start_time_vec)),
+        Arc::new(Int64Array::from(end_time_vec)),
         Arc::new(UInt32Array::from(start_qd_vec)),
         Arc::new(UInt32Array::from(end_qd_vec)),
         Arc::new(Float64Array::from(dtoc_vec)),
         Arc::new(Float64Array::from(ctoc_vec)),
         Arc::new(Float64Array::from(ctod_vec)),
         Arc::new(BooleanArray::from(continuous_vec)),
+        Arc::new(pattern_array(ufscustom_list.iter().map(|u| &*u.pattern))),
     ];
 
     RecordBatch::try_new(schema, arrays).map_err(|e| e.to_string())
@@ -320,6 +323,7 @@ pub fn save_ufscustom_to_parquet(
     fname: String,
     timestamp: &str,
     window: Option<&tauri::Window>,
+    provenance: Option<&TraceProvenance>,
 ) -> Result<String, String> {
     // logfolder 내에 stem 폴더 생성
     let stem = PathBuf::from(&fname)
@@ -358,7 +362,12 @@ pub fn save_ufscustom_to_parquet(
     let first_batch = ufscustom_to_record_batch(first_chunk)?;
     let schema = first_batch.schema();
     let file = File::create(&path).map_err(|e| e.to_string())?;
-    let mut writer = ArrowWriter::try_new(file, schema.clone(), None).map_err(|e| e.to_string())?;
+    // 출처 정보는 footer key-value metadata로 기록
+    let props = provenance
+        .map(|provenance| provenance.key_value_metadata("ufscustom"))
+        .transpose()?
+        .map(|kvs| WriterProperties::builder().set_key_value_metadata(Some(kvs)).build());
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), props).map_err(|e| e.to_string())?;
     
     // 첫 번째 Chunk 쓰기
     writer.write(&first_batch).map_err(|e| e.to_string())?;
@@ -467,7 +476,7 @@ pub async fn latencystats(params: UfscustomLatencyStatsParams) -> Result<Vec<u8>
         "dtoc" => filtered_ufscustom
             .iter()
             .map(|ufscustom| LatencyStat {
-                time: ufscustom.start_time,
+                time: timestamp::ns_to_secs(ufscustom.start_time),
                 opcode: ufscustom.opcode.clone(),
                 value: LatencyValue::F64(ufscustom.dtoc),
             })
@@ -475,7 +484,7 @@ pub async fn latencystats(params: UfscustomLatencyStatsParams) -> Result<Vec<u8>
         "ctoc" => filtered_ufscustom
             .iter()
             .map(|ufscustom| LatencyStat {
-                time: ufscustom.start_time,
+                time: timestamp::ns_to_secs(ufscustom.start_time),
                 opcode: ufscustom.opcode.clone(),
                 value: LatencyValue::F64(ufscustom.ctoc),
             })
@@ -483,7 +492,7 @@ pub async fn latencystats(params: UfscustomLatencyStatsParams) -> Result<Vec<u8>
         "ctod" => filtered_ufscustom
             .iter()
             .map(|ufscustom| LatencyStat {
-                time: ufscustom.start_time,
+                time: timestamp::ns_to_secs(ufscustom.start_time),
                 opcode: ufscustom.opcode.clone(),
                 value: LatencyValue::F64(ufscustom.ctod),
             })
@@ -654,6 +663,7 @@ pub async fn continuity_stats(
             continuous_bytes,
             bytes_ratio,
         },
+        streams: None,
     };
 
     // JSON으로 직렬화 후 바이트로 변환
@@ -662,8 +672,8 @@ pub async fn continuity_stats(
 
 // UFSCUSTOM 종합 통계 함수
 pub async fn allstats(params: UfscustomAllStatsParams, thresholds: Vec<String>) -> Result<Vec<u8>, String> {
-    // 필터링 적용 (전체 통계에서는 개별 함수에서 필터링하므로 여기서는 불필요)
-    let _filtered_ufscustom =
+    // 필터링 적용 (통계는 개별 함수에서 다시 필터링하므로 여기서는 실제 시각 범위 계산에만 사용)
+    let filtered_ufscustom =
         filter_ufscustom_data(&params.logname, params.time_from, params.time_to, &params.zoom_column, params.col_from, params.col_to, None)?;
 
     // dtoc 통계 계산
@@ -745,6 +755,12 @@ pub async fn allstats(params: UfscustomAllStatsParams, thresholds: Vec<String>)
         ctoc_stat,
         size_counts,
         continuity,
+        wall_clock: clock_sync::wall_clock_range(
+            &params.logname,
+            params.time_from,
+            params.time_to,
+            filtered_ufscustom.iter().map(|u| timestamp::ns_to_secs(u.start_time)),
+        ),
     };
 
     // JSON으로 직렬화 후 바이트로 변환
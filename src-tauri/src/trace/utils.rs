@@ -2,6 +2,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::io::{Write, Read};
+use std::sync::Arc;
 
 use chrono::Local;
 use datafusion::prelude::*;
@@ -19,16 +20,24 @@ use serde::Serialize;
 use crate::trace::block::{block_bottom_half_latency_process, save_block_to_parquet};
 use crate::trace::ufs::{save_ufs_to_parquet, ufs_bottom_half_latency_process};
 use crate::trace::ufscustom::{save_ufscustom_to_parquet, ufscustom_bottom_half_latency_process, ufscustom_to_record_batch};
-use crate::trace::{Block, LatencySummary, TraceParseResult, BLOCK_CACHE, UFS, UFS_CACHE, UFSCUSTOM, UFSCUSTOM_CACHE, ProgressEvent, CANCEL_SIGNAL};
+use crate::trace::{unnamed_pattern, Block, LatencySummary, PatternMatchCount, TraceParseResult, BLOCK_CACHE, EVENT_CACHE, UFS, UFS_CACHE, UFSCUSTOM, UFSCUSTOM_CACHE, ProgressEvent, CANCEL_SIGNAL};
 
 use crate::trace::filter::{filter_block_data, filter_ufs_data, filter_ufscustom_data};
 use crate::trace::block::block_to_record_batch;
 use crate::trace::ufs::ufs_to_record_batch;
 use crate::trace::constants::{UFS_DEBUG_LBA, MAX_VALID_UFS_LBA};
+use crate::trace::crosslayer::assign_ufs_comm;
 use crate::trace::parser_highperf::parse_log_file_highperf;
+use crate::trace::patterns::named_patterns;
+use crate::trace::clock_sync::{self, ClockSync};
+use crate::trace::timestamp;
+use crate::trace::provenance::{self, ParseReport, PostProcessOptions, SourceLog, TraceProvenance};
 
 use super::{ACTIVE_BLOCK_PATTERN, ACTIVE_UFS_PATTERN, ACTIVE_UFSCUSTOM_PATTERN};
 
+// 라인 청크 파싱 결과 (UFS, Block, UFSCUSTOM, 미인식 라인 번호, 첫 번째 시각 동기화 마커)
+type ChunkParse = (Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>, Vec<usize>, Option<ClockSync>);
+
 // 샘플링 결과를 담는 구조체
 #[derive(Serialize, Debug, Clone)]
 pub struct SamplingInfo<T> {
@@ -179,6 +188,8 @@ pub struct TraceDataBytes {
     pub ufs: ArrowBytes,
     pub block: ArrowBytes,
     pub ufscustom: ArrowBytes,
+    // 트레이스 time -> 실제 시각 변환 정보 (parquet footer에 있는 경우)
+    pub clock_sync: Option<ClockSync>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -305,10 +316,55 @@ pub fn normalize_io_type(io: &str) -> String {
     io.chars().next().unwrap_or_default().to_string()
 }
 
+// pattern 컬럼 - 이름 종류가 활성 패턴 수만큼이므로 dictionary로 저장
+pub(crate) fn pattern_field() -> arrow::datatypes::Field {
+    use arrow::datatypes::DataType;
+    arrow::datatypes::Field::new(
+        "pattern",
+        DataType::Dictionary(Box::new(DataType::UInt16), Box::new(DataType::Utf8)),
+        false,
+    )
+}
+
+pub(crate) fn pattern_array<'a>(
+    names: impl Iterator<Item = &'a str>,
+) -> arrow::array::DictionaryArray<arrow::datatypes::UInt16Type> {
+    names.collect()
+}
+
+// parquet의 pattern 컬럼을 행별 패턴 이름으로 읽음 (같은 이름은 하나의 Arc<str>를 공유)
+// 이전 버전 parquet에는 컬럼이 없고, Utf8 / dictionary 어느 쪽으로 저장됐어도 읽을 수 있음
+fn read_pattern_column(batch: &arrow::record_batch::RecordBatch) -> Result<Vec<Arc<str>>, String> {
+    let Ok(idx) = batch.schema().index_of("pattern") else {
+        return Ok(vec![unnamed_pattern(); batch.num_rows()]);
+    };
+    let column = arrow::compute::cast(batch.column(idx), &arrow::datatypes::DataType::Utf8)
+        .map_err(|e| e.to_string())?;
+    let names = column
+        .as_any()
+        .downcast_ref::<arrow::array::StringArray>()
+        .ok_or("Failed to downcast 'pattern'")?;
+
+    let mut interned: Vec<Arc<str>> = Vec::new();
+    Ok((0..names.len())
+        .map(|row| {
+            let name = names.value(row);
+            if let Some(shared) = interned.iter().find(|shared| &***shared == name) {
+                return Arc::clone(shared);
+            }
+            let shared: Arc<str> = Arc::from(name);
+            interned.push(Arc::clone(&shared));
+            shared
+        })
+        .collect())
+}
+
 // RecordBatch를 Arrow IPC 바이트로 변환하는 헬퍼
 fn batch_to_ipc_bytes(batch: &arrow::record_batch::RecordBatch) -> Result<Vec<u8>, String> {
     let ipc_start = std::time::Instant::now();
     
+    // 화면에는 나노초 시간 컬럼을 초 단위로 전달
+    let batch = &timestamp::seconds_view(batch)?;
     let mut buf = Vec::new();
     let mut writer = StreamWriter::try_new(&mut buf, batch.schema().as_ref()).map_err(|e| e.to_string())?;
     writer.write(batch).map_err(|e| e.to_string())?;
@@ -323,7 +379,7 @@ fn batch_to_ipc_bytes(batch: &arrow::record_batch::RecordBatch) -> Result<Vec<u8
 }
 
 // 파일 인코딩을 자동 감지하고 UTF-8로 변환하는 함수
-fn read_file_with_encoding_detection(path: &Path) -> Result<String, String> {
+pub(crate) fn read_file_with_encoding_detection(path: &Path) -> Result<String, String> {
     let mut file = File::open(path).map_err(|e| e.to_string())?;
     
     // 파일의 일부를 읽어서 인코딩 감지 (최대 8KB)
@@ -499,6 +555,7 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                     sampled_count: ufscustom_sample_info.sampled_count,
                     sampling_ratio: ufscustom_sample_info.sampling_ratio,
                 },
+                clock_sync: clock_sync::clock_sync_for_logname(&logname),
             });
         }
     }
@@ -584,16 +641,9 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                         .as_any()
                         .downcast_ref::<arrow::array::UInt32Array>()
                         .ok_or("Failed to downcast 'size'")?;
-                    let start_time_array = batch
-                        .column(start_time_idx)
-                        .as_any()
-                        .downcast_ref::<arrow::array::Float64Array>()
-                        .ok_or("Failed to downcast 'start_time'")?;
-                    let end_time_array = batch
-                        .column(end_time_idx)
-                        .as_any()
-                        .downcast_ref::<arrow::array::Float64Array>()
-                        .ok_or("Failed to downcast 'end_time'")?;
+                    // 이전 버전 parquet(Float64 초)도 나노초로 변환하여 읽음
+                    let start_time_ns = timestamp::time_column_ns(batch.column(start_time_idx).as_ref(), "start_time")?;
+                    let end_time_ns = timestamp::time_column_ns(batch.column(end_time_idx).as_ref(), "end_time")?;
                     let dtoc_array = batch
                         .column(dtoc_idx)
                         .as_any()
@@ -625,20 +675,23 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                         .downcast_ref::<arrow::array::BooleanArray>()
                         .ok_or("Failed to downcast 'continuous'")?;
 
+                    let pattern_names = read_pattern_column(&batch)?;
+
                     // 배열에서 값을 추출하여 UFSCUSTOM 객체 생성
                     for row in 0..num_rows {
                         ufscustom_vec.push(UFSCUSTOM {
                             opcode: opcode_array.value(row).to_string(),
                             lba: lba_array.value(row),
                             size: size_array.value(row),
-                            start_time: start_time_array.value(row),
-                            end_time: end_time_array.value(row),
+                            start_time: start_time_ns[row],
+                            end_time: end_time_ns[row],
                             dtoc: dtoc_array.value(row),
                             start_qd: start_qd_array.value(row),
                             end_qd: end_qd_array.value(row),
                             ctoc: ctoc_array.value(row),
                             ctod: ctod_array.value(row),
                             continuous: cont_array.value(row),
+                            pattern: Arc::clone(&pattern_names[row]),
                         });
                     }
                 }
@@ -679,11 +732,8 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                     let cont_idx = schema.index_of("continuous").map_err(|e| e.to_string())?;
 
                     // 각 칼럼 배열 다운캐스팅
-                    let time_array = batch
-                        .column(time_idx)
-                        .as_any()
-                        .downcast_ref::<arrow::array::Float64Array>()
-                        .ok_or("Failed to downcast 'time'")?;
+                    // 이전 버전 parquet(Float64 초)도 나노초로 변환하여 읽음
+                    let time_ns = timestamp::time_column_ns(batch.column(time_idx).as_ref(), "time")?;
                     let process_array = batch
                         .column(process_idx)
                         .as_any()
@@ -755,10 +805,55 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                         .downcast_ref::<arrow::array::BooleanArray>()
                         .ok_or("Failed to downcast 'continuous'")?;
 
+                    let pattern_names = read_pattern_column(&batch)?;
+                    // orphan, hwq_*, stream_* 컬럼도 이전 버전 parquet에는 없음
+                    let orphan_array = schema.index_of("orphan").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::BooleanArray>()
+                    });
+                    let hwq_qd_array = schema.index_of("hwq_qd").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::UInt32Array>()
+                    });
+                    let hwq_ctoc_array = schema.index_of("hwq_ctoc").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::Float64Array>()
+                    });
+                    let hwq_ctod_array = schema.index_of("hwq_ctod").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::Float64Array>()
+                    });
+                    let stream_id_array = schema.index_of("stream_id").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::UInt32Array>()
+                    });
+                    let stream_position_array = schema.index_of("stream_position").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::UInt32Array>()
+                    });
+                    let comm_array = schema.index_of("comm").ok().and_then(|idx| {
+                        batch
+                            .column(idx)
+                            .as_any()
+                            .downcast_ref::<arrow::array::StringViewArray>()
+                    });
+
                     // 배열에서 값을 추출하여 UFS 객체 생성
                     for row in 0..num_rows {
                         ufs_vec.push(UFS {
-                            time: time_array.value(row),
+                            time: time_ns[row],
                             process: process_array.value(row).to_string(),
                             cpu: cpu_array.value(row),
                             action: action_array.value(row).to_string(),
@@ -773,6 +868,14 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                             ctoc: ctoc_array.value(row),
                             ctod: ctod_array.value(row),
                             continuous: cont_array.value(row),
+                            orphan: orphan_array.is_some_and(|a| a.value(row)),
+                            hwq_qd: hwq_qd_array.map_or(0, |a| a.value(row)),
+                            hwq_ctoc: hwq_ctoc_array.map_or(0.0, |a| a.value(row)),
+                            hwq_ctod: hwq_ctod_array.map_or(0.0, |a| a.value(row)),
+                            stream_id: stream_id_array.map_or(0, |a| a.value(row)),
+                            stream_position: stream_position_array.map_or(0, |a| a.value(row)),
+                            comm: comm_array.map_or_else(String::new, |a| a.value(row).to_string()),
+                            pattern: Arc::clone(&pattern_names[row]),
                         });
                     }
                 }
@@ -814,11 +917,8 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                     let cont_idx = schema.index_of("continuous").map_err(|e| e.to_string())?;
 
                     // 각 칼럼 배열 다운캐스팅
-                    let time_array = batch
-                        .column(time_idx)
-                        .as_any()
-                        .downcast_ref::<arrow::array::Float64Array>()
-                        .ok_or("Failed to downcast 'time'")?;
+                    // 이전 버전 parquet(Float64 초)도 나노초로 변환하여 읽음
+                    let time_ns = timestamp::time_column_ns(batch.column(time_idx).as_ref(), "time")?;
                     let process_array = batch
                         .column(process_idx)
                         .as_any()
@@ -900,10 +1000,12 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                         .downcast_ref::<arrow::array::BooleanArray>()
                         .ok_or("Failed to downcast 'continuous'")?;
 
+                    let pattern_names = read_pattern_column(&batch)?;
+
                     // 배열에서 값을 추출하여 Block 객체 생성
                     for row in 0..num_rows {
                         block_vec.push(Block {
-                            time: time_array.value(row),
+                            time: time_ns[row],
                             process: process_array.value(row).to_string(),
                             cpu: cpu_array.value(row),
                             flags: flags_array.value(row).to_string(),
@@ -920,6 +1022,7 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
                             ctoc: ctoc_array.value(row),
                             ctod: ctod_array.value(row),
                             continuous: cont_array.value(row),
+                            pattern: Arc::clone(&pattern_names[row]),
                         });
                     }
                 }
@@ -1011,6 +1114,7 @@ pub async fn readtrace(logname: String, max_records: usize) -> Result<TraceDataB
             sampled_count: ufscustom_sample_info.sampled_count,
             sampling_ratio: ufscustom_sample_info.sampling_ratio,
         },
+        clock_sync: clock_sync::clock_sync_for_logname(&logname),
     })
 }
 
@@ -1114,7 +1218,12 @@ pub async fn trace_lengths(logname: String) -> Result<TraceLengths, String> {
 }
 
 // 로그 파일 파싱 및 parquet 저장 함수
-pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window) -> Result<TraceParseResult, String> {
+pub async fn starttrace(
+    fname: String,
+    logfolder: String,
+    window: tauri::Window,
+    options: PostProcessOptions,
+) -> Result<TraceParseResult, String> {
     spawn_blocking(move || {
         // 파일 정보 확인
         let file_meta = match std::fs::metadata(&fname) {
@@ -1125,6 +1234,12 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
         // 파일 크기 확인 및 출력
         let file_size = file_meta.len();
         println!("로그 파일 크기: {} bytes ({:.2} GB)", file_size, file_size as f64 / 1_073_741_824.0);
+
+        // 출처 정보(parquet footer)에 기록할 로그 해시는 파싱과 동시에 계산
+        let hash_handle = {
+            let path = fname.clone();
+            std::thread::spawn(move || provenance::hash_file(&path))
+        };
         
         // 진행 상태 초기 이벤트 전송
         let _ = window.emit("trace-progress", ProgressEvent {
@@ -1148,13 +1263,31 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             let parse_start = std::time::Instant::now();
             
             // 고성능 파서로 파싱 (window 전달)
-            let (mut ufs_list, mut block_list, mut ufscustom_list) = match parse_log_file_highperf(&fname, Some(&window)) {
+            // 시각 동기화 마커(tracing_mark_write의 realtime_ts / epoch)는 파싱 중 함께 검색
+            let (mut ufs_list, mut block_list, mut ufscustom_list, sync_marker) = match parse_log_file_highperf(&fname, Some(&window)) {
                 Ok(result) => result,
                 Err(e) => return Err(format!("고성능 파서 실행 실패: {}", e)),
             };
             
             let parse_time = parse_start.elapsed().as_secs_f64();
             println!("⏱️  고성능 파서 파싱 시간: {:.2}초", parse_time);
+
+            let (pattern_matches, patterns) = {
+                let active_ufs = ACTIVE_UFS_PATTERN.read().map_err(|e| e.to_string())?;
+                let active_block = ACTIVE_BLOCK_PATTERN.read().map_err(|e| e.to_string())?;
+                let active_ufscustom = ACTIVE_UFSCUSTOM_PATTERN.read().map_err(|e| e.to_string())?;
+                (
+                    collect_pattern_matches(
+                        &active_ufs,
+                        &active_block,
+                        &active_ufscustom,
+                        &ufs_list,
+                        &block_list,
+                        &ufscustom_list,
+                    ),
+                    provenance::active_patterns(&active_ufs, &active_block, &active_ufscustom),
+                )
+            };
             
             // 진행 상태 업데이트: 후처리 시작
             let _ = window.emit("trace-progress", ProgressEvent {
@@ -1172,18 +1305,26 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             
             if !ufs_list.is_empty() {
                 println!("🔄 UFS 후처리 시작...");
-                ufs_list = ufs_bottom_half_latency_process(ufs_list);
+                ufs_list = ufs_bottom_half_latency_process(ufs_list, &options.ufs_streams);
             }
             
+            let mut block_dedup = None;
             if !block_list.is_empty() {
                 println!("🔄 Block 후처리 시작...");
-                block_list = block_bottom_half_latency_process(block_list);
+                let (processed, report) = block_bottom_half_latency_process(block_list, options.block_dedup);
+                block_list = processed;
+                block_dedup = Some(report);
             }
             
             if !ufscustom_list.is_empty() {
                 println!("🔄 UFSCustom 후처리 시작...");
                 ufscustom_list = ufscustom_bottom_half_latency_process(ufscustom_list);
             }
+
+            // UFS 명령에 요청을 발생시킨 block comm 기록
+            if !ufs_list.is_empty() && !block_list.is_empty() {
+                assign_ufs_comm(&block_list, &mut ufs_list);
+            }
             
             let postprocess_time = postprocess_start.elapsed().as_secs_f64();
             println!("⏱️  후처리 시간: {:.2}초", postprocess_time);
@@ -1195,6 +1336,27 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             // Parquet 저장
             let save_start = std::time::Instant::now();
             let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+
+            let provenance = TraceProvenance::new(
+                SourceLog {
+                    path: fname.clone(),
+                    size: file_size,
+                    sha256: provenance::join_hash(hash_handle),
+                },
+                patterns,
+                "highperf",
+                ParseReport {
+                    total_lines: None,
+                    missing_lines: None,
+                    ufs: ufs_list.len(),
+                    block: block_list.len(),
+                    ufscustom: ufscustom_list.len(),
+                    pattern_matches: pattern_matches.clone(),
+                    block_dedup: block_dedup.clone(),
+                },
+            )
+            .with_post_processing(options.clone())
+            .with_clock_sync(clock_sync::report_marker(sync_marker));
             
             // 진행 상태 업데이트: 파일 저장 중
             let _ = window.emit("trace-progress", ProgressEvent {
@@ -1215,6 +1377,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                     fname.clone(),
                     &timestamp,
                     Some(&window),
+                    Some(&provenance),
                 )?
             } else {
                 String::new()
@@ -1228,6 +1391,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                     fname.clone(),
                     &timestamp,
                     Some(&window),
+                    Some(&provenance),
                 )?
             } else {
                 String::new()
@@ -1241,6 +1405,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                     fname.clone(),
                     &timestamp,
                     Some(&window),
+                    Some(&provenance),
                 )?
             } else {
                 String::new()
@@ -1272,6 +1437,8 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                 ufs_parquet_filename,
                 block_parquet_filename,
                 ufscustom_parquet_filename,
+                pattern_matches,
+                block_dedup,
             });
         }
         
@@ -1330,13 +1497,16 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
         let mut block_list: Vec<Block> = Vec::new();
         let mut ufscustom_list: Vec<UFSCUSTOM> = Vec::new();
         let mut missing_lines: Vec<usize> = Vec::new();
+        let mut missing_count = 0;
+        // 시각 동기화 마커(tracing_mark_write의 realtime_ts / epoch)는 파싱 중 함께 검색
+        let mut sync_marker: Option<ClockSync> = None;
 
         // 라인별 병렬 처리
         let lines: Vec<&str> = content.lines().collect();
         let total_lines = lines.len();
         println!("All Line Count: {}", total_lines);
 
-        // 현재 활성화된 패턴 가져오기
+        // 현재 활성화된 패턴 목록 가져오기 (타입별로 순서대로 시도)
         let active_ufs_pattern = match ACTIVE_UFS_PATTERN.read() {
             Ok(pattern) => pattern,
             Err(e) => return Err(format!("UFS 패턴 로드 실패: {}", e)),
@@ -1352,6 +1522,11 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             Err(e) => return Err(format!("UFSCUSTOM 패턴 로드 실패: {}", e)),
         };
 
+        // 라인 파싱용 (이름, 정규식) - 이벤트는 패턴 이름 Arc<str>를 공유
+        let named_ufs_pattern = named_patterns(&active_ufs_pattern);
+        let named_block_pattern = named_patterns(&active_block_pattern);
+        let named_ufscustom_pattern = named_patterns(&active_ufscustom_pattern);
+
         // 진행 상황 표시용 변수
         let mut last_progress = 0;
         let start_time = std::time::Instant::now();
@@ -1412,41 +1587,49 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             let chunk_slice = &lines[chunk_start..chunk_end];
 
             // 청크 병렬 처리
-            let chunk_results: (Vec<UFS>, Vec<Block>, Vec<UFSCUSTOM>, Vec<usize>) = chunk_slice
+            let chunk_results: ChunkParse = chunk_slice
                 .par_iter()
                 .enumerate()
                 .map(|(i, &line)| {
                     let line_number = chunk_start + i + 1; // 실제 라인 번호 계산
                     if line.trim().is_empty() {
-                        return (Vec::new(), Vec::new(), Vec::new(), vec![line_number]);
+                        return (Vec::new(), Vec::new(), Vec::new(), vec![line_number], None);
                     }
 
                     // UFSCUSTOM 패턴으로 먼저 파싱 시도
-                    let ufscustom_caps = active_ufscustom_pattern.1.captures(line);
-                    if let Some(caps) = ufscustom_caps {
-                        if let Ok(ufscustom) = parse_ufscustom_trace_with_caps(&caps) {
-                            return (Vec::new(), Vec::new(), vec![ufscustom], Vec::new());
-                        }
+                    if let Some((mut ufscustom, name)) = parse_with_patterns(
+                        line,
+                        &named_ufscustom_pattern,
+                        parse_ufscustom_trace_with_caps,
+                    ) {
+                        ufscustom.pattern = Arc::clone(name);
+                        return (Vec::new(), Vec::new(), vec![ufscustom], Vec::new(), None);
                     }
 
                     // UFS 패턴으로 파싱 시도
-                    let ufs_caps = active_ufs_pattern.1.captures(line);
-                    if let Some(caps) = ufs_caps {
-                        if let Ok(ufs) = parse_ufs_trace_with_caps(&caps) {
-                            return (vec![ufs], Vec::new(), Vec::new(), Vec::new());
-                        }
+                    if let Some((mut ufs, name)) =
+                        parse_with_patterns(line, &named_ufs_pattern, parse_ufs_trace_with_caps)
+                    {
+                        ufs.pattern = Arc::clone(name);
+                        return (vec![ufs], Vec::new(), Vec::new(), Vec::new(), None);
                     }
 
                     // Block 패턴으로 파싱 시도
-                    let block_caps = active_block_pattern.1.captures(line);
-                    if let Some(caps) = block_caps {
-                        if let Ok(block) = parse_block_trace_with_caps(&caps) {
-                            return (Vec::new(), vec![block], Vec::new(), Vec::new());
-                        }
+                    if let Some((mut block, name)) =
+                        parse_with_patterns(line, &named_block_pattern, parse_block_trace_with_caps)
+                    {
+                        block.pattern = Arc::clone(name);
+                        return (Vec::new(), vec![block], Vec::new(), Vec::new(), None);
                     }
 
-                    // 어떤 패턴과도 일치하지 않음
-                    (Vec::new(), Vec::new(), Vec::new(), vec![line_number])
+                    // 어떤 패턴과도 일치하지 않음 - 시각 동기화 마커인지 확인
+                    (
+                        Vec::new(),
+                        Vec::new(),
+                        Vec::new(),
+                        vec![line_number],
+                        clock_sync::clock_sync_from_unmatched(line),
+                    )
                 })
                 .reduce(
                     || {
@@ -1455,15 +1638,17 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                             Vec::with_capacity(chunk_size / 4),
                             Vec::with_capacity(chunk_size / 4),
                             Vec::new(),
+                            None,
                         )
                     },
-                    |(mut acc_ufs, mut acc_block, mut acc_ufscustom, mut acc_missing),
-                     (ufs_vec, block_vec, ufscustom_vec, missing_vec)| {
+                    // reduce는 순서를 유지하므로 앞쪽 라인의 마커가 우선
+                    |(mut acc_ufs, mut acc_block, mut acc_ufscustom, mut acc_missing, acc_sync),
+                     (ufs_vec, block_vec, ufscustom_vec, missing_vec, sync)| {
                         acc_ufs.extend(ufs_vec);
                         acc_block.extend(block_vec);
                         acc_ufscustom.extend(ufscustom_vec);
                         acc_missing.extend(missing_vec);
-                        (acc_ufs, acc_block, acc_ufscustom, acc_missing)
+                        (acc_ufs, acc_block, acc_ufscustom, acc_missing, acc_sync.or(sync))
                     },
                 );
 
@@ -1471,7 +1656,10 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             ufs_list.extend(chunk_results.0);
             block_list.extend(chunk_results.1);
             ufscustom_list.extend(chunk_results.2);
+            sync_marker = sync_marker.or(chunk_results.4);
             
+            missing_count += chunk_results.3.len();
+
             // missing_lines가 너무 많으면 처음 1000개만 저장 (메모리 절약)
             if missing_lines.len() < 1000 {
                 missing_lines.extend(chunk_results.3);
@@ -1495,6 +1683,15 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                  } else { 
                      missing_lines.len().to_string()
                  });
+
+        let pattern_matches = collect_pattern_matches(
+            &active_ufs_pattern,
+            &active_block_pattern,
+            &active_ufscustom_pattern,
+            &ufs_list,
+            &block_list,
+            &ufscustom_list,
+        );
         
         // 진행 상태 업데이트: latency 계산 시작
         let _ = window.emit("trace-progress", ProgressEvent {
@@ -1526,7 +1723,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
         
         // UFS latency 처리
         let ufs_start = std::time::Instant::now();
-        let processed_ufs_list = ufs_bottom_half_latency_process(ufs_list);
+        let mut processed_ufs_list = ufs_bottom_half_latency_process(ufs_list, &options.ufs_streams);
         let ufs_elapsed = ufs_start.elapsed().as_secs_f32();
         
         // 진행 상태 업데이트: UFS 처리 완료
@@ -1551,7 +1748,9 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
         // Block latency 처리
         println!("Block latency 처리 시작...");
         let block_start = std::time::Instant::now();
-        let processed_block_list = block_bottom_half_latency_process(block_list);
+        let (processed_block_list, block_dedup_report) =
+            block_bottom_half_latency_process(block_list, options.block_dedup);
+        let block_dedup = (!processed_block_list.is_empty()).then_some(block_dedup_report);
         let block_elapsed = block_start.elapsed().as_secs_f32();
         
         // 진행 상태 업데이트: Block 처리 완료
@@ -1590,10 +1789,40 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             processing_speed: if ufscustom_elapsed > 0.0 { processed_ufscustom_list.len() as f32 / ufscustom_elapsed } else { 0.0 },
         });
 
+        // UFS 명령에 요청을 발생시킨 block comm 기록
+        if !processed_ufs_list.is_empty() && !processed_block_list.is_empty() {
+            assign_ufs_comm(&processed_block_list, &mut processed_ufs_list);
+        }
+
         // 공통 timestamp 생성
         let now = Local::now();
         let timestamp = now.format("%Y%m%d_%H%M%S").to_string();
 
+        let provenance = TraceProvenance::new(
+            SourceLog {
+                path: fname.clone(),
+                size: file_size,
+                sha256: provenance::join_hash(hash_handle),
+            },
+            provenance::active_patterns(
+                &active_ufs_pattern,
+                &active_block_pattern,
+                &active_ufscustom_pattern,
+            ),
+            "legacy",
+            ParseReport {
+                total_lines: Some(total_lines),
+                missing_lines: Some(missing_count),
+                ufs: processed_ufs_list.len(),
+                block: processed_block_list.len(),
+                ufscustom: processed_ufscustom_list.len(),
+                pattern_matches: pattern_matches.clone(),
+                block_dedup: block_dedup.clone(),
+            },
+        )
+        .with_post_processing(options.clone())
+        .with_clock_sync(clock_sync::report_marker(sync_marker));
+
         // 진행 상태 업데이트: 파일 저장 시작
         let _ = window.emit("trace-progress", ProgressEvent {
             stage: "saving".to_string(),
@@ -1636,6 +1865,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                 fname.clone(),
                 &timestamp,
                 Some(&window),
+                Some(&provenance),
             )?
         } else {
             String::new()
@@ -1670,6 +1900,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                 fname.clone(),
                 &timestamp,
                 Some(&window),
+                Some(&provenance),
             )?
         } else {
             String::new()
@@ -1704,6 +1935,7 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
                 fname.clone(),
                 &timestamp,
                 Some(&window),
+                Some(&provenance),
             )?
         } else {
             String::new()
@@ -1737,18 +1969,90 @@ pub async fn starttrace(fname: String, logfolder: String, window: tauri::Window)
             ufs_parquet_filename,
             block_parquet_filename,
             ufscustom_parquet_filename,
+            pattern_matches,
+            block_dedup,
         })
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+/// 순서 있는 활성 패턴 목록으로 한 라인 파싱
+/// 매치되고 변환까지 성공한 첫 번째 패턴의 이름과 함께 반환
+pub fn parse_with_patterns<'a, T>(
+    line: &str,
+    patterns: &'a [(Arc<str>, regex::Regex)],
+    parse: impl Fn(&regex::Captures) -> Result<T, String>,
+) -> Option<(T, &'a Arc<str>)> {
+    patterns.iter().find_map(|(name, regex)| {
+        let caps = regex.captures(line)?;
+        parse(&caps).ok().map(|event| (event, name))
+    })
+}
+
+// 한 타입의 활성 패턴별 파싱 이벤트 수 집계 (활성 목록 순서 유지)
+fn count_pattern_matches<'a>(
+    pattern_type: &str,
+    active: &[(String, regex::Regex)],
+    matched: impl Iterator<Item = &'a str>,
+) -> Vec<PatternMatchCount> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in matched {
+        *counts.entry(name).or_insert(0) += 1;
+    }
+
+    active
+        .iter()
+        .enumerate()
+        .map(|(order, (name, _))| {
+            let count = counts.get(name.as_str()).copied().unwrap_or(0);
+            println!("  - {} 패턴 #{} '{}': {} 이벤트", pattern_type, order, name, count);
+            PatternMatchCount {
+                pattern_type: pattern_type.to_string(),
+                name: name.clone(),
+                order,
+                count,
+            }
+        })
+        .collect()
+}
+
+// 파싱 리포트용 패턴별 매치 수 (UFS, Block, UFSCUSTOM 순)
+fn collect_pattern_matches(
+    active_ufs: &[(String, regex::Regex)],
+    active_block: &[(String, regex::Regex)],
+    active_ufscustom: &[(String, regex::Regex)],
+    ufs_list: &[UFS],
+    block_list: &[Block],
+    ufscustom_list: &[UFSCUSTOM],
+) -> Vec<PatternMatchCount> {
+    println!("패턴별 매치 수:");
+    let mut result = count_pattern_matches("ufs", active_ufs, ufs_list.iter().map(|u| &*u.pattern));
+    result.extend(count_pattern_matches(
+        "block",
+        active_block,
+        block_list.iter().map(|b| &*b.pattern),
+    ));
+    result.extend(count_pattern_matches(
+        "ufscustom",
+        active_ufscustom,
+        ufscustom_list.iter().map(|u| &*u.pattern),
+    ));
+    result
+}
+
+// 16진수 필드(group_id) 변환 - 0x 접두사는 있어도 없어도 16진수로 해석
+pub fn parse_hex_u32(value: &str) -> Option<u32> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    u32::from_str_radix(digits, 16).ok()
+}
+
 // Captures가 이미 있는 경우 UFS 파싱 (중복 코드 방지)
 pub fn parse_ufs_trace_with_caps(caps: &regex::Captures) -> Result<UFS, String> {
     // Named captures 사용
     let time = caps
         .name("time")
-        .and_then(|m| m.as_str().parse::<f64>().ok())
+        .and_then(|m| timestamp::parse_seconds(m.as_str()))
         .ok_or("time parse error")?;
     let process = caps
         .name("process")
@@ -1786,7 +2090,7 @@ pub fn parse_ufs_trace_with_caps(caps: &regex::Captures) -> Result<UFS, String>
         .unwrap_or_default();
     let groupid = caps
         .name("group_id")
-        .and_then(|m| u32::from_str_radix(m.as_str(), 16).ok())
+        .and_then(|m| parse_hex_u32(m.as_str()))
         .ok_or("group_id parse error")?;
     let hwqid = caps
         .name("hwq_id")
@@ -1809,6 +2113,14 @@ pub fn parse_ufs_trace_with_caps(caps: &regex::Captures) -> Result<UFS, String>
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        orphan: false,
+        hwq_qd: 0,
+        hwq_ctoc: 0.0,
+        hwq_ctod: 0.0,
+        stream_id: 0,
+        stream_position: 0,
+        comm: String::new(),
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -1824,17 +2136,17 @@ pub fn parse_ufscustom_trace_with_caps(caps: &regex::Captures) -> Result<UFSCUST
         .name("size")
         .and_then(|m| m.as_str().parse().ok())
         .unwrap_or(0);
-    let start_time: f64 = caps
+    let start_time: i64 = caps
         .name("start_time")
-        .and_then(|m| m.as_str().parse().ok())
-        .unwrap_or(0.0);
-    let end_time: f64 = caps
+        .and_then(|m| timestamp::parse_seconds(m.as_str()))
+        .unwrap_or(0);
+    let end_time: i64 = caps
         .name("end_time")
-        .and_then(|m| m.as_str().parse().ok())
-        .unwrap_or(0.0);
+        .and_then(|m| timestamp::parse_seconds(m.as_str()))
+        .unwrap_or(0);
 
-    // dtoc 계산 (밀리초 단위)
-    let dtoc = (end_time - start_time) * 1000.0;
+    // dtoc 계산 (나노초 차이 -> 밀리초)
+    let dtoc = timestamp::ns_to_ms(end_time - start_time);
 
     Ok(UFSCUSTOM {
         opcode,
@@ -1848,6 +2160,7 @@ pub fn parse_ufscustom_trace_with_caps(caps: &regex::Captures) -> Result<UFSCUST
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -1855,7 +2168,7 @@ pub fn parse_block_trace_with_caps(caps: &regex::Captures) -> Result<Block, Stri
     // Named captures 사용
     let time = caps
         .name("time")
-        .and_then(|m| m.as_str().parse::<f64>().ok())
+        .and_then(|m| timestamp::parse_seconds(m.as_str()))
         .ok_or("time parse error")?;
     let process = caps
         .name("process")
@@ -1924,6 +2237,7 @@ pub fn parse_block_trace_with_caps(caps: &regex::Captures) -> Result<Block, Stri
         ctoc: 0.0,
         ctod: 0.0,
         continuous: false,
+        pattern: unnamed_pattern(),
     })
 }
 
@@ -2004,6 +2318,7 @@ async fn filter_block_trace(
             sampled_count: 0,
             sampling_ratio: 100.0,
         },
+        clock_sync: clock_sync::clock_sync_for_logname(logname),
     })
 }
 
@@ -2071,6 +2386,7 @@ async fn filter_ufs_trace(
             sampled_count: 0,
             sampling_ratio: 100.0,
         },
+        clock_sync: clock_sync::clock_sync_for_logname(logname),
     })
 }
 
@@ -2135,6 +2451,7 @@ async fn filter_ufscustom_trace(
             sampled_count,
             sampling_ratio,
         },
+        clock_sync: clock_sync::clock_sync_for_logname(logname),
     })
 }
 
@@ -2174,6 +2491,14 @@ pub async fn clear_all_cache() -> Result<String, String> {
         block_cache.clear();
         println!("  - Block 캐시 초기화: {} 항목 삭제", block_count);
     }
+
+    // 이벤트 테이블 캐시 초기화
+    {
+        let mut event_cache = EVENT_CACHE.lock().map_err(|e| e.to_string())?;
+        let event_count = event_cache.len();
+        event_cache.clear();
+        println!("  - 이벤트 테이블 캐시 초기화: {} 항목 삭제", event_count);
+    }
     
     println!("✅ 모든 캐시 초기화 완료");
     Ok("캐시가 성공적으로 초기화되었습니다.".to_string())
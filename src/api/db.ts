@@ -63,6 +63,17 @@ export async function initial() {
             created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
         );
     `).catch((e) => { console.log('error creating trace_patterns table', e); });
+
+    // 번들 내보내기/가져오기용 sample line 컬럼 (JSON 배열, 기존 테이블에는 없음)
+    try {
+        const patternTableInfo = await db.select("PRAGMA table_info(trace_patterns)");
+        if (!patternTableInfo.some(col => col.name === 'sample_lines')) {
+            console.log('Adding sample_lines column to trace_patterns table');
+            await db.execute('ALTER TABLE trace_patterns ADD COLUMN sample_lines TEXT;');
+        }
+    } catch(e) {
+        console.error('Error checking or adding sample_lines column:', e);
+    }
     
     // Insert default patterns if the table is empty
     const patterns = await db.select('SELECT COUNT(*) as count FROM trace_patterns');
@@ -93,6 +104,21 @@ export async function initial() {
         ]);
     }
     
+    // Default UFSCUSTOM pattern (기존 DB에는 ufscustom 패턴이 없을 수 있으므로 타입별로 확인)
+    const ufscustomPatterns = await db.select("SELECT COUNT(*) as count FROM trace_patterns WHERE type = 'ufscustom'");
+    if (ufscustomPatterns[0].count === 0) {
+        await db.execute(`
+            INSERT INTO trace_patterns (name, type, pattern, description, is_active)
+            VALUES (?, ?, ?, ?, ?)
+        `, [
+            'Default UFS Custom Pattern',
+            'ufscustom',
+            '^(?P<opcode>0x[0-9a-f]+),(?P<lba>\\d+),(?P<size>\\d+),(?P<start_time>\\d+(?:\\.\\d+)?),(?P<end_time>\\d+(?:\\.\\d+)?)$',
+            'Default pattern for parsing UFSCUSTOM CSV traces',
+            1
+        ]);
+    }
+    
     const result:number[] = await db.select('SELECT * FROM buffersize');
     if(result.length === 0) {
         await db.execute('INSERT OR REPLACE INTO buffersize (id, buffersize) VALUES (1, 500000);');
@@ -220,22 +246,22 @@ export async function getActivePatterns() {
 /**
  * Add a new pattern
  */
-export async function addPattern(name: string, type: string, pattern: string, description: string) {
+export async function addPattern(name: string, type: string, pattern: string, description: string, sampleLines: string[] = []) {
     await open();
     await db.execute(
-        'INSERT INTO trace_patterns (name, type, pattern, description) VALUES (?, ?, ?, ?)',
-        [name, type, pattern, description]
+        'INSERT INTO trace_patterns (name, type, pattern, description, sample_lines) VALUES (?, ?, ?, ?, ?)',
+        [name, type, pattern, description, JSON.stringify(sampleLines)]
     );
 }
 
 /**
  * Update an existing pattern
  */
-export async function updatePattern(id: number, name: string, pattern: string, description: string) {
+export async function updatePattern(id: number, name: string, pattern: string, description: string, sampleLines: string[] = []) {
     await open();
     await db.execute(
-        'UPDATE trace_patterns SET name = ?, pattern = ?, description = ? WHERE id = ?',
-        [name, pattern, description, id]
+        'UPDATE trace_patterns SET name = ?, pattern = ?, description = ?, sample_lines = ? WHERE id = ?',
+        [name, pattern, description, JSON.stringify(sampleLines), id]
     );
 }
 
@@ -0,0 +1,191 @@
+/**
+ * ftrace 이벤트 format 기반 파서 API
+ *
+ * /sys/kernel/tracing/events/<sys>/<event>/format 파일로 이벤트별 파서를 만들고
+ * 로그에 적용하여 이벤트별 parquet 파일을 생성합니다.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface EventColumn {
+    name: string;
+    kind: 'int' | 'uint' | 'hex' | 'text';
+    field: string | null;
+    arg: string;
+}
+
+export interface EventParser {
+    system: string;
+    name: string;
+    id: number | null;
+    fields: { c_type: string; name: string; offset: number; size: number; signed: boolean }[];
+    columns: EventColumn[];
+    pattern: string;
+}
+
+export interface FormatLoadReport {
+    loaded: EventParser[];
+    failed: { path: string; error: string }[];
+}
+
+export interface EventParseReport {
+    total_lines: number;
+    files: { event: string; system: string; filename: string; rows: number; columns: string[] }[];
+    mismatched: Record<string, number>;
+}
+
+/**
+ * events 디렉터리(또는 format 파일 하나)를 읽어 이벤트 파서 등록
+ */
+export async function loadEventFormats(path: string): Promise<FormatLoadReport> {
+    const result = await invoke<string>('load_event_formats', { path });
+    return JSON.parse(result);
+}
+
+export async function getEventParsers(): Promise<EventParser[]> {
+    const result = await invoke<string>('get_event_parsers');
+    return JSON.parse(result);
+}
+
+/**
+ * 등록된 이벤트 파서로 로그 파싱 (events를 지정하면 해당 이벤트만)
+ */
+export async function parseEventLog(
+    fname: string,
+    logfolder: string,
+    events?: string[]
+): Promise<EventParseReport> {
+    const result = await invoke<string>('parse_event_log', { fname, logfolder, events: events ?? null });
+    return JSON.parse(result);
+}
+
+/**
+ * 이벤트 테이블 (패턴/format 파일 없이 모든 ftrace 라인 보관)
+ * args의 key=value / key: value 쌍은 fields로 분리됨
+ */
+export interface FtraceEvent {
+    time: number;
+    cpu: number;
+    process: string;
+    pid: number;
+    flags: string;
+    event: string;
+    args: string;
+    fields: Record<string, string>;
+}
+
+export interface GenericParseReport {
+    filename: string;
+    total_lines: number;
+    rows: number;
+    unparsed_lines: number;
+    events: Record<string, number>;
+}
+
+export interface EventArgFilter {
+    key: string;
+    value?: string;
+    min?: number;
+    max?: number;
+}
+
+export interface EventFilter {
+    time_from?: number;
+    time_to?: number;
+    events?: string[];
+    process?: string;
+    pid?: number;
+    cpu?: number;
+    args_contains?: string;
+    arg_filters?: EventArgFilter[];
+    offset?: number;
+    limit?: number;
+}
+
+export interface EventSummary {
+    event: string;
+    count: number;
+    first_time: number;
+    last_time: number;
+    keys: string[];
+}
+
+/**
+ * 로그의 모든 ftrace 라인을 events parquet로 저장 (skipEvents에 지정한 이벤트 제외)
+ */
+export async function parseGenericEvents(
+    fname: string,
+    logfolder: string,
+    skipEvents?: string[]
+): Promise<GenericParseReport> {
+    const result = await invoke<string>('parse_generic_events', {
+        fname,
+        logfolder,
+        skipEvents: skipEvents ?? null
+    });
+    return JSON.parse(result);
+}
+
+export async function filterEvents(
+    logname: string,
+    filter: EventFilter
+): Promise<{ total: number; rows: FtraceEvent[] }> {
+    const result = await invoke<string>('filter_events', { logname, filter });
+    return JSON.parse(result);
+}
+
+export async function getEventSummary(logname: string): Promise<EventSummary[]> {
+    const result = await invoke<string>('event_summary', { logname });
+    return JSON.parse(result);
+}
+
+/**
+ * tracing_mark_write 마커 구간 (B/E 중첩, S/F 비동기) 및 카운터(C)
+ */
+export interface MarkerSection {
+    id: number;
+    name: string;
+    pid: number;
+    tid: number;
+    process: string;
+    depth: number;
+    parent: number | null;
+    is_async: boolean;
+    start_time: number;
+    end_time: number;
+    duration_ms: number;
+    closed: boolean;
+}
+
+export interface MarkerTimeline {
+    sections: MarkerSection[];
+    counters: { time: number; pid: number; name: string; value: number }[];
+    unmatched_ends: number;
+}
+
+export async function getMarkerSections(eventsLogname: string): Promise<MarkerTimeline> {
+    const result = await invoke<string>('marker_sections', { eventsLogname });
+    return JSON.parse(result);
+}
+
+/**
+ * 마커 구간별 latency/size/continuity 통계 (stats는 ufs_allstats/block_allstats와 같은 형식)
+ */
+export async function getMarkerSectionStats(
+    eventsLogname: string,
+    logname: string,
+    traceType: 'ufs' | 'block',
+    thresholds: string[],
+    sectionName?: string,
+    group?: boolean
+): Promise<{ section: MarkerSection; stats: any }[]> {
+    const result = await invoke<string>('marker_section_stats', {
+        eventsLogname,
+        logname,
+        traceType,
+        sectionName: sectionName ?? null,
+        thresholds,
+        group: group ?? null
+    });
+    return JSON.parse(result);
+}
@@ -13,11 +13,25 @@ import {
 export interface Pattern {
     id: number;
     name: string;
-    type: string;  // 'ufs' or 'block'
+    type: string;  // 'ufs', 'block' or 'ufscustom'
     pattern: string;
     description: string;
     is_active: boolean;
     created_at: string;
+    sample_lines: string | null;  // JSON array of sample lines (bundle export/import)
+}
+
+/**
+ * Sample lines stored with a pattern (empty if none or unreadable)
+ */
+export function patternSamples(pattern: Pattern): string[] {
+    if (!pattern.sample_lines) return [];
+    try {
+        const lines = JSON.parse(pattern.sample_lines);
+        return Array.isArray(lines) ? lines.filter(line => typeof line === 'string') : [];
+    } catch {
+        return [];
+    }
 }
 
 /**
@@ -57,10 +71,18 @@ export async function getPatternsFromRust(type?: string): Promise<Pattern[]> {
     }
 }
 
+export interface ActivePattern {
+    name: string;
+    pattern_type: string;
+    pattern: string;
+    is_active: boolean;
+    active_order: number | null;  // 0 = primary, 1.. = fallback
+}
+
 /**
- * Get active patterns from the Rust backend
+ * Get active patterns from the Rust backend (type -> patterns in the order they are tried)
  */
-export async function getActivePatterns(): Promise<Record<string, Pattern>> {
+export async function getActivePatterns(): Promise<Record<string, ActivePattern[]>> {
     try {
         const patternsJson = await invoke<string>('get_active_patterns');
         return JSON.parse(patternsJson);
@@ -77,18 +99,19 @@ export async function addPattern(
     name: string,
     type: string,
     pattern: string,
-    description: string
+    description: string,
+    sampleLines: string[] = []
 ): Promise<void> {
     try {
-        // First add to DB
-        await dbAddPattern(name, type, pattern, description);
-        
-        // Then add to Rust backend
+        // First add to Rust backend (validates regex and required named groups)
         await invoke('add_pattern', {
             name,
             patternType: type,
             pattern
         });
+        
+        // Then add to DB
+        await dbAddPattern(name, type, pattern, description, sampleLines);
     } catch (error) {
         console.error('Error adding pattern:', error);
         throw error;
@@ -102,7 +125,8 @@ export async function updateExistingPattern(
     id: number,
     name: string,
     pattern: string,
-    description: string
+    description: string,
+    sampleLines: string[] = []
 ): Promise<void> {
     try {
         const patterns = await getPatterns();
@@ -113,7 +137,7 @@ export async function updateExistingPattern(
         }
         
         // Update in DB
-        await dbUpdatePattern(id, name, pattern, description);
+        await dbUpdatePattern(id, name, pattern, description, sampleLines);
         
         // Delete old pattern from Rust backend
         await invoke('delete_pattern', {
@@ -165,6 +189,96 @@ export async function setActivePattern(id: number): Promise<void> {
     }
 }
 
+/**
+ * Set the ordered list of active patterns for a type in the Rust backend
+ * The first name is the primary pattern, the rest are fallbacks tried in order
+ */
+export async function setActivePatternOrder(type: string, names: string[]): Promise<void> {
+    try {
+        await invoke('set_active_patterns', {
+            names,
+            patternType: type
+        });
+    } catch (error) {
+        console.error('Error setting active pattern order:', error);
+        throw error;
+    }
+}
+
+export interface PatternImportReport {
+    imported: {
+        name: string;
+        original_name: string;
+        pattern_type: string;
+        pattern: string;
+        description: string;
+        sample_lines: string[];
+        action: 'added' | 'renamed' | 'overwritten';
+    }[];
+    conflicts: {
+        name: string;
+        pattern_type: string;
+        existing_pattern: string;
+        incoming_pattern: string;
+    }[];
+    rejected: { name: string; pattern_type: string; reason: string }[];
+    unchanged: number;
+    aborted: boolean;
+}
+
+/**
+ * Export patterns to a JSON/TOML bundle file (format chosen by extension)
+ * Descriptions are taken from the DB
+ */
+export async function exportPatterns(path: string, patterns?: Pattern[]): Promise<number> {
+    try {
+        const source = patterns ?? await getPatterns();
+        return await invoke<number>('export_patterns', {
+            path,
+            items: source.map(p => ({
+                name: p.name,
+                pattern_type: p.type,
+                description: p.description || '',
+                sample_lines: patternSamples(p)
+            }))
+        });
+    } catch (error) {
+        console.error('Error exporting patterns:', error);
+        throw error;
+    }
+}
+
+/**
+ * Import a pattern bundle into the Rust backend and mirror the result into the DB
+ * onConflict: undefined (report conflicts only), 'rename', 'overwrite' or 'skip'
+ */
+export async function importPatterns(
+    path: string,
+    onConflict?: 'rename' | 'overwrite' | 'skip'
+): Promise<PatternImportReport> {
+    try {
+        const reportJson = await invoke<string>('import_patterns', { path, onConflict });
+        const report: PatternImportReport = JSON.parse(reportJson);
+        
+        const dbPatterns = await getPatterns();
+        for (const imported of report.imported) {
+            const existing = dbPatterns.find(
+                p => p.name === imported.name && p.type === imported.pattern_type
+            );
+            if (existing) {
+                await dbUpdatePattern(existing.id, imported.name, imported.pattern, imported.description, imported.sample_lines);
+            } else {
+                await dbAddPattern(imported.name, imported.pattern_type, imported.pattern, imported.description, imported.sample_lines);
+            }
+        }
+        
+        return report;
+    } catch (error) {
+        console.error('Error importing patterns:', error);
+        throw error;
+    }
+}
+
 /**
  * Delete a pattern from both DB and Rust backend
  */
@@ -183,14 +297,14 @@ export async function deletePatternById(id: number): Promise<void> {
             throw new Error('Cannot delete an active pattern. Make another pattern active first.');
         }
         
-        // Delete from DB
-        await dbDeletePattern(id);
-        
-        // Delete from Rust backend
+        // Delete from Rust backend first (refuses patterns used as fallback)
         await invoke('delete_pattern', {
             name: patternToDelete.name,
             patternType: patternToDelete.type
         });
+        
+        // Delete from DB
+        await dbDeletePattern(id);
     } catch (error) {
         console.error('Error deleting pattern:', error);
         throw error;
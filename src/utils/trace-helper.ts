@@ -7,6 +7,30 @@ export async function fetchTraceLengths(logname: string) {
   return await invoke('trace_lengths', { logname });
 }
 
+// parquet footer에 기록된 출처 정보 (원본 로그, 패턴, 파서, 파싱 리포트)
+export async function fetchTraceInfo(logname: string) {
+  const result = await invoke<string>('trace_info', { logname });
+  return JSON.parse(result);
+}
+
+// 트레이스 time -> 실제 시각 변환 정보 (wall clock = time + offset, 없으면 null)
+export async function fetchClockSync(logname: string) {
+  const result = await invoke<string>('get_clock_sync', { logname });
+  return JSON.parse(result);
+}
+
+// (트레이스 time, 실제 시각) 쌍으로 offset 저장 - wallClock은 RFC 3339 문자열 또는 epoch 초
+export async function saveClockSync(logname: string, uptime: number, wallClock: string) {
+  const result = await invoke<string>('set_clock_sync', { logname, uptime, wallClock });
+  return JSON.parse(result);
+}
+
+// UFS orphan send/complete, tag 재사용 리포트 (해당 요청은 레이턴시 통계에서 제외됨)
+export async function fetchUfsTagAnomalies(logname: string) {
+  const result = await invoke<string>('ufs_tag_anomalies', { logname });
+  return JSON.parse(result);
+}
+
 // 공통으로 사용되는 지연시간 임계값 상수
 export const THRESHOLDS = [
   '0.1ms', '0.5ms', '1ms', '5ms', '10ms', '50ms', '100ms', 
@@ -35,11 +59,33 @@ export async function fetchUfsStats(fileName: string, filterParams: any) {
   }
 }
 
+/**
+ * UFS 하드웨어 큐(hwqid, MCQ)별 통계 - 큐 불균형 / 큐별 완료 간격
+ */
+export async function fetchUfsHwqStats(fileName: string, filterParams: any) {
+  const { from_time, to_time, from_lba, to_lba, zoom_column } = filterParams;
+  try {
+    const result = await invoke('ufs_hwq_stats', {
+      logname: fileName,
+      zoomColumn: zoom_column,
+      timeFrom: from_time,
+      timeTo: to_time,
+      colFrom: from_lba,
+      colTo: to_lba,
+      thresholds: THRESHOLDS
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching UFS hwq stats:', error);
+    throw error;
+  }
+}
+
 /**
  * Block 관련 통계 데이터를 가져오는 함수
  */
 export async function fetchBlockStats(fileName: string, filterParams: any) {
-  const { from_time, to_time, from_lba, to_lba, zoom_column } = filterParams;
+  const { from_time, to_time, from_lba, to_lba, zoom_column, device, group_by_device } = filterParams;
   try {
     const result = await invoke('block_allstats', {
       logname: fileName,
@@ -49,7 +95,9 @@ export async function fetchBlockStats(fileName: string, filterParams: any) {
       colFrom: from_lba,
       colTo: to_lba,
       thresholds: THRESHOLDS,
-      group: true
+      group: true,
+      device: device || null,
+      groupByDevice: group_by_device ?? false
     });
     return validateAllStats(result);
   } catch (error) {
@@ -58,6 +106,204 @@ export async function fetchBlockStats(fileName: string, filterParams: any) {
   }
 }
 
+// Block 트레이스의 장치(major:minor) 목록 - 장치 필터 선택용
+export async function fetchBlockDevices(logname: string) {
+  const result = await invoke<string>('block_devices', { logname });
+  return JSON.parse(result);
+}
+
+/**
+ * 장치 busy / idle 분석 - 사용률, idle gap 분포, 가장 긴 busy 구간
+ * traceType: 'ufs' | 'block' | 'ufscustom'
+ */
+export async function fetchBusyIdleStats(fileName: string, traceType: string, filterParams: any, topN: number = 10) {
+  const { from_time, to_time, device } = filterParams;
+  try {
+    const result = await invoke<string>('busy_idle_stats', {
+      logname: fileName,
+      traceType,
+      timeFrom: from_time,
+      timeTo: to_time,
+      thresholds: THRESHOLDS,
+      topN,
+      device: device || null
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching busy/idle stats:', error);
+    throw error;
+  }
+}
+
+/**
+ * iostat 형식 구간 리포트 - 구간(intervalMs) / op별 IOPS, MB/s, 평균 / p99 레이턴시, 평균 QD, 사용률, R/W 비율
+ * traceType: 'ufs' | 'block'
+ */
+export async function fetchIostatReport(fileName: string, traceType: string, filterParams: any, intervalMs: number = 100) {
+  const { from_time, to_time, device } = filterParams;
+  try {
+    const result = await invoke<string>('iostat_report', {
+      logname: fileName,
+      traceType,
+      timeFrom: from_time,
+      timeTo: to_time,
+      intervalMs,
+      group: true,
+      device: device || null
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching iostat report:', error);
+    throw error;
+  }
+}
+
+/**
+ * Block 요청 -> UFS 명령 매칭 - host 오버헤드(block issue -> UFS send, UFS complete -> block complete), comm별 UFS 명령
+ */
+export async function fetchBlockUfsMatch(fileName: string, filterParams: any, toleranceMs: number = 0) {
+  const { from_time, to_time, device } = filterParams;
+  try {
+    const result = await invoke<string>('block_ufs_match', {
+      logname: fileName,
+      timeFrom: from_time,
+      timeTo: to_time,
+      toleranceMs,
+      device: device || null
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching block-UFS match:', error);
+    throw error;
+  }
+}
+
+/**
+ * 가장 느린 요청 N개와 주변 상황 (issue / complete QD, in-flight 요청, 선행 flush / discard / unmap, 전원 / 유실 마커)
+ * traceType: 'ufs' | 'block' | 'ufscustom', metric: 'dtoc' | 'ctod' | 'ctoc'
+ * 각 요청의 index / issue_index는 트레이스 전체 기준 행 번호
+ */
+export async function fetchTopSlowRequests(
+  fileName: string,
+  traceType: string,
+  filterParams: any,
+  metric: string = 'dtoc',
+  topN: number = 20,
+  windowMs: number = 100,
+  eventsLogname: string | null = null
+) {
+  const { from_time, to_time } = filterParams;
+  try {
+    const result = await invoke<string>('top_slow_requests', {
+      logname: fileName,
+      traceType,
+      metric,
+      topN,
+      timeFrom: from_time,
+      timeTo: to_time,
+      windowMs,
+      eventsLogname
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching slow requests:', error);
+    throw error;
+  }
+}
+
+/**
+ * flush / FUA / discard / UNMAP 영향 분석 - 명령 레이턴시, 직후 nextN개 / windowMs 동안 요청의 baseline 대비 레이턴시,
+ * intervalMs 구간별 discard / UNMAP 해제량
+ * traceType: 'ufs' | 'block'
+ */
+export async function fetchBarrierImpact(
+  fileName: string,
+  traceType: string,
+  filterParams: any,
+  nextN: number = 16,
+  windowMs: number = 10,
+  intervalMs: number = 1000
+) {
+  const { from_time, to_time, device } = filterParams;
+  try {
+    const result = await invoke<string>('barrier_impact', {
+      logname: fileName,
+      traceType,
+      timeFrom: from_time,
+      timeTo: to_time,
+      nextN,
+      windowMs,
+      intervalMs,
+      device: device || null
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching barrier impact:', error);
+    throw error;
+  }
+}
+
+/**
+ * LBA hot / cold 히트맵 - [시간 구간][주소 구간] 행렬 (read / write 횟수, 바이트, 평균 / 최대 레이턴시)
+ * traceType: 'ufs' | 'block' | 'ufscustom', intervalMs가 null이면 트레이스 전체를 한 구간으로 집계
+ * block은 장치(filterParams.device)를 고르지 않으면 장치가 하나인 트레이스만 허용
+ */
+export async function fetchLbaHeatmap(
+  fileName: string,
+  traceType: string,
+  filterParams: any,
+  addrBins: number = 256,
+  intervalMs: number | null = null
+) {
+  const { from_time, to_time, from_lba, to_lba, device } = filterParams;
+  try {
+    const result = await invoke<string>('lba_heatmap', {
+      logname: fileName,
+      traceType,
+      timeFrom: from_time,
+      timeTo: to_time,
+      colFrom: from_lba,
+      colTo: to_lba,
+      addrBins,
+      intervalMs,
+      device: device || null
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching LBA heatmap:', error);
+    throw error;
+  }
+}
+
+/**
+ * 4KB 단위 재사용 거리 / working set 분석 - read / write 재사용 거리 히스토그램(캐시 크기별 hit 비율),
+ * windowMs 구간(stepMs 간격)별 working set 크기, read-after-write / overwrite 비율
+ * traceType: 'ufs' | 'block'
+ */
+export async function fetchReuseDistance(
+  fileName: string,
+  traceType: string,
+  filterParams: any,
+  windowMs: number = 1000,
+  stepMs: number | null = null
+) {
+  const { from_time, to_time } = filterParams;
+  try {
+    const result = await invoke<string>('reuse_distance', {
+      logname: fileName,
+      traceType,
+      timeFrom: from_time,
+      timeTo: to_time,
+      windowMs,
+      stepMs
+    });
+    return parseJsonResult(result);
+  } catch (error) {
+    console.error('Error fetching reuse distance:', error);
+    throw error;
+  }
+}
+
 /**
  * UFSCUSTOM 관련 통계 데이터를 가져오는 함수
  */